@@ -1,12 +1,27 @@
 # Converts data/blockcounts-categories.tsv into
 # CCNATURAL_COLORS_BLOCK_BIOME and CCNATURAL_COLORS_BLOCK_DEFAULT
+#
+# The TSV has legacy (pre-1.13) block names, they are converted to the modern ones
+# with flattened_block_names.tsv, which lists the names `mc::flattening` turns
+# each legacy block into, without namespace and properties.
 
+import os
 import sys
 from collections import defaultdict
 
+# read flattening table
+
+flattened = defaultdict(list)  # legacy block -> modern blocks
+
+with open(os.path.join(os.path.dirname(__file__), 'flattened_block_names.tsv')) as f:
+    f.readline()  # skip header
+    for line in f:
+        legacy, modern = line.strip().split('\t')
+        flattened[legacy].append(modern)
+
 # read tsv
 
-blocks = defaultdict(lambda: defaultdict(list))  # block -> category -> biomes
+legacy_blocks = defaultdict(lambda: defaultdict(list))  # block -> category -> biomes
 
 sys.stdin.readline()  # skip header
 
@@ -19,7 +34,22 @@ for line in sys.stdin:
         category = 'unknown'
     biomeid = int(biomeid)
 
-    blocks[block][category].append(biomeid)
+    legacy_blocks[block][category].append(biomeid)
+
+# convert to modern names
+
+# a modern block takes the categories of one legacy block only:
+# the one with the same name, else the one with the fewest variants,
+# so `dead_bush` is classified like `deadbush`, not like `tallgrass[type=dead_bush]`
+sources = {}  # modern block -> (priority, legacy block)
+for legacy in sorted(legacy_blocks):
+    moderns = flattened.get(legacy, [legacy])
+    for modern in moderns:
+        priority = (legacy != modern, len(moderns))
+        if modern not in sources or priority < sources[modern][0]:
+            sources[modern] = (priority, legacy)
+
+blocks = {modern: legacy_blocks[legacy] for modern, (_, legacy) in sources.items()}
 
 # calculate primary category
 
@@ -0,0 +1,1447 @@
+extern crate flate2;
+
+use self::flate2::read::{GzDecoder, ZlibDecoder};
+use self::flate2::write::{GzEncoder, ZlibEncoder};
+use self::flate2::Compression;
+use crate::mc::biomes::get_biome_by_legacy_id;
+use crate::mc::block_registry::BlockRegistry;
+use crate::mc::blocks::{
+    get_legacy_block_state, BlockState, BLOCK_STRINGS_ARR, UNKNOWN_BLOCK_NAME,
+};
+use crate::nbt::{
+    byte_array, compound, get_byte, get_byte_array, get_int, get_int_array, get_key, get_list,
+    get_long_array, get_string, int_array, read_named_tag, write_named_tag, Tag,
+};
+use crate::tile::{column_nr_of_pos, Tile, TilePos};
+use crate::{CHUNK_COLUMNS, CHUNK_HEIGHT, CHUNK_WIDTH, REGION_WIDTH, TILE_WIDTH};
+use byteorder::{BigEndian, ByteOrder, WriteBytesExt};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// a region file has 32x32 chunks
+pub const ANVIL_REGION_CHUNKS: usize = REGION_WIDTH / CHUNK_WIDTH;
+/// a chunk has 16 sections of 16x16x16 blocks
+pub const ANVIL_SECTION_HEIGHT: usize = 16;
+pub const ANVIL_WORLD_HEIGHT: usize = 256;
+pub const ANVIL_SECTION_BLOCKS: usize = ANVIL_SECTION_HEIGHT * CHUNK_COLUMNS;
+/// region files are made of 4KiB sectors, the first two are the header
+pub const ANVIL_SECTOR_BYTES: usize = 4096;
+pub const ANVIL_COMPRESSION_GZIP: u8 = 1;
+pub const ANVIL_COMPRESSION_ZLIB: u8 = 2;
+/// since 1.15.1
+pub const ANVIL_COMPRESSION_NONE: u8 = 3;
+/// `version` in level.dat of Anvil worlds
+pub const ANVIL_VERSION: i32 = 19133;
+/// 17w47a, the first version with block palettes instead of numeric ids
+pub const FLATTENING_DATA_VERSION: i32 = 1451;
+/// biomes are stored for 4x4x4 blocks since 1.15
+pub const BIOME_CELL_WIDTH: usize = 4;
+
+/// `Status` of chunks that are fully generated, without `minecraft:`, 1.13 had the latter two
+const FULL_CHUNK_STATUSES: &[&str] = &["full", "fullchunk", "postprocessed"];
+const AIR_BLOCK_NAMES: &[&str] = &["minecraft:air", "minecraft:cave_air", "minecraft:void_air"];
+
+lazy_static! {
+    /// tells water, lava and see-through blocks of modern chunks apart, like the colorizers do
+    static ref BLOCKS: BlockRegistry = BlockRegistry::new();
+}
+
+const BEDROCK: u16 = 7 << 4;
+
+/// Legacy block ids whose material doesn't block movement, which rain falls through.
+/// Liquids are not listed, as they stop rain.
+const RAIN_PASSING_BLOCK_IDS: &[u16] = &[
+    0,   // air
+    6,   // sapling
+    27,  // golden_rail
+    28,  // detector_rail
+    30,  // web
+    31,  // tallgrass
+    32,  // deadbush
+    37,  // yellow_flower
+    38,  // red_flower
+    39,  // brown_mushroom
+    40,  // red_mushroom
+    50,  // torch
+    51,  // fire
+    55,  // redstone_wire
+    59,  // wheat
+    63,  // standing_sign
+    65,  // ladder
+    66,  // rail
+    68,  // wall_sign
+    69,  // lever
+    70,  // stone_pressure_plate
+    72,  // wooden_pressure_plate
+    75,  // unlit_redstone_torch
+    76,  // redstone_torch
+    77,  // stone_button
+    78,  // snow_layer
+    83,  // reeds
+    90,  // portal
+    93,  // unpowered_repeater
+    94,  // powered_repeater
+    104, // pumpkin_stem
+    105, // melon_stem
+    106, // vine
+    111, // waterlily
+    115, // nether_wart
+    119, // end_portal
+    131, // tripwire_hook
+    132, // tripwire
+    140, // flower_pot
+    141, // carrots
+    142, // potatoes
+    143, // wooden_button
+    144, // skull
+    147, // light_weighted_pressure_plate
+    148, // heavy_weighted_pressure_plate
+    149, // unpowered_comparator
+    150, // powered_comparator
+    157, // activator_rail
+    171, // carpet
+    175, // double_plant
+    176, // standing_banner
+    177, // wall_banner
+    198, // end_rod
+    207, // beetroots
+    217, // structure_void
+];
+
+fn is_water(block_id: u16) -> bool {
+    block_id == 8 || block_id == 9
+}
+
+fn is_lava(block_id: u16) -> bool {
+    block_id == 10 || block_id == 11
+}
+
+/// Blocks, light and biomes of a pre-1.13 chunk.
+/// Blocks and light are indexed `y * 256 + z * 16 + x` like in Anvil's sections,
+/// biomes and height map `z * 16 + x`.
+pub struct LegacyChunk {
+    pub pos: TilePos,
+    /// `id << 4 | meta`, like `BLOCK_STRINGS_ARR`
+    pub blocks: Vec<u16>,
+    /// blockLight + skyLight*16
+    pub light: Vec<u8>,
+    pub biomes: Vec<u8>,
+    /// y above the highest light blocking block
+    pub height_map: Vec<i32>,
+}
+
+impl LegacyChunk {
+    pub fn new(pos: TilePos) -> Self {
+        Self {
+            pos,
+            blocks: vec![0; ANVIL_WORLD_HEIGHT * CHUNK_COLUMNS],
+            // sections that aren't stored are empty, and in full sky light if there is a sky
+            light: vec![0xf0; ANVIL_WORLD_HEIGHT * CHUNK_COLUMNS],
+            biomes: vec![0; CHUNK_COLUMNS],
+            height_map: vec![0; CHUNK_COLUMNS],
+        }
+    }
+
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> u16 {
+        self.blocks[get_block_index(x, y, z)]
+    }
+
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, block: u16) {
+        self.blocks[get_block_index(x, y, z)] = block;
+    }
+
+    /// The light byte of the block above, where VoxelMap takes a layer's light from.
+    pub fn get_light_above(&self, x: usize, y: usize, z: usize) -> u8 {
+        if y + 1 >= ANVIL_WORLD_HEIGHT {
+            return 0xf0;
+        }
+        self.light[get_block_index(x, y + 1, z)]
+    }
+}
+
+fn get_block_index(x: usize, y: usize, z: usize) -> usize {
+    x + z * CHUNK_WIDTH + y * CHUNK_COLUMNS
+}
+
+/// Blocks, light and biomes of a chunk saved by 1.13 or later.
+/// Blocks and light are indexed `(y - min_y) * 256 + z * 16 + x`,
+/// biomes `(y - min_y) / 4 * 256 + z * 16 + x`, which also fits the 2D biomes before 1.15.
+pub struct ModernChunk {
+    pub pos: TilePos,
+    /// y of the lowest section, negative since 1.18
+    pub min_y: i32,
+    /// number of blocks from `min_y` up
+    pub height: usize,
+    /// indices into `block_states`, 0 is air
+    pub blocks: Vec<u16>,
+    pub block_states: Vec<&'static BlockState>,
+    /// blockLight + skyLight*16
+    pub light: Vec<u8>,
+    /// indices into `biome_names`, 0 is unknown
+    pub biomes: Vec<u16>,
+    pub biome_names: Vec<String>,
+    /// from `min_y` to above the highest non-air block, `z * 16 + x`,
+    /// from the `WORLD_SURFACE` height map if the chunk has it
+    pub surface_heights: Option<Vec<u16>>,
+}
+
+impl ModernChunk {
+    pub fn new(pos: TilePos, min_y: i32, height: usize) -> Self {
+        Self {
+            pos,
+            min_y,
+            height,
+            blocks: vec![0; height * CHUNK_COLUMNS],
+            block_states: vec![BlockState::intern(AIR_BLOCK_NAMES[0])],
+            light: vec![0xf0; height * CHUNK_COLUMNS],
+            biomes: vec![0; height / BIOME_CELL_WIDTH * CHUNK_COLUMNS],
+            biome_names: vec![String::new()],
+            surface_heights: None,
+        }
+    }
+
+    /// `y` from `min_y` up
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> u16 {
+        self.blocks[get_block_index(x, y, z)]
+    }
+
+    pub fn get_block_state(&self, x: usize, y: usize, z: usize) -> &'static BlockState {
+        self.block_states[self.get_block(x, y, z) as usize]
+    }
+
+    /// Air of any kind is 0.
+    pub fn get_or_add_block(&mut self, block_name: &str) -> u16 {
+        if AIR_BLOCK_NAMES.contains(&block_name) {
+            return 0;
+        }
+        let state = BlockState::intern(block_name);
+        match self.block_states.iter().position(|s| *s == state) {
+            Some(id) => id as u16,
+            None => {
+                self.block_states.push(state);
+                (self.block_states.len() - 1) as u16
+            }
+        }
+    }
+
+    pub fn get_or_add_biome(&mut self, biome_name: &str) -> u16 {
+        match self.biome_names.iter().position(|name| name == biome_name) {
+            Some(id) => id as u16,
+            None => {
+                self.biome_names.push(biome_name.to_string());
+                (self.biome_names.len() - 1) as u16
+            }
+        }
+    }
+
+    /// `y` from `min_y` up, empty if unknown
+    pub fn get_biome_name(&self, x: usize, y: usize, z: usize) -> &str {
+        let cell_y = y / BIOME_CELL_WIDTH;
+        &self.biome_names[self.biomes[x + z * CHUNK_WIDTH + cell_y * CHUNK_COLUMNS] as usize]
+    }
+
+    /// Sets the biome of all columns in the 4x4 cell at `cell_x`,`cell_z`.
+    fn set_biome_cell(&mut self, cell_x: usize, cell_y: usize, cell_z: usize, biome: u16) {
+        if cell_y >= self.height / BIOME_CELL_WIDTH {
+            return;
+        }
+        for z in cell_z * BIOME_CELL_WIDTH..(cell_z + 1) * BIOME_CELL_WIDTH {
+            for x in cell_x * BIOME_CELL_WIDTH..(cell_x + 1) * BIOME_CELL_WIDTH {
+                self.biomes[x + z * CHUNK_WIDTH + cell_y * CHUNK_COLUMNS] = biome;
+            }
+        }
+    }
+
+    /// The light byte of the block above, where VoxelMap takes a layer's light from.
+    pub fn get_light_above(&self, x: usize, y: usize, z: usize) -> u8 {
+        if y + 1 >= self.height {
+            return 0xf0;
+        }
+        self.light[get_block_index(x, y + 1, z)]
+    }
+}
+
+/// A chunk of a region file, in the format of the version that saved it.
+pub enum AnvilChunk {
+    Legacy(LegacyChunk),
+    Modern(ModernChunk),
+}
+
+impl AnvilChunk {
+    pub fn pos(&self) -> TilePos {
+        match self {
+            AnvilChunk::Legacy(chunk) => chunk.pos,
+            AnvilChunk::Modern(chunk) => chunk.pos,
+        }
+    }
+}
+
+/// Anvil names region files `r.<x>.<z>.mca`.
+pub fn get_xz_from_anvil_path(region_path: &Path) -> Result<TilePos, String> {
+    let fname = region_path
+        .file_name()
+        .and_then(|fname| fname.to_str())
+        .ok_or("no file name".to_owned())?;
+    let parts: Vec<_> = fname.split('.').collect();
+    if parts.len() != 4 || parts[0] != "r" || parts[3] != "mca" {
+        return Err(format!("{} is not named r.<x>.<z>.mca", fname));
+    }
+    let parse = |coord: &str| {
+        coord
+            .parse()
+            .map_err(|e: std::num::ParseIntError| e.to_string())
+    };
+    Ok((parse(parts[1])?, parse(parts[2])?))
+}
+
+/// Reads all chunks of a region file that have been fully generated.
+/// Chunks that are in the file but can't be read are an error.
+pub fn read_anvil_region(region_path: &PathBuf) -> Result<Vec<AnvilChunk>, String> {
+    let region = fs::read(region_path).map_err(|e| e.to_string())?;
+    if region.len() < 2 * ANVIL_SECTOR_BYTES {
+        return Err(format!("region file has only {} bytes", region.len()));
+    }
+    let mut chunks = Vec::new();
+    for z in 0..ANVIL_REGION_CHUNKS {
+        for x in 0..ANVIL_REGION_CHUNKS {
+            let chunk = read_region_chunk(&region, x + z * ANVIL_REGION_CHUNKS)
+                .and_then(|chunk| match chunk {
+                    Some(chunk) => parse_anvil_chunk(&chunk),
+                    None => Ok(None),
+                })
+                .map_err(|e| format!("chunk {},{} {}", x, z, e))?;
+            chunks.extend(chunk);
+        }
+    }
+    Ok(chunks)
+}
+
+/// The chunk at `x + z * 32` in the region, `None` if it was never generated.
+/// The first header sector has each chunk's location, which is 0 for chunks never generated.
+fn read_region_chunk(region: &[u8], index: usize) -> Result<Option<Tag>, String> {
+    let location = BigEndian::read_u32(&region[index * 4..index * 4 + 4]);
+    if location == 0 {
+        return Ok(None);
+    }
+    let offset = (location >> 8) as usize * ANVIL_SECTOR_BYTES;
+    let header = region
+        .get(offset..offset + 5)
+        .ok_or("is outside the region file")?;
+    // length includes the compression type
+    let length = BigEndian::read_u32(header) as usize;
+    let data = region
+        .get(offset + 5..offset + 4 + length.max(1))
+        .ok_or("is truncated")?;
+    let nbt = match header[4] {
+        ANVIL_COMPRESSION_GZIP => read_all(GzDecoder::new(data))?,
+        ANVIL_COMPRESSION_ZLIB => read_all(ZlibDecoder::new(data))?,
+        ANVIL_COMPRESSION_NONE => data.to_vec(),
+        compression if compression & 0x80 != 0 => {
+            return Err("is stored in a separate .mcc file".to_owned())
+        }
+        compression => return Err(format!("has unknown compression {}", compression)),
+    };
+    read_named_tag(&mut &nbt[..]).map(|(_name, chunk)| Some(chunk))
+}
+
+fn read_all<R: Read>(mut reader: R) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map_err(|e| e.to_string())?;
+    Ok(data)
+}
+
+/// Reads the `Level` of a chunk as saved by 1.12 and earlier.
+pub fn parse_legacy_chunk(chunk: &Tag) -> Result<LegacyChunk, String> {
+    let level = get_key(chunk, "Level")?;
+    let pos = match (get_key(level, "xPos")?, get_key(level, "zPos")?) {
+        (Tag::Int(x), Tag::Int(z)) => (*x, *z),
+        _ => return Err("chunk position is not an int".to_owned()),
+    };
+    let mut chunk = LegacyChunk::new(pos);
+    let mut stored_sections = Vec::new();
+    let mut has_sky = true;
+
+    for section in get_list(level, "Sections")? {
+        if get_key(section, "Palette").is_ok() {
+            return Err(format!("chunk {:?} was saved by 1.13 or later", pos));
+        }
+        let section_y = get_byte(section, "Y")?;
+        if section_y < 0 || section_y as usize >= ANVIL_WORLD_HEIGHT / ANVIL_SECTION_HEIGHT {
+            continue;
+        }
+        let ids = get_byte_array(section, "Blocks")?;
+        let add = get_byte_array(section, "Add").ok();
+        let metas = get_byte_array(section, "Data")?;
+        let block_light = get_byte_array(section, "BlockLight")?;
+        // only in dimensions with a sky
+        let sky_light = get_byte_array(section, "SkyLight").ok();
+        has_sky &= sky_light.is_some();
+        stored_sections.push(section_y as usize);
+        if ids.len() != ANVIL_SECTION_BLOCKS {
+            return Err(format!("section has {} blocks", ids.len()));
+        }
+
+        let offset = section_y as usize * ANVIL_SECTION_BLOCKS;
+        for (i, &id) in ids.iter().enumerate() {
+            let id = id as u16 | add.as_ref().map_or(0, |add| get_nibble(add, i) as u16) << 8;
+            chunk.blocks[offset + i] = id << 4 | get_nibble(&metas, i) as u16;
+            let sky = sky_light.as_ref().map_or(0, |sky| get_nibble(sky, i));
+            chunk.light[offset + i] = get_nibble(&block_light, i) | sky << 4;
+        }
+    }
+
+    if !has_sky {
+        for section_y in 0..ANVIL_WORLD_HEIGHT / ANVIL_SECTION_HEIGHT {
+            if !stored_sections.contains(&section_y) {
+                let offset = section_y * ANVIL_SECTION_BLOCKS;
+                for light in &mut chunk.light[offset..offset + ANVIL_SECTION_BLOCKS] {
+                    *light = 0;
+                }
+            }
+        }
+    }
+
+    if let Ok(biomes) = get_byte_array(level, "Biomes") {
+        if biomes.len() == CHUNK_COLUMNS {
+            chunk.biomes = biomes;
+        }
+    }
+    chunk.height_map = get_int_array(level, "HeightMap")?;
+    if chunk.height_map.len() != CHUNK_COLUMNS {
+        return Err(format!("HeightMap has {} entries", chunk.height_map.len()));
+    }
+    Ok(chunk)
+}
+
+fn get_nibble(nibbles: &[u8], index: usize) -> u8 {
+    let byte = nibbles.get(index >> 1).cloned().unwrap_or(0);
+    if index & 1 == 0 {
+        byte & 0xf
+    } else {
+        byte >> 4
+    }
+}
+
+/// Reads a chunk in the format of the version that saved it, by its `DataVersion`,
+/// which 1.8 and earlier didn't save.
+/// `None` if it isn't fully generated or has no sections.
+pub fn parse_anvil_chunk(chunk: &Tag) -> Result<Option<AnvilChunk>, String> {
+    let data_version = get_int(chunk, "DataVersion").unwrap_or(0);
+    if data_version < FLATTENING_DATA_VERSION {
+        parse_legacy_chunk(chunk).map(|chunk| Some(AnvilChunk::Legacy(chunk)))
+    } else {
+        parse_modern_chunk(chunk).map(|chunk| chunk.map(AnvilChunk::Modern))
+    }
+}
+
+/// Reads a chunk as saved by 1.13 and later, with block palettes.
+/// Until 1.17 the chunk is in `Level` with capitalized names,
+/// since 1.18 it's at the root, with palettes for biomes too.
+pub fn parse_modern_chunk(chunk: &Tag) -> Result<Option<ModernChunk>, String> {
+    let level = get_key(chunk, "Level").unwrap_or(chunk);
+    let is_at_root = level == chunk;
+    if let Ok(status) = get_string(level, "Status") {
+        if !FULL_CHUNK_STATUSES.contains(&status.trim_start_matches("minecraft:")) {
+            return Ok(None);
+        }
+    }
+    let pos = (get_int(level, "xPos")?, get_int(level, "zPos")?);
+    let sections = get_list(level, if is_at_root { "sections" } else { "Sections" })
+        .map(|sections| &sections[..])
+        .unwrap_or(&[]);
+    let section_ys = sections
+        .iter()
+        .map(|section| get_byte(section, "Y").map(|y| y as i32))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (min_section, max_section) = match (section_ys.iter().min(), section_ys.iter().max()) {
+        (Some(min), Some(max)) => (*min, *max),
+        _ => return Ok(None),
+    };
+    let min_y = min_section * ANVIL_SECTION_HEIGHT as i32;
+    let height = (max_section - min_section + 1) as usize * ANVIL_SECTION_HEIGHT;
+    let mut chunk = ModernChunk::new(pos, min_y, height);
+    let mut has_sky = false;
+
+    for (section, section_y) in sections.iter().zip(section_ys) {
+        let offset = (section_y - min_section) as usize * ANVIL_SECTION_BLOCKS;
+        let (palette, states) = match get_key(section, "block_states") {
+            Ok(block_states) => (
+                get_list(block_states, "palette"),
+                get_long_array(block_states, "data"),
+            ),
+            Err(_) => (
+                get_list(section, "Palette"),
+                get_long_array(section, "BlockStates"),
+            ),
+        };
+        // sections with only light have no palette
+        if let Ok(palette) = palette {
+            let ids = palette
+                .iter()
+                .map(|state| Ok(chunk.get_or_add_block(&get_block_state_name(state)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            let indices = match states {
+                Ok(states) => unpack_palette_indices(states, ids.len(), 4, ANVIL_SECTION_BLOCKS)?,
+                Err(_) => vec![0; ANVIL_SECTION_BLOCKS],
+            };
+            for (i, index) in indices.into_iter().enumerate() {
+                chunk.blocks[offset + i] = *ids
+                    .get(index as usize)
+                    .ok_or("block state is outside the palette")?;
+            }
+        }
+
+        // missing light is empty, except for the sky light in dimensions with a sky
+        let block_light = get_byte_array(section, "BlockLight").ok();
+        let sky_light = get_byte_array(section, "SkyLight").ok();
+        has_sky |= sky_light.is_some();
+        for i in 0..ANVIL_SECTION_BLOCKS {
+            let block = block_light.as_ref().map_or(0, |light| get_nibble(light, i));
+            let sky = sky_light.as_ref().map_or(15, |light| get_nibble(light, i));
+            chunk.light[offset + i] = block | sky << 4;
+        }
+
+        if let Ok(biomes) = get_key(section, "biomes") {
+            let names = get_list(biomes, "palette")?
+                .iter()
+                .map(|name| match name {
+                    Tag::String(name) => Ok(chunk.get_or_add_biome(name)),
+                    _ => Err("biome is not a string".to_owned()),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let cells = ANVIL_SECTION_BLOCKS / BIOME_CELL_WIDTH.pow(3);
+            let indices = match get_long_array(biomes, "data") {
+                Ok(data) => unpack_palette_indices(data, names.len(), 0, cells)?,
+                Err(_) => vec![0; cells],
+            };
+            let cell_y0 =
+                (section_y - min_section) as usize * ANVIL_SECTION_HEIGHT / BIOME_CELL_WIDTH;
+            for (i, index) in indices.into_iter().enumerate() {
+                let biome = *names
+                    .get(index as usize)
+                    .ok_or("biome is outside the palette")?;
+                chunk.set_biome_cell(i & 3, cell_y0 + (i >> 4), i >> 2 & 3, biome);
+            }
+        }
+    }
+
+    if !has_sky {
+        for light in &mut chunk.light {
+            *light &= 0xf;
+        }
+    }
+
+    if let Ok(biomes) = get_int_array(level, "Biomes") {
+        let biomes: Vec<u16> = biomes
+            .into_iter()
+            .map(|biome_id| match get_biome_by_legacy_id(biome_id as u16) {
+                Some(biome) => chunk.get_or_add_biome(biome.name),
+                None => 0,
+            })
+            .collect();
+        if biomes.len() == CHUNK_COLUMNS {
+            // 1.13 and 1.14 have one biome per column
+            for (i, biome) in biomes.into_iter().enumerate() {
+                for cell_y in 0..height / BIOME_CELL_WIDTH {
+                    chunk.biomes[i + cell_y * CHUNK_COLUMNS] = biome;
+                }
+            }
+        } else {
+            // 1.15 to 1.17 have 4x4x4 cells from y=0 up
+            let cell_y0 = -min_section as usize * ANVIL_SECTION_HEIGHT / BIOME_CELL_WIDTH;
+            for (i, biome) in biomes.into_iter().enumerate() {
+                chunk.set_biome_cell(i & 3, cell_y0 + (i >> 4), i >> 2 & 3, biome);
+            }
+        }
+    }
+
+    if let Ok(height_maps) = get_key(level, "Heightmaps") {
+        if let Ok(surface) = get_long_array(height_maps, "WORLD_SURFACE") {
+            chunk.surface_heights = Some(unpack_palette_indices(
+                surface,
+                height + 1,
+                0,
+                CHUNK_COLUMNS,
+            )?);
+        }
+    }
+    Ok(Some(chunk))
+}
+
+/// Such as `minecraft:oak_stairs[facing=east,half=bottom]`, with the properties sorted
+/// like VoxelMap does.
+fn get_block_state_name(state: &Tag) -> Result<String, String> {
+    let name = get_string(state, "Name")?;
+    let properties = match get_key(state, "Properties") {
+        Ok(Tag::Compound(properties)) if !properties.is_empty() => properties,
+        _ => return Ok(name.to_string()),
+    };
+    let mut properties: Vec<String> = properties
+        .iter()
+        .map(|(key, value)| match value {
+            Tag::String(value) => format!("{}={}", key, value),
+            _ => key.clone(),
+        })
+        .collect();
+    properties.sort();
+    Ok(format!("{}[{}]", name, properties.join(",")))
+}
+
+/// Unpacks `count` indices into a palette of `palette_len` entries,
+/// each with as many bits as the largest index needs, but at least `min_bits`.
+/// Until 1.16, indices continue into the next long, since then they don't,
+/// which is told apart by the number of longs.
+fn unpack_palette_indices(
+    longs: &[i64],
+    palette_len: usize,
+    min_bits: usize,
+    count: usize,
+) -> Result<Vec<u16>, String> {
+    let max_index = palette_len.saturating_sub(1) as u64;
+    let bits = ((64 - max_index.leading_zeros()) as usize).max(min_bits);
+    if bits == 0 {
+        return Ok(vec![0; count]);
+    }
+    let mask = (1u64 << bits) - 1;
+    let per_long = 64 / bits;
+    let mut indices = Vec::with_capacity(count);
+    if longs.len() == (count + per_long - 1) / per_long {
+        for i in 0..count {
+            let long = longs[i / per_long] as u64;
+            indices.push((long >> (i % per_long * bits) & mask) as u16);
+        }
+    } else if longs.len() == (count * bits + 63) / 64 {
+        for i in 0..count {
+            let (long_nr, shift) = (i * bits / 64, i * bits % 64);
+            let mut value = longs[long_nr] as u64 >> shift;
+            if shift + bits > 64 {
+                value |= (longs[long_nr + 1] as u64) << (64 - shift);
+            }
+            indices.push((value & mask) as u16);
+        }
+    } else {
+        return Err(format!(
+            "{} longs for {} indices of {} bits",
+            longs.len(),
+            count,
+            bits
+        ));
+    }
+    Ok(indices)
+}
+
+/// Converts the chunks of one region to the four VoxelMap tiles it covers,
+/// in z-then-x order. Tiles without any chunks are left out.
+pub fn anvil_chunks_to_tiles(chunks: &[AnvilChunk]) -> Vec<Tile> {
+    let chunks_per_tile = (TILE_WIDTH / CHUNK_WIDTH) as i32;
+    let mut tiles: HashMap<TilePos, Tile> = HashMap::new();
+    for chunk in chunks {
+        let (chunk_x, chunk_z) = chunk.pos();
+        let tile_pos = (
+            chunk_x.div_euclid(chunks_per_tile),
+            chunk_z.div_euclid(chunks_per_tile),
+        );
+        let tile = tiles
+            .entry(tile_pos)
+            .or_insert_with(|| Tile::new(Some(tile_pos)));
+        let x0 = chunk_x.rem_euclid(chunks_per_tile) as usize * CHUNK_WIDTH;
+        let z0 = chunk_z.rem_euclid(chunks_per_tile) as usize * CHUNK_HEIGHT;
+        for z in 0..CHUNK_HEIGHT {
+            for x in 0..CHUNK_WIDTH {
+                let column_nr = column_nr_of_pos(x0 + x, z0 + z);
+                match chunk {
+                    AnvilChunk::Legacy(chunk) => {
+                        set_column_from_chunk(tile, column_nr, chunk, x, z)
+                    }
+                    AnvilChunk::Modern(chunk) => {
+                        set_column_from_modern_chunk(tile, column_nr, chunk, x, z)
+                    }
+                }
+            }
+        }
+    }
+    let mut tiles: Vec<_> = tiles.into_values().collect();
+    tiles.sort_by_key(|tile| tile.pos.map(|(x, z)| (z, x)));
+    tiles
+}
+
+/// Computes VoxelMap's four layers, see `voxelmap-cache-format.md`:
+/// the surface is the highest light blocking block (from the height map) or lava,
+/// the ocean floor is the highest non-water block below surface water,
+/// the transparent layer is the highest rain blocking block above the surface,
+/// and the foliage is the block right above the surface.
+fn set_column_from_chunk(
+    tile: &mut Tile,
+    column_nr: usize,
+    chunk: &LegacyChunk,
+    x: usize,
+    z: usize,
+) {
+    let height_map = chunk.height_map[x + z * CHUNK_WIDTH];
+    if height_map <= 0 {
+        // void, nothing to see
+        return;
+    }
+    let height_map_y = (height_map as usize).min(ANVIL_WORLD_HEIGHT) - 1;
+    // lava doesn't block light, but VoxelMap counts it as surface
+    let surface_y = (height_map_y + 1..ANVIL_WORLD_HEIGHT)
+        .rev()
+        .find(|&y| is_lava(chunk.get_block(x, y, z) >> 4))
+        .unwrap_or(height_map_y);
+    let get_layer = |tile: &mut Tile, layer_y: usize| {
+        let block = chunk.get_block(x, layer_y, z);
+        // ids above 255 are from mods
+        let name = BLOCK_STRINGS_ARR
+            .get(block as usize)
+            .unwrap_or(&UNKNOWN_BLOCK_NAME);
+        let id = tile.get_or_add_key(name);
+        // VoxelMap stores the height above the block
+        (layer_y as i32 + 1, id, chunk.get_light_above(x, layer_y, z))
+    };
+
+    let (height, id, light) = get_layer(tile, surface_y);
+    tile.set_height(column_nr, height);
+    tile.set_blockstate(column_nr, id);
+    tile.set_light(column_nr, light);
+
+    if is_water(chunk.get_block(x, surface_y, z) >> 4) {
+        let floor_y = (0..surface_y).rev().find(|&y| {
+            let block_id = chunk.get_block(x, y, z) >> 4;
+            block_id != 0 && !is_water(block_id)
+        });
+        if let Some(floor_y) = floor_y {
+            let (height, id, light) = get_layer(tile, floor_y);
+            tile.set_ocean_floor_height(column_nr, height);
+            tile.set_ocean_floor_blockstate(column_nr, id);
+            tile.set_ocean_floor_light(column_nr, light);
+        }
+    }
+
+    let rain_y = (surface_y + 1..ANVIL_WORLD_HEIGHT).rev().find(|&y| {
+        let block_id = chunk.get_block(x, y, z) >> 4;
+        !RAIN_PASSING_BLOCK_IDS.contains(&block_id)
+    });
+    if let Some(rain_y) = rain_y {
+        let (height, id, light) = get_layer(tile, rain_y);
+        tile.set_transparent_height(column_nr, height);
+        tile.set_transparent_blockstate(column_nr, id);
+        tile.set_transparent_light(column_nr, light);
+    }
+
+    if surface_y + 1 < ANVIL_WORLD_HEIGHT && chunk.get_block(x, surface_y + 1, z) != 0 {
+        let (height, id, light) = get_layer(tile, surface_y + 1);
+        tile.set_foliage_height(column_nr, height);
+        tile.set_foliage_blockstate(column_nr, id);
+        tile.set_foliage_light(column_nr, light);
+    }
+
+    let biome_id = chunk.biomes[x + z * CHUNK_WIDTH] as u16;
+    // worlds upgraded to 1.13 or later have chunks of both formats
+    if tile.biome_keys.is_empty() {
+        tile.set_biome_id(column_nr, biome_id);
+    } else {
+        let biome_id = get_biome_by_legacy_id(biome_id)
+            .map_or(0, |biome| tile.get_or_add_biome_key(biome.name));
+        tile.set_biome_id(column_nr, biome_id);
+    }
+}
+
+/// Like `set_column_from_chunk`, telling blocks apart by `BlockRegistry`:
+/// the surface is the highest block that isn't see-through, or water or lava,
+/// the ocean floor is the highest non-water block below surface water,
+/// the transparent layer is the highest solid block above the surface,
+/// and the foliage is the block right above the surface.
+/// The biome is the one at the surface, as a biome key.
+fn set_column_from_modern_chunk(
+    tile: &mut Tile,
+    column_nr: usize,
+    chunk: &ModernChunk,
+    x: usize,
+    z: usize,
+) {
+    let top = chunk
+        .surface_heights
+        .as_ref()
+        .map_or(chunk.height, |heights| {
+            heights[x + z * CHUNK_WIDTH] as usize
+        })
+        .min(chunk.height);
+    let info = |y: usize| BLOCKS.get_block_info(chunk.get_block_state(x, y, z));
+    let is_air = |y: usize| chunk.get_block(x, y, z) == 0;
+    let surface_y = (0..top).rev().find(|&y| {
+        let info = info(y);
+        !is_air(y) && (!info.transparent || info.water || info.lava)
+    });
+    let surface_y = match surface_y {
+        Some(y) => y,
+        // void, nothing to see
+        None => return,
+    };
+    let get_layer = |tile: &mut Tile, layer_y: usize| {
+        let id = tile.get_or_add_key(chunk.get_block_state(x, layer_y, z).as_str());
+        // VoxelMap stores the height above the block
+        let height = chunk.min_y + layer_y as i32 + 1;
+        (height, id, chunk.get_light_above(x, layer_y, z))
+    };
+
+    let (height, id, light) = get_layer(tile, surface_y);
+    tile.set_height(column_nr, height);
+    tile.set_blockstate(column_nr, id);
+    tile.set_light(column_nr, light);
+
+    if info(surface_y).water {
+        let floor_y = (0..surface_y).rev().find(|&y| !is_air(y) && !info(y).water);
+        if let Some(floor_y) = floor_y {
+            let (height, id, light) = get_layer(tile, floor_y);
+            tile.set_ocean_floor_height(column_nr, height);
+            tile.set_ocean_floor_blockstate(column_nr, id);
+            tile.set_ocean_floor_light(column_nr, light);
+        }
+    }
+
+    let solid_y = (surface_y + 1..top)
+        .rev()
+        .find(|&y| !is_air(y) && info(y).solid);
+    if let Some(solid_y) = solid_y {
+        let (height, id, light) = get_layer(tile, solid_y);
+        tile.set_transparent_height(column_nr, height);
+        tile.set_transparent_blockstate(column_nr, id);
+        tile.set_transparent_light(column_nr, light);
+    }
+
+    if surface_y + 1 < chunk.height && !is_air(surface_y + 1) {
+        let (height, id, light) = get_layer(tile, surface_y + 1);
+        tile.set_foliage_height(column_nr, height);
+        tile.set_foliage_blockstate(column_nr, id);
+        tile.set_foliage_light(column_nr, light);
+    }
+
+    let biome_name = chunk.get_biome_name(x, surface_y, z);
+    let biome_id = if biome_name.is_empty() {
+        tile.use_biome_keys();
+        0
+    } else {
+        tile.get_or_add_biome_key(biome_name)
+    };
+    tile.set_biome_id(column_nr, biome_id);
+}
+
+/// Minecraft 1.12.2, the last version with numeric block ids.
+pub const ANVIL_DATA_VERSION: i32 = 1343;
+
+pub fn get_anvil_region_pos_of_tile((tile_x, tile_z): TilePos) -> TilePos {
+    let tiles_per_region = (REGION_WIDTH / TILE_WIDTH) as i32;
+    (
+        tile_x.div_euclid(tiles_per_region),
+        tile_z.div_euclid(tiles_per_region),
+    )
+}
+
+/// `id << 4 | meta` of a block name such as `minecraft:stone[variant=granite]`.
+pub fn get_legacy_block(block_name: &str) -> Option<u16> {
+    get_legacy_block_state(block_name).map(|state| (state & 0xfff) << 4 | state >> 12)
+}
+
+/// Builds the chunks of a tile, with only what VoxelMap saw:
+/// the surface block with `filler` (`id << 4 | meta`) below it down to bedrock,
+/// or water down to the ocean floor, and the foliage and transparent blocks
+/// floating at their heights. Sky light is full above the surface.
+/// Chunks without any data are left out.
+///
+/// Also returns the names of blocks that have no legacy id;
+/// surface blocks are replaced by the filler, others are left out.
+pub fn tile_to_legacy_chunks(tile: &Tile, filler: u16) -> (Vec<LegacyChunk>, BTreeSet<String>) {
+    let (tile_x, tile_z) = tile.pos.unwrap_or((0, 0));
+    let chunks_per_tile = TILE_WIDTH / CHUNK_WIDTH;
+    let mut chunks = Vec::new();
+    let mut unknown_blocks = BTreeSet::new();
+    for chunk_dz in 0..chunks_per_tile {
+        for chunk_dx in 0..chunks_per_tile {
+            let mut chunk = LegacyChunk::new((
+                tile_x * chunks_per_tile as i32 + chunk_dx as i32,
+                tile_z * chunks_per_tile as i32 + chunk_dz as i32,
+            ));
+            let mut has_data = false;
+            for z in 0..CHUNK_HEIGHT {
+                for x in 0..CHUNK_WIDTH {
+                    let column_nr =
+                        column_nr_of_pos(chunk_dx * CHUNK_WIDTH + x, chunk_dz * CHUNK_HEIGHT + z);
+                    if tile.is_col_empty(column_nr) {
+                        continue;
+                    }
+                    set_chunk_column(
+                        &mut chunk,
+                        x,
+                        z,
+                        tile,
+                        column_nr,
+                        filler,
+                        &mut unknown_blocks,
+                    );
+                    has_data = true;
+                }
+            }
+            if has_data {
+                chunks.push(chunk);
+            }
+        }
+    }
+    (chunks, unknown_blocks)
+}
+
+fn set_chunk_column(
+    chunk: &mut LegacyChunk,
+    x: usize,
+    z: usize,
+    tile: &Tile,
+    column_nr: usize,
+    filler: u16,
+    unknown_blocks: &mut BTreeSet<String>,
+) {
+    let mut get_block = |block_nr: u16| {
+        let block_name = tile.get_block_name(block_nr);
+        let block = get_legacy_block(block_name);
+        if block.is_none() {
+            unknown_blocks.insert(block_name.to_string());
+        }
+        block
+    };
+    // VoxelMap stores the height above the block, 1.12 worlds only go up to y=255
+    let get_y = |height: i32| (height - 1).max(0).min(ANVIL_WORLD_HEIGHT as i32 - 1) as usize;
+
+    let surface_y = get_y(tile.get_height(column_nr));
+    let surface = get_block(tile.get_blockstate(column_nr)).unwrap_or(filler);
+    let ocean_floor_height = tile.get_ocean_floor_height(column_nr);
+    let ground_y = if is_water(surface >> 4) && ocean_floor_height != 0 {
+        let floor_y = get_y(ocean_floor_height).min(surface_y);
+        for y in floor_y + 1..=surface_y {
+            chunk.set_block(x, y, z, surface);
+        }
+        let floor = tile.get_ocean_floor_blockstate(column_nr);
+        chunk.set_block(x, floor_y, z, get_block(floor).unwrap_or(filler));
+        floor_y
+    } else {
+        chunk.set_block(x, surface_y, z, surface);
+        surface_y
+    };
+    for y in 1..ground_y {
+        chunk.set_block(x, y, z, filler);
+    }
+    if ground_y > 0 {
+        chunk.set_block(x, 0, z, BEDROCK);
+    }
+
+    for &(height, block_nr) in &[
+        (
+            tile.get_foliage_height(column_nr),
+            tile.get_foliage_blockstate(column_nr),
+        ),
+        (
+            tile.get_transparent_height(column_nr),
+            tile.get_transparent_blockstate(column_nr),
+        ),
+    ] {
+        if height == 0 || get_y(height) <= surface_y {
+            continue;
+        }
+        if let Some(block) = get_block(block_nr) {
+            chunk.set_block(x, get_y(height), z, block);
+        }
+    }
+
+    for y in 0..=surface_y {
+        chunk.light[get_block_index(x, y, z)] = 0;
+    }
+    chunk.height_map[x + z * CHUNK_WIDTH] = surface_y as i32 + 1;
+    // biomes added since 1.13 become plains
+    chunk.biomes[x + z * CHUNK_WIDTH] = tile
+        .get_biome(column_nr)
+        .and_then(|biome| biome.legacy_id)
+        .unwrap_or(1);
+}
+
+/// The chunk's NBT as saved by 1.12, with lighting left to Minecraft.
+/// Sections without blocks are left out.
+pub fn legacy_chunk_to_nbt(chunk: &LegacyChunk) -> Tag {
+    let mut sections = Vec::new();
+    for section_y in 0..ANVIL_WORLD_HEIGHT / ANVIL_SECTION_HEIGHT {
+        let offset = section_y * ANVIL_SECTION_BLOCKS;
+        let blocks = &chunk.blocks[offset..offset + ANVIL_SECTION_BLOCKS];
+        if blocks.iter().all(|block| *block == 0) {
+            continue;
+        }
+        let light = &chunk.light[offset..offset + ANVIL_SECTION_BLOCKS];
+        let ids: Vec<u8> = blocks.iter().map(|block| (block >> 4) as u8).collect();
+        let metas = to_nibbles(blocks.iter().map(|block| (block & 0xf) as u8));
+        let block_light = to_nibbles(light.iter().map(|light| light & 0xf));
+        let sky_light = to_nibbles(light.iter().map(|light| light >> 4));
+        sections.push(compound(vec![
+            ("Y", Tag::Byte(section_y as _)),
+            ("Blocks", byte_array(&ids)),
+            ("Data", byte_array(&metas)),
+            ("BlockLight", byte_array(&block_light)),
+            ("SkyLight", byte_array(&sky_light)),
+        ]));
+    }
+
+    let (x, z) = chunk.pos;
+    compound(vec![
+        ("DataVersion", Tag::Int(ANVIL_DATA_VERSION)),
+        (
+            "Level",
+            compound(vec![
+                ("xPos", Tag::Int(x)),
+                ("zPos", Tag::Int(z)),
+                ("LastUpdate", Tag::Long(0)),
+                ("InhabitedTime", Tag::Long(0)),
+                ("TerrainPopulated", Tag::Byte(1)),
+                ("LightPopulated", Tag::Byte(0)),
+                ("V", Tag::Byte(1)),
+                ("Biomes", byte_array(&chunk.biomes)),
+                ("HeightMap", int_array(&chunk.height_map)),
+                ("Sections", Tag::List(sections)),
+                ("Entities", Tag::List(Vec::new())),
+                ("TileEntities", Tag::List(Vec::new())),
+            ]),
+        ),
+    ])
+}
+
+fn to_nibbles<I: Iterator<Item = u8>>(values: I) -> Vec<u8> {
+    let values: Vec<u8> = values.collect();
+    values
+        .chunks(2)
+        .map(|pair| pair[0] & 0xf | (pair.get(1).cloned().unwrap_or(0) & 0xf) << 4)
+        .collect()
+}
+
+/// Writes the chunks, which must all be in this region, as `r.<x>.<z>.mca`.
+pub fn write_anvil_region(region_path: &PathBuf, chunks: &[LegacyChunk]) -> Result<(), String> {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as u32)
+        .unwrap_or(0);
+    // locations and timestamps, each 4 bytes per chunk
+    let mut header = vec![0; 2 * ANVIL_SECTOR_BYTES];
+    let mut body = Vec::new();
+    for chunk in chunks {
+        let (chunk_x, chunk_z) = chunk.pos;
+        let index = chunk_x.rem_euclid(ANVIL_REGION_CHUNKS as i32) as usize
+            + chunk_z.rem_euclid(ANVIL_REGION_CHUNKS as i32) as usize * ANVIL_REGION_CHUNKS;
+
+        let mut nbt = Vec::new();
+        write_named_tag(&mut nbt, "", &legacy_chunk_to_nbt(chunk));
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&nbt).map_err(|e| e.to_string())?;
+        let compressed = encoder.finish().map_err(|e| e.to_string())?;
+
+        let first_sector = header.len() / ANVIL_SECTOR_BYTES + body.len() / ANVIL_SECTOR_BYTES;
+        // length includes the compression type
+        body.write_u32::<BigEndian>(compressed.len() as u32 + 1)
+            .map_err(|e| e.to_string())?;
+        body.push(ANVIL_COMPRESSION_ZLIB);
+        body.extend_from_slice(&compressed);
+        let padding = (ANVIL_SECTOR_BYTES - body.len() % ANVIL_SECTOR_BYTES) % ANVIL_SECTOR_BYTES;
+        body.resize(body.len() + padding, 0);
+        let num_sectors =
+            header.len() / ANVIL_SECTOR_BYTES + body.len() / ANVIL_SECTOR_BYTES - first_sector;
+        if num_sectors > 255 {
+            return Err(format!("chunk {:?} is too large", chunk.pos));
+        }
+
+        let location = (first_sector as u32) << 8 | num_sectors as u32;
+        (&mut header[index * 4..index * 4 + 4])
+            .write_u32::<BigEndian>(location)
+            .map_err(|e| e.to_string())?;
+        let timestamp_index = ANVIL_SECTOR_BYTES + index * 4;
+        (&mut header[timestamp_index..timestamp_index + 4])
+            .write_u32::<BigEndian>(timestamp)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut file = fs::File::create(region_path).map_err(|e| e.to_string())?;
+    file.write_all(&header).map_err(|e| e.to_string())?;
+    file.write_all(&body).map_err(|e| e.to_string())
+}
+
+/// Writes a creative mode `level.dat` for a void superflat world,
+/// so only the exported chunks have any blocks.
+pub fn write_level_dat(
+    level_dat_path: &PathBuf,
+    level_name: &str,
+    (spawn_x, spawn_y, spawn_z): (i32, i32, i32),
+) -> Result<(), String> {
+    let last_played = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0);
+    let game_rules = compound(vec![
+        ("doDaylightCycle", Tag::String("false".to_string())),
+        ("doWeatherCycle", Tag::String("false".to_string())),
+        ("doMobSpawning", Tag::String("false".to_string())),
+    ]);
+    let data = compound(vec![
+        ("DataVersion", Tag::Int(ANVIL_DATA_VERSION)),
+        (
+            "Version",
+            compound(vec![
+                ("Id", Tag::Int(ANVIL_DATA_VERSION)),
+                ("Name", Tag::String("1.12.2".to_string())),
+                ("Snapshot", Tag::Byte(0)),
+            ]),
+        ),
+        ("version", Tag::Int(ANVIL_VERSION)),
+        ("LevelName", Tag::String(level_name.to_string())),
+        ("generatorName", Tag::String("flat".to_string())),
+        ("generatorVersion", Tag::Int(0)),
+        (
+            "generatorOptions",
+            Tag::String("3;minecraft:air;127;".to_string()),
+        ),
+        ("MapFeatures", Tag::Byte(0)),
+        ("RandomSeed", Tag::Long(0)),
+        ("GameType", Tag::Int(1)),
+        ("hardcore", Tag::Byte(0)),
+        ("allowCommands", Tag::Byte(1)),
+        ("initialized", Tag::Byte(1)),
+        ("Difficulty", Tag::Byte(0)),
+        ("SpawnX", Tag::Int(spawn_x)),
+        ("SpawnY", Tag::Int(spawn_y)),
+        ("SpawnZ", Tag::Int(spawn_z)),
+        ("Time", Tag::Long(0)),
+        ("DayTime", Tag::Long(6000)),
+        ("LastPlayed", Tag::Long(last_played)),
+        ("raining", Tag::Byte(0)),
+        ("thundering", Tag::Byte(0)),
+        ("GameRules", game_rules),
+    ]);
+
+    let mut nbt = Vec::new();
+    write_named_tag(&mut nbt, "", &compound(vec![("Data", data)]));
+    let file = fs::File::create(level_dat_path).map_err(|e| e.to_string())?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder.write_all(&nbt).map_err(|e| e.to_string())?;
+    encoder.finish().map(|_| ()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_block(chunk: &mut LegacyChunk, x: usize, y: usize, z: usize, id: u16, meta: u16) {
+        chunk.blocks[get_block_index(x, y, z)] = id << 4 | meta;
+    }
+
+    #[test]
+    fn computes_layers() {
+        let mut chunk = LegacyChunk::new((-1, 16));
+        // column 0,0: lake of water on sand, with glass floating above
+        for y in 0..60 {
+            set_block(&mut chunk, 0, y, 0, 12, 0);
+        }
+        for y in 60..63 {
+            set_block(&mut chunk, 0, y, 0, 9, 0);
+        }
+        set_block(&mut chunk, 0, 80, 0, 20, 0);
+        chunk.height_map[0] = 63;
+        chunk.light[get_block_index(0, 63, 0)] = 0xe2;
+        chunk.biomes[0] = 24;
+        // column 1,0: stone with a flower on it
+        for y in 0..70 {
+            set_block(&mut chunk, 1, y, 0, 1, 0);
+        }
+        set_block(&mut chunk, 1, 70, 0, 38, 0);
+        chunk.height_map[1] = 70;
+        // column 2,0: lava on stone
+        for y in 0..50 {
+            set_block(&mut chunk, 2, y, 0, 1, 0);
+        }
+        for y in 50..53 {
+            set_block(&mut chunk, 2, y, 0, 11, 0);
+        }
+        chunk.height_map[2] = 50;
+
+        let tiles = anvil_chunks_to_tiles(&[AnvilChunk::Legacy(chunk)]);
+        assert_eq!(tiles.len(), 1);
+        let tile = &tiles[0];
+        assert_eq!(tile.pos, Some((-1, 1)));
+        let name = |block_nr| tile.get_block_name(block_nr);
+
+        let lake = column_nr_of_pos(15 * 16, 0);
+        assert_eq!(tile.get_height(lake), 63);
+        assert_eq!(name(tile.get_blockstate(lake)), "minecraft:water[level=0]");
+        assert_eq!(tile.get_light(lake), 0xe2);
+        assert_eq!(tile.get_ocean_floor_height(lake), 60);
+        assert_eq!(
+            name(tile.get_ocean_floor_blockstate(lake)),
+            "minecraft:sand[variant=sand]"
+        );
+        assert_eq!(tile.get_transparent_height(lake), 81);
+        assert_eq!(
+            name(tile.get_transparent_blockstate(lake)),
+            "minecraft:glass"
+        );
+        assert_eq!(tile.get_foliage_height(lake), 0);
+        assert_eq!(tile.get_biome_id(lake), 24);
+
+        let meadow = lake + 1;
+        assert_eq!(tile.get_height(meadow), 70);
+        assert_eq!(
+            name(tile.get_blockstate(meadow)),
+            "minecraft:stone[variant=stone]"
+        );
+        assert_eq!(tile.get_ocean_floor_height(meadow), 0);
+        assert_eq!(tile.get_transparent_height(meadow), 0);
+        assert_eq!(tile.get_foliage_height(meadow), 71);
+        assert_eq!(
+            name(tile.get_foliage_blockstate(meadow)),
+            "minecraft:red_flower[type=poppy]"
+        );
+
+        let lava = meadow + 1;
+        assert_eq!(tile.get_height(lava), 53);
+        assert_eq!(name(tile.get_blockstate(lava)), "minecraft:lava[level=0]");
+        assert!(tile.is_col_empty(lava + 1));
+    }
+
+    #[test]
+    fn exports_what_it_imports() {
+        let mut tile = Tile::new(Some((-1, 3)));
+        let water = tile.get_or_add_key("minecraft:water[level=0]");
+        let sand = tile.get_or_add_key("minecraft:sand[variant=sand]");
+        let grass = tile.get_or_add_key("minecraft:grass[snowy=false]");
+        let poppy = tile.get_or_add_key("minecraft:red_flower[type=poppy]");
+        // modern names are converted back where possible
+        let glass = tile.get_or_add_key("minecraft:white_stained_glass");
+        let modern = tile.get_or_add_key("minecraft:cherry_log[axis=y]");
+        let (sea, land, unknown) = (
+            column_nr_of_pos(0, 0),
+            column_nr_of_pos(17, 255),
+            column_nr_of_pos(100, 100),
+        );
+        tile.set_height(sea, 63);
+        tile.set_blockstate(sea, water);
+        tile.set_ocean_floor_height(sea, 40);
+        tile.set_ocean_floor_blockstate(sea, sand);
+        tile.set_biome_id(sea, 24);
+        tile.set_height(land, 70);
+        tile.set_blockstate(land, grass);
+        tile.set_foliage_height(land, 71);
+        tile.set_foliage_blockstate(land, poppy);
+        tile.set_transparent_height(land, 90);
+        tile.set_transparent_blockstate(land, glass);
+        tile.set_height(unknown, 80);
+        tile.set_blockstate(unknown, modern);
+
+        let stone = get_legacy_block("minecraft:stone[variant=stone]").unwrap();
+        let (chunks, unknown_blocks) = tile_to_legacy_chunks(&tile, stone);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            unknown_blocks.into_iter().collect::<Vec<_>>(),
+            vec!["minecraft:cherry_log[axis=y]"]
+        );
+        let chunks: Vec<_> = chunks
+            .iter()
+            .map(|chunk| parse_legacy_chunk(&legacy_chunk_to_nbt(chunk)).unwrap())
+            .collect();
+        assert_eq!(chunks[0].get_block(0, 0, 0), BEDROCK);
+        assert_eq!(chunks[0].get_block(0, 38, 0), stone);
+
+        let chunks: Vec<_> = chunks.into_iter().map(AnvilChunk::Legacy).collect();
+        let tiles = anvil_chunks_to_tiles(&chunks);
+        assert_eq!(tiles.len(), 1);
+        let tile = &tiles[0];
+        assert_eq!(tile.pos, Some((-1, 3)));
+        let name = |block_nr| tile.get_block_name(block_nr);
+        assert_eq!(name(tile.get_blockstate(sea)), "minecraft:water[level=0]");
+        assert_eq!(tile.get_height(sea), 63);
+        assert_eq!(tile.get_light(sea), 0xf0);
+        assert_eq!(
+            name(tile.get_ocean_floor_blockstate(sea)),
+            "minecraft:sand[variant=sand]"
+        );
+        assert_eq!(tile.get_ocean_floor_height(sea), 40);
+        assert_eq!(tile.get_biome_id(sea), 24);
+        assert_eq!(
+            name(tile.get_blockstate(land)),
+            "minecraft:grass[snowy=false]"
+        );
+        assert_eq!(tile.get_height(land), 70);
+        assert_eq!(
+            name(tile.get_foliage_blockstate(land)),
+            "minecraft:red_flower[type=poppy]"
+        );
+        assert_eq!(tile.get_foliage_height(land), 71);
+        assert_eq!(
+            name(tile.get_transparent_blockstate(land)),
+            "minecraft:stained_glass[color=white]"
+        );
+        assert_eq!(tile.get_transparent_height(land), 90);
+        assert_eq!(
+            name(tile.get_blockstate(unknown)),
+            "minecraft:stone[variant=stone]"
+        );
+        assert_eq!(tile.get_height(unknown), 80);
+    }
+
+    #[test]
+    fn reads_generated_chunks() {
+        let region_path = std::env::temp_dir().join("r.-1.0.mca");
+        let mut chunks = [LegacyChunk::new((-32, 0)), LegacyChunk::new((-1, 5))];
+        chunks[1].set_block(3, 70, 4, 1 << 4);
+        chunks[1].height_map[3 + 4 * 16] = 71;
+        write_anvil_region(&region_path, &chunks).unwrap();
+        let read = read_anvil_region(&region_path).unwrap();
+        fs::write(&region_path, [0; 100]).unwrap();
+        let truncated = read_anvil_region(&region_path);
+        fs::remove_file(&region_path).unwrap();
+
+        let positions: Vec<TilePos> = read.iter().map(|chunk| chunk.pos()).collect();
+        assert_eq!(positions, vec![(-32, 0), (-1, 5)]);
+        match &read[1] {
+            AnvilChunk::Legacy(chunk) => {
+                assert_eq!(chunk.get_block(3, 70, 4), 1 << 4);
+                assert_eq!(chunk.height_map[3 + 4 * 16], 71);
+            }
+            AnvilChunk::Modern(_) => panic!("chunk was saved by 1.12"),
+        }
+        assert!(truncated.is_err());
+    }
+
+    #[test]
+    fn computes_layers_of_modern_chunks() {
+        let state = |name: &str, properties: Vec<(&str, Tag)>| {
+            compound(vec![
+                ("Name", Tag::String(name.to_string())),
+                ("Properties", compound(properties)),
+            ])
+        };
+        let palette = vec![
+            state("minecraft:air", vec![]),
+            state("minecraft:stone", vec![]),
+            state(
+                "minecraft:water",
+                vec![("level", Tag::String("0".to_string()))],
+            ),
+            state("minecraft:poppy", vec![]),
+            state("minecraft:glass", vec![]),
+        ];
+        // column 0,0: lake of water on stone, with glass floating above
+        // column 1,0: stone with a flower on it
+        let mut blocks = vec![0u64; ANVIL_SECTION_BLOCKS];
+        for y in 0..16 {
+            blocks[get_block_index(0, y, 0)] = [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 0, 0, 4, 0][y];
+            blocks[get_block_index(1, y, 0)] = [1, 1, 1, 1, 1, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0][y];
+        }
+        // 5 block states take 4 bits, 16 to a long
+        let block_states = blocks
+            .chunks(16)
+            .map(|values| {
+                let long = values
+                    .iter()
+                    .enumerate()
+                    .fold(0, |long, (i, value)| long | value << (i * 4));
+                long as i64
+            })
+            .collect();
+        let section = compound(vec![
+            ("Y", Tag::Byte(-4)),
+            (
+                "block_states",
+                compound(vec![
+                    ("palette", Tag::List(palette)),
+                    ("data", Tag::LongArray(block_states)),
+                ]),
+            ),
+            (
+                "biomes",
+                compound(vec![
+                    (
+                        "palette",
+                        Tag::List(vec![
+                            Tag::String("minecraft:plains".to_string()),
+                            Tag::String("minecraft:ocean".to_string()),
+                        ]),
+                    ),
+                    // ocean in the cell of the lake's surface
+                    ("data", Tag::LongArray(vec![1 << 32])),
+                ]),
+            ),
+            ("SkyLight", byte_array(&[0xff; ANVIL_SECTION_BLOCKS / 2])),
+        ]);
+        let chunk = |status: &str| {
+            compound(vec![
+                ("DataVersion", Tag::Int(3465)),
+                ("Status", Tag::String(status.to_string())),
+                ("xPos", Tag::Int(-1)),
+                ("zPos", Tag::Int(16)),
+                ("yPos", Tag::Int(-4)),
+                ("sections", Tag::List(vec![section.clone()])),
+            ])
+        };
+        assert!(parse_anvil_chunk(&chunk("minecraft:features"))
+            .unwrap()
+            .is_none());
+        let chunk = parse_anvil_chunk(&chunk("minecraft:full"))
+            .unwrap()
+            .unwrap();
+
+        let tiles = anvil_chunks_to_tiles(&[chunk]);
+        assert_eq!(tiles.len(), 1);
+        let tile = &tiles[0];
+        assert_eq!(tile.pos, Some((-1, 1)));
+        let name = |block_nr| tile.get_block_name(block_nr);
+
+        let lake = column_nr_of_pos(15 * 16, 0);
+        assert_eq!(tile.get_height(lake), -52);
+        assert_eq!(name(tile.get_blockstate(lake)), "minecraft:water[level=0]");
+        assert_eq!(tile.get_light(lake), 0xf0);
+        assert_eq!(tile.get_ocean_floor_height(lake), -54);
+        assert_eq!(
+            name(tile.get_ocean_floor_blockstate(lake)),
+            "minecraft:stone"
+        );
+        assert_eq!(tile.get_transparent_height(lake), -49);
+        assert_eq!(
+            name(tile.get_transparent_blockstate(lake)),
+            "minecraft:glass"
+        );
+        assert_eq!(tile.get_foliage_height(lake), 0);
+        assert_eq!(tile.get_biome_name(lake), "minecraft:ocean");
+
+        let meadow = lake + 1;
+        assert_eq!(tile.get_height(meadow), -59);
+        assert_eq!(name(tile.get_blockstate(meadow)), "minecraft:stone");
+        assert_eq!(tile.get_transparent_height(meadow), 0);
+        assert_eq!(tile.get_foliage_height(meadow), -58);
+        assert_eq!(name(tile.get_foliage_blockstate(meadow)), "minecraft:poppy");
+        assert_eq!(tile.get_biome_name(meadow), "minecraft:plains");
+        assert!(tile.is_col_empty(meadow + 1));
+    }
+
+    #[test]
+    fn unpacks_indices_across_longs() {
+        // before 1.16, 5 bit indices continue into the next long
+        let mut longs = vec![0i64; 5];
+        for i in 0..64 {
+            let (long_nr, shift) = (i * 5 / 64, i * 5 % 64);
+            longs[long_nr] |= ((i % 32) << shift) as i64;
+            if shift > 59 {
+                longs[long_nr + 1] |= ((i % 32) >> (64 - shift)) as i64;
+            }
+        }
+        let indices = unpack_palette_indices(&longs, 32, 4, 64).unwrap();
+        assert_eq!(indices, (0..64).map(|i| i % 32).collect::<Vec<u16>>());
+        assert!(unpack_palette_indices(&longs[1..], 32, 4, 64).is_err());
+    }
+
+    #[test]
+    fn reads_region_file_names() {
+        let path = PathBuf::from("world/region/r.-3.12.mca");
+        assert_eq!(get_xz_from_anvil_path(&path), Ok((-3, 12)));
+        assert!(get_xz_from_anvil_path(&PathBuf::from("r.-3.12.mcr")).is_err());
+    }
+}
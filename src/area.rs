@@ -0,0 +1,202 @@
+extern crate serde_json;
+
+use self::serde_json::Value;
+use crate::{parse_bounds, TILE_HEIGHT, TILE_WIDTH};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// A named part of the world, such as a nation's claims.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Area {
+    pub name: String,
+    /// closed outlines of `(x, z)` corners; a column is inside if it is
+    /// inside an odd number of them, so holes are outlines within outlines
+    pub rings: Vec<Vec<(f64, f64)>>,
+}
+
+impl Area {
+    /// bounds as returned by `parse_bounds`: w,n,e,s
+    pub fn from_bounds(name: &str, bounds: &[i32]) -> Self {
+        let (w, n, e, s) = (
+            bounds[0] as f64,
+            bounds[1] as f64,
+            bounds[2] as f64,
+            bounds[3] as f64,
+        );
+        Self {
+            name: name.to_string(),
+            rings: vec![vec![(w, n), (e, n), (e, s), (w, s)]],
+        }
+    }
+
+    /// Whether the center of the block column at `x,z` is inside the area.
+    pub fn contains_column(&self, x: i32, z: i32) -> bool {
+        let (px, pz) = (x as f64 + 0.5, z as f64 + 0.5);
+        let containing = self
+            .rings
+            .iter()
+            .filter(|ring| is_in_ring(ring, px, pz))
+            .count();
+        containing % 2 == 1
+    }
+
+    /// Quick check before looking at the tile's columns, may be true for tiles
+    /// that are only near the area.
+    pub fn may_overlap_tile(&self, (tile_x, tile_z): (i32, i32)) -> bool {
+        let x = (tile_x * TILE_WIDTH as i32) as f64;
+        let z = (tile_z * TILE_HEIGHT as i32) as f64;
+        let (min_x, min_z) = self.min_corner();
+        let (max_x, max_z) = self.max_corner();
+        min_x < x + TILE_WIDTH as f64 && max_x > x && min_z < z + TILE_HEIGHT as f64 && max_z > z
+    }
+
+    fn min_corner(&self) -> (f64, f64) {
+        self.rings
+            .iter()
+            .flat_map(|ring| ring.iter())
+            .fold((f64::MAX, f64::MAX), |(x, z), (cx, cz)| {
+                (x.min(*cx), z.min(*cz))
+            })
+    }
+
+    fn max_corner(&self) -> (f64, f64) {
+        self.rings
+            .iter()
+            .flat_map(|ring| ring.iter())
+            .fold((f64::MIN, f64::MIN), |(x, z), (cx, cz)| {
+                (x.max(*cx), z.max(*cz))
+            })
+    }
+}
+
+/// Even-odd test, the ring does not need to repeat its first corner.
+fn is_in_ring(ring: &[(f64, f64)], px: f64, pz: f64) -> bool {
+    let mut inside = false;
+    for i in 0..ring.len() {
+        let (x1, z1) = ring[i];
+        let (x2, z2) = ring[(i + 1) % ring.len()];
+        if (z1 > pz) != (z2 > pz) && px < x1 + (pz - z1) * (x2 - x1) / (z2 - z1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// One entry of an areas file, see `parse_areas`.
+#[derive(Debug, Deserialize)]
+struct AreaJson {
+    name: String,
+    polygon: Option<Vec<(f64, f64)>>,
+    bounds: Option<String>,
+}
+
+pub fn read_areas(path: &Path) -> Result<Vec<Area>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{:?} {}", path, e))?;
+    parse_areas(&text).map_err(|e| format!("{:?} {}", path, e))
+}
+
+/// Either a list of areas with a polygon or bounds (as in `--between`):
+///
+/// ```json
+/// [
+///   {"name": "Mount Augusta", "polygon": [[-100, -100], [300, -80], [250, 200], [-90, 180]]},
+///   {"name": "Spawn", "bounds": "-500,-500,500,500"}
+/// ]
+/// ```
+///
+/// or a GeoJSON FeatureCollection of Polygons and MultiPolygons in `[x, z]` coordinates,
+/// named by their `name` property.
+pub fn parse_areas(text: &str) -> Result<Vec<Area>, String> {
+    let json: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    if json.get("type").and_then(|t| t.as_str()) == Some("FeatureCollection") {
+        return parse_geojson_areas(&json);
+    }
+    let areas: Vec<AreaJson> = serde_json::from_value(json).map_err(|e| e.to_string())?;
+    areas
+        .into_iter()
+        .map(
+            |AreaJson {
+                 name,
+                 polygon,
+                 bounds,
+             }| match (polygon, bounds) {
+                (Some(polygon), None) => Ok(Area {
+                    name,
+                    rings: vec![polygon],
+                }),
+                (None, Some(bounds)) => parse_bounds(&bounds)
+                    .map(|bounds| Area::from_bounds(&name, &bounds))
+                    .map_err(|e| format!("Invalid bounds of {:?}: {}", name, e)),
+                _ => Err(format!("Area {:?} needs either a polygon or bounds", name)),
+            },
+        )
+        .collect()
+}
+
+fn parse_geojson_areas(json: &Value) -> Result<Vec<Area>, String> {
+    let features = json
+        .get("features")
+        .and_then(|f| f.as_array())
+        .ok_or("FeatureCollection has no features")?;
+    let mut areas = Vec::new();
+    for (feature_nr, feature) in features.iter().enumerate() {
+        let name = feature
+            .pointer("/properties/name")
+            .and_then(|n| n.as_str())
+            .map_or_else(|| format!("#{}", feature_nr), |n| n.to_string());
+        let geometry_type = feature.pointer("/geometry/type").and_then(|t| t.as_str());
+        let coordinates = feature
+            .pointer("/geometry/coordinates")
+            .cloned()
+            .unwrap_or(Value::Null);
+        let rings: Vec<Vec<(f64, f64)>> = match geometry_type {
+            Some("Polygon") => serde_json::from_value(coordinates),
+            Some("MultiPolygon") => {
+                serde_json::from_value::<Vec<Vec<Vec<(f64, f64)>>>>(coordinates)
+                    .map(|polygons| polygons.into_iter().flatten().collect())
+            }
+            _ => continue,
+        }
+        .map_err(|e| format!("Area {:?}: {}", name, e))?;
+        areas.push(Area { name, rings });
+    }
+    Ok(areas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_columns_within_polygons_and_bounds() {
+        let areas = parse_areas(
+            r#"[
+                {"name": "Triangle", "polygon": [[0, 0], [100, 0], [0, 100]]},
+                {"name": "Box", "bounds": "-16,-16,16,16"}
+            ]"#,
+        )
+        .unwrap();
+        assert!(areas[0].contains_column(10, 10));
+        assert!(!areas[0].contains_column(60, 60));
+        assert!(!areas[0].contains_column(-1, 10));
+        assert!(areas[1].contains_column(-16, 15));
+        assert!(!areas[1].contains_column(16, 0));
+        assert!(areas[1].may_overlap_tile((-1, -1)));
+        assert!(!areas[1].may_overlap_tile((1, 0)));
+
+        let geojson = parse_areas(
+            r#"{"type": "FeatureCollection", "features": [{"type": "Feature",
+                "properties": {"name": "Donut"},
+                "geometry": {"type": "Polygon", "coordinates": [
+                    [[0, 0], [30, 0], [30, 30], [0, 30]],
+                    [[10, 10], [20, 10], [20, 20], [10, 20]]]}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(geojson[0].name, "Donut");
+        assert!(geojson[0].contains_column(5, 5));
+        assert!(!geojson[0].contains_column(15, 15));
+
+        assert!(parse_areas(r#"[{"name": "Nothing"}]"#).is_err());
+    }
+}
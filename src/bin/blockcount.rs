@@ -10,11 +10,12 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::mpsc::channel;
 use threadpool::ThreadPool;
+use voxelmap_cache::mc::biomes::get_biome_by_name;
+use voxelmap_cache::tile::TILE_COLUMNS;
 use voxelmap_cache::tile::{get_tile_paths_in_dirs, read_tile, Tile};
 use voxelmap_cache::ProgressTracker;
-use voxelmap_cache::{biomes::BIOME_NAMES, tile::TILE_COLUMNS};
 
-const USAGE: &'static str = "
+const USAGE: &str = "
 Usage: blockcount [-q] [-t threads] <cache-path>
 
 cache-path contains voxelmap caches in the format `<x>,<z>.zip`
@@ -31,8 +32,8 @@ struct Args {
     arg_threads: Option<usize>,
 }
 
-/// (biome, block) -> count
-type BiomeBlockCounts = HashMap<(u16, String), usize>;
+/// (biome name, block) -> count
+type BiomeBlockCounts = HashMap<(String, String), usize>;
 
 fn new_biome_block_counts() -> BiomeBlockCounts {
     HashMap::new()
@@ -66,13 +67,10 @@ fn main() {
     let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
     let (tx, rx) = channel();
 
-    // let global_keys_map = Arc::new(build_global_keys_map());
-
     for tile_path in tile_paths.into_iter() {
         let tx = tx.clone();
-        // let global_keys_map = global_keys_map.clone();
         pool.execute(move || {
-            let result = count_tile(&tile_path); //, &global_keys_map);
+            let result = count_tile(&tile_path);
             tx.send((tile_path, result)).expect("Sending result");
         });
     }
@@ -96,17 +94,20 @@ fn main() {
         }
     }
 
-    let mut biome_counts = [0; 256];
-    for ((biome_id, _block_name), count) in counts.iter() {
-        biome_counts[*biome_id as usize] += count;
+    let mut biome_counts = HashMap::new();
+    for ((biome_name, _block_name), count) in counts.iter() {
+        *biome_counts.entry(biome_name.as_str()).or_insert(0) += count;
     }
 
     // let counts_array = counts.iter().array();
     // counts_array.sort_unstable_by_key(|((biome_id, block_name), count)| (biome_id, block_name, count));
     // for (biome_id, block_name, count) in counts_array.iter() {
-    for ((biome_id, block_name), count) in counts.iter() {
-        let biome_name = BIOME_NAMES[*biome_id as usize];
-        let rel_count = *count as f32 / biome_counts[*biome_id as usize] as f32;
+    for ((biome_name, block_name), count) in counts.iter() {
+        // ids of modern caches differ per tile, only legacy ids are meaningful
+        let biome_id = get_biome_by_name(biome_name)
+            .and_then(|biome| biome.legacy_id)
+            .map_or("-".to_string(), |biome_id| biome_id.to_string());
+        let rel_count = *count as f32 / biome_counts[biome_name.as_str()] as f32;
         // println!("{}\t{}\t{:10}\t{}", biome_name, biome_id, count, block_name);
         println!(
             "{}\t{}\t{}\t{}\t{}",
@@ -128,7 +129,6 @@ fn main() {
     };
 }
 
-// fn count_tile(tile_path: &PathBuf, global_keys_map: &KeysMap) -> Result<BiomeBlockCounts, String> {
 fn count_tile(tile_path: &PathBuf) -> Result<BiomeBlockCounts, String> {
     let tile = read_tile(tile_path).map_err(|e| e.to_string())?;
 
@@ -142,14 +142,15 @@ fn count_tile(tile_path: &PathBuf) -> Result<BiomeBlockCounts, String> {
     ];
 
     for column_nr in 0..TILE_COLUMNS {
-        let biome = tile.get_biome_id(column_nr);
+        let biome = tile.get_biome_name(column_nr);
         for get_block_nr in &steps_block_getters {
-            let block_nr = get_block_nr(&tile, column_nr) as usize;
+            let block_nr = get_block_nr(&tile, column_nr);
             if block_nr != 0 {
-                let block_name_full = tile.names.get(block_nr).unwrap().to_string();
-                let block_name_stem = block_name_full.split("[").next().unwrap().to_string();
+                let block_name = tile.get_block_state(block_nr).prefixed_name();
 
-                *counts.entry((biome, block_name_stem)).or_insert(0) += 1;
+                *counts
+                    .entry((biome.to_string(), block_name.to_string()))
+                    .or_insert(0) += 1;
             }
         }
     }
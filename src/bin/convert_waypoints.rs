@@ -0,0 +1,169 @@
+extern crate docopt;
+extern crate serde;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use voxelmap_cache::waypoint::journeymap::{get_journeymap_id, write_journeymap};
+use voxelmap_cache::waypoint::voxelmap::write_points;
+use voxelmap_cache::waypoint::xaero::{get_xaero_dimension_dir, write_xaero};
+use voxelmap_cache::waypoint::{
+    dedup_waypoints, get_format_losses, get_waypoint_paths, read_waypoints, waypoints_to_json,
+    Waypoint, WaypointFormat, OVERWORLD,
+};
+
+const USAGE: &str = "
+Usage: convert_waypoints [-q] [--dimension=<dim>...] <format> <output-path> <input-path>...
+
+Converts waypoints between the VoxelMap, Xaero and JourneyMap minimap mods.
+
+format is the format to write: voxelmap, xaero, journeymap, json
+
+input-path is a waypoints file of any of these formats, or a directory that is searched for them:
+VoxelMap `.points` files, Xaero `dim%*/*.txt` files, or JourneyMap `waypoints/*.json` files.
+
+output-path is, depending on the format:
+- voxelmap, json: the file to write
+- xaero: a directory that gets one `dim%*/mw$default_1.txt` file per dimension,
+  to be copied into `XaeroWaypoints/<server>/`
+- journeymap: a directory that gets one `.json` file per waypoint,
+  to be copied into `journeymap/data/mp/<server>/waypoints/`
+
+Anything that the chosen format can't store is reported for each waypoint.
+
+Options:
+    -q, --quiet         Do not output info messages.
+    --dimension=<dim>   Only convert waypoints in this dimension, can be repeated,
+                        overworld, the_nether, the_end, ...
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_dimension: Vec<String>,
+    flag_quiet: bool,
+    arg_format: String,
+    arg_output_path: String,
+    arg_input_path: Vec<String>,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let format = WaypointFormat::from_name(&args.arg_format).unwrap_or_else(|| {
+        eprintln!("Unknown format {}", &args.arg_format);
+        std::process::exit(1);
+    });
+
+    let mut waypoints = Vec::new();
+    for input_path in &args.arg_input_path {
+        let paths = get_waypoint_paths(Path::new(input_path)).unwrap_or_else(|e| {
+            eprintln!("Error while listing {:?}: {}", input_path, e);
+            std::process::exit(1);
+        });
+        for path in paths {
+            match read_waypoints(&path) {
+                Ok(file_waypoints) => {
+                    if verbose {
+                        eprintln!("Read {} waypoints from {:?}", file_waypoints.len(), path);
+                    }
+                    waypoints.extend(file_waypoints);
+                }
+                Err(e) => eprintln!("Failed reading waypoints {:?} {}", path, e),
+            }
+        }
+    }
+
+    let waypoints: Vec<_> = waypoints
+        .into_iter()
+        .filter(|waypoint| {
+            args.flag_dimension.is_empty()
+                || args
+                    .flag_dimension
+                    .iter()
+                    .any(|d| waypoint.is_in_dimension(d))
+        })
+        .collect();
+    // the same waypoint may be in several of the input files
+    let waypoints = dedup_waypoints(waypoints, 0);
+
+    for waypoint in &waypoints {
+        let losses = get_format_losses(waypoint, format);
+        if !losses.is_empty() {
+            eprintln!(
+                "{} at {},{},{}: {} can't store {}",
+                waypoint.name,
+                waypoint.x,
+                waypoint.y,
+                waypoint.z,
+                &args.arg_format,
+                losses.join(", ")
+            );
+        }
+    }
+
+    let output_path = Path::new(&args.arg_output_path);
+    let result = match format {
+        WaypointFormat::VoxelMap => write_file(output_path, &write_points(&waypoints)),
+        WaypointFormat::Json => write_file(output_path, &waypoints_to_json(&waypoints)),
+        WaypointFormat::Xaero => write_xaero_dirs(output_path, &waypoints),
+        WaypointFormat::JourneyMap => write_journeymap_dir(output_path, &waypoints),
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("Failed writing {:?} {}", output_path, e);
+        std::process::exit(1);
+    });
+
+    if verbose {
+        eprintln!(
+            "Wrote {} waypoints to {:?}",
+            waypoints.len(),
+            &args.arg_output_path
+        );
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Waypoints in several dimensions are written to each of their dimensions' files.
+fn write_xaero_dirs(output_path: &Path, waypoints: &[Waypoint]) -> Result<(), String> {
+    let mut by_dimension: BTreeMap<String, Vec<Waypoint>> = BTreeMap::new();
+    for waypoint in waypoints {
+        let dimensions = if waypoint.dimensions.is_empty() {
+            vec![OVERWORLD.to_string()]
+        } else {
+            waypoint.dimensions.clone()
+        };
+        for dimension in dimensions {
+            by_dimension
+                .entry(dimension)
+                .or_default()
+                .push(waypoint.clone());
+        }
+    }
+    for (dimension, waypoints) in by_dimension {
+        let path = output_path
+            .join(get_xaero_dimension_dir(&dimension))
+            .join("mw$default_1.txt");
+        write_file(&path, &write_xaero(&waypoints))?;
+    }
+    Ok(())
+}
+
+fn write_journeymap_dir(output_path: &Path, waypoints: &[Waypoint]) -> Result<(), String> {
+    for waypoint in waypoints {
+        let path = output_path.join(format!("{}.json", get_journeymap_id(waypoint)));
+        write_file(&path, &write_journeymap(waypoint))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,217 @@
+extern crate docopt;
+extern crate serde;
+extern crate threadpool;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use threadpool::ThreadPool;
+use voxelmap_cache::anvil::{
+    get_anvil_region_pos_of_tile, get_legacy_block, tile_to_legacy_chunks, write_anvil_region,
+    write_level_dat, ANVIL_WORLD_HEIGHT,
+};
+use voxelmap_cache::tile::{
+    column_nr_of_pos, get_tile_paths_in_dirs, get_xz_from_tile_path, is_tile_pos_in_bounds,
+    read_tile, TilePos,
+};
+use voxelmap_cache::{parse_bounds, ProgressTracker, TILE_HEIGHT, TILE_WIDTH};
+
+const USAGE: &str = "
+Usage: export_anvil [-q] [-t threads] [--between=<bounds>] [--filler=<block>] [--name=<name>] <cache-path> <world-path>
+
+Converts voxelmap caches to a Minecraft 1.12 world that can be opened in singleplayer.
+
+cache-path contains voxelmap caches in the format `<x>,<z>.zip`,
+for example the result of `merge_caches`.
+
+world-path is the world directory to create, containing `level.dat` and `region/`,
+to be copied into `.minecraft/saves/`.
+
+Only the surface is exported: each column is filled up to its top block,
+with water down to the ocean floor, and foliage and transparent blocks at their heights.
+The world is a creative mode void superflat world, so everything else is empty.
+
+Options:
+    -q, --quiet         Do not output info messages.
+    -t, --threads       Number of threads to use for parallel processing
+    --between=<bounds>  Only export regions at least partially within this bounding box,
+                        format: w,n,e,s [default: -99999,-99999,99999,99999]
+    --filler=<block>    Block to fill columns with below their top block,
+                        also used for top blocks without legacy id [default: minecraft:stone]
+    --name=<name>       Name of the world in the world list [default: VoxelMap export]
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_quiet: bool,
+    arg_threads: Option<usize>,
+    flag_between: String,
+    flag_filler: String,
+    flag_name: String,
+    arg_cache_path: String,
+    arg_world_path: String,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let filler = get_legacy_block(&args.flag_filler).unwrap_or_else(|| {
+        eprintln!(
+            "Invalid arg: --filler={} has no legacy id",
+            &args.flag_filler
+        );
+        std::process::exit(1);
+    });
+
+    let tile_paths = get_tile_paths_in_dirs(&vec![args.arg_cache_path.clone()], verbose)
+        .unwrap_or_else(|e| {
+            eprintln!("Error while listing cache directory: {:?}", e);
+            std::process::exit(1);
+        });
+
+    let bounds = parse_bounds(&args.flag_between).unwrap_or_else(|e| {
+        eprintln!("Invalid arg: --between={} {}", &args.flag_between, e);
+        std::process::exit(1);
+    });
+
+    let mut tile_paths_by_region = HashMap::new();
+    let mut tiles = Vec::new();
+    for tile_path in tile_paths {
+        let pos = get_xz_from_tile_path(&tile_path).expect("getting pos from tile path");
+        if !is_tile_pos_in_bounds(pos, &bounds) {
+            continue;
+        }
+        tiles.push((pos, tile_path.clone()));
+        tile_paths_by_region
+            .entry(get_anvil_region_pos_of_tile(pos))
+            .or_insert_with(Vec::new)
+            .push(tile_path);
+    }
+    if tiles.is_empty() {
+        eprintln!("No caches to export");
+        std::process::exit(1);
+    }
+
+    let world_path = PathBuf::from(&args.arg_world_path);
+    let regions_path = world_path.join("region");
+    fs::create_dir_all(&regions_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to create output directory {:?} {:?}",
+            &regions_path, e
+        );
+        std::process::exit(1);
+    });
+
+    write_level_dat(
+        &world_path.join("level.dat"),
+        &args.flag_name,
+        get_spawn(&tiles),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("Failed writing level.dat {}", e);
+        std::process::exit(1);
+    });
+
+    let total_work = tile_paths_by_region.len();
+    let mut progress = ProgressTracker::new(total_work);
+    if verbose {
+        eprintln!("Exporting {} regions to {:?}", total_work, &regions_path);
+    }
+
+    let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
+    let (tx, rx) = channel();
+
+    for ((region_x, region_z), tile_paths) in tile_paths_by_region.into_iter() {
+        let tx = tx.clone();
+        let region_path = regions_path.join(format!("r.{}.{}.mca", region_x, region_z));
+        pool.execute(move || {
+            let result = export_region(&tile_paths, &region_path, filler);
+            tx.send((region_path, result)).expect("Sending result");
+        });
+    }
+
+    let mut all_unknown_blocks = BTreeSet::new();
+    for work_done in 0..total_work {
+        let (region_path, result) = rx.recv().expect("Receiving next result");
+        match result {
+            Ok(unknown_blocks) => all_unknown_blocks.extend(unknown_blocks),
+            Err(e) => eprintln!("Failed exporting region {:?} {}", region_path, e),
+        }
+
+        progress.progress_to(work_done);
+        if verbose {
+            progress.print_progress();
+        }
+    }
+
+    if !all_unknown_blocks.is_empty() {
+        eprintln!(
+            "These blocks have no legacy block id and were replaced by the filler or left out: {}",
+            all_unknown_blocks.into_iter().collect::<Vec<_>>().join(" ")
+        );
+    }
+}
+
+/// On top of the exported column closest to the center of all exported caches,
+/// in the closest cache that has any columns. The world is void,
+/// so the center itself may be empty for sparse maps.
+fn get_spawn(tiles: &[(TilePos, PathBuf)]) -> (i32, i32, i32) {
+    let (tile_w, tile_h) = (TILE_WIDTH as i32, TILE_HEIGHT as i32);
+    let min_x = tiles.iter().map(|(pos, _)| pos.0).min().unwrap_or(0);
+    let max_x = tiles.iter().map(|(pos, _)| pos.0).max().unwrap_or(0);
+    let min_z = tiles.iter().map(|(pos, _)| pos.1).min().unwrap_or(0);
+    let max_z = tiles.iter().map(|(pos, _)| pos.1).max().unwrap_or(0);
+    let (center_x, center_z) = (
+        (min_x + max_x + 1) * tile_w / 2,
+        (min_z + max_z + 1) * tile_h / 2,
+    );
+    let distance = |x: i32, z: i32| {
+        let (dx, dz) = ((x - center_x) as i64, (z - center_z) as i64);
+        dx * dx + dz * dz
+    };
+
+    let mut tiles: Vec<_> = tiles.iter().collect();
+    tiles.sort_by_key(|((x, z), _)| distance(x * tile_w + tile_w / 2, z * tile_h + tile_h / 2));
+    for ((tile_x, tile_z), tile_path) in tiles {
+        let tile = match read_tile(tile_path) {
+            Ok(tile) => tile,
+            Err(_) => continue,
+        };
+        let closest = (0..TILE_HEIGHT)
+            .flat_map(|z| (0..TILE_WIDTH).map(move |x| (x, z)))
+            .filter(|(x, z)| !tile.is_col_empty(column_nr_of_pos(*x, *z)))
+            .map(|(x, z)| (tile_x * tile_w + x as i32, tile_z * tile_h + z as i32, x, z))
+            .min_by_key(|(x, z, _, _)| distance(*x, *z));
+        if let Some((x, z, column_x, column_z)) = closest {
+            // above the top block, which is exported at y=0..=255
+            let height = tile.get_height(column_nr_of_pos(column_x, column_z));
+            return (x, height.clamp(1, ANVIL_WORLD_HEIGHT as i32), z);
+        }
+    }
+    (center_x, 64, center_z)
+}
+
+/// Returns the names of blocks that couldn't be exported.
+fn export_region(
+    tile_paths: &[PathBuf],
+    region_path: &PathBuf,
+    filler: u16,
+) -> Result<BTreeSet<String>, String> {
+    let mut chunks = Vec::new();
+    let mut unknown_blocks = BTreeSet::new();
+    for tile_path in tile_paths {
+        let tile = read_tile(tile_path)?;
+        let (tile_chunks, tile_unknown_blocks) = tile_to_legacy_chunks(&tile, filler);
+        chunks.extend(tile_chunks);
+        unknown_blocks.extend(tile_unknown_blocks);
+    }
+    write_anvil_region(region_path, &chunks)?;
+    Ok(unknown_blocks)
+}
@@ -0,0 +1,170 @@
+extern crate docopt;
+extern crate serde;
+extern crate threadpool;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use threadpool::ThreadPool;
+use voxelmap_cache::tile::{
+    get_tile_paths_in_dirs, get_xz_from_tile_path, is_tile_pos_in_bounds, read_tile,
+};
+use voxelmap_cache::waypoint::xaero::get_xaero_world_map_dir;
+use voxelmap_cache::xaero::{
+    get_xaero_region_pos_of_tile, tiles_to_xaero_region, write_xaero_region,
+};
+use voxelmap_cache::{parse_bounds, ProgressTracker};
+
+const USAGE: &str = "
+Usage: export_xaero [-q] [-t threads] [--between=<bounds>] [--dimension=<dim>] <cache-path> <output-path> <server>
+
+Converts voxelmap caches to Xaero's World Map regions,
+so Xaero users can start out with the whole map.
+
+cache-path contains voxelmap caches in the format `<x>,<z>.zip`,
+for example the result of `merge_caches`.
+
+output-path will contain the regions at
+`XaeroWorldMap/Multiplayer_<server>/<dimension directory>/mw$default/<x>_<z>.zip`,
+ready to be copied into the `.minecraft` directory.
+
+server is the server address as typed in Minecraft, for example `civclassic.com`
+
+Options:
+    -q, --quiet         Do not output info messages.
+    -t, --threads       Number of threads to use for parallel processing
+    --between=<bounds>  Only export regions at least partially within this bounding box,
+                        format: w,n,e,s [default: -99999,-99999,99999,99999]
+    --dimension=<dim>   Which dimension the caches are of:
+                        overworld, the_nether, the_end [default: overworld]
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_quiet: bool,
+    arg_threads: Option<usize>,
+    flag_between: String,
+    flag_dimension: String,
+    arg_cache_path: String,
+    arg_output_path: String,
+    arg_server: String,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let dimension_dir = get_xaero_world_map_dir(&args.flag_dimension).unwrap_or_else(|| {
+        eprintln!("Invalid arg: --dimension={}", &args.flag_dimension);
+        std::process::exit(1);
+    });
+
+    let tile_paths = get_tile_paths_in_dirs(&vec![args.arg_cache_path.clone()], verbose)
+        .unwrap_or_else(|e| {
+            eprintln!("Error while listing cache directory: {:?}", e);
+            std::process::exit(1);
+        });
+
+    let bounds = parse_bounds(&args.flag_between).unwrap_or_else(|e| {
+        eprintln!("Invalid arg: --between={} {}", &args.flag_between, e);
+        std::process::exit(1);
+    });
+
+    let mut tile_paths_by_region = HashMap::new();
+    for tile_path in tile_paths {
+        let pos = get_xz_from_tile_path(&tile_path).expect("getting pos from tile path");
+        if !is_tile_pos_in_bounds(pos, &bounds) {
+            continue;
+        }
+        tile_paths_by_region
+            .entry(get_xaero_region_pos_of_tile(pos))
+            .or_insert_with(Vec::new)
+            .push(tile_path);
+    }
+
+    // Xaero replaces the port separator
+    let server_dir = format!("Multiplayer_{}", args.arg_server.replace(":", "_"));
+    let regions_path = PathBuf::from(&args.arg_output_path)
+        .join("XaeroWorldMap")
+        .join(server_dir)
+        .join(dimension_dir)
+        .join("mw$default");
+    fs::create_dir_all(&regions_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to create output directory {:?} {:?}",
+            &regions_path, e
+        );
+        std::process::exit(1);
+    });
+
+    let total_work = tile_paths_by_region.len();
+    let mut progress = ProgressTracker::new(total_work);
+    if verbose {
+        eprintln!("Exporting {} regions to {:?}", total_work, &regions_path);
+    }
+
+    let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
+    let (tx, rx) = channel();
+
+    for (region_pos, tile_paths) in tile_paths_by_region.into_iter() {
+        let tx = tx.clone();
+        let region_path = regions_path.join(format!("{}_{}.zip", region_pos.0, region_pos.1));
+        pool.execute(move || {
+            let result = export_region(region_pos, &tile_paths, &region_path);
+            tx.send((region_path, result)).expect("Sending result");
+        });
+    }
+
+    let mut all_unknown_blocks = BTreeSet::new();
+    let mut all_clamped_columns = 0;
+    for work_done in 0..total_work {
+        let (region_path, result) = rx.recv().expect("Receiving next result");
+        match result {
+            Ok((unknown_blocks, clamped_columns)) => {
+                all_unknown_blocks.extend(unknown_blocks);
+                all_clamped_columns += clamped_columns;
+            }
+            Err(e) => eprintln!("Failed exporting region {:?} {}", region_path, e),
+        }
+
+        progress.progress_to(work_done);
+        if verbose {
+            progress.print_progress();
+        }
+    }
+
+    if !all_unknown_blocks.is_empty() {
+        eprintln!(
+            "These blocks have no legacy block id and were exported as air: {}",
+            all_unknown_blocks.into_iter().collect::<Vec<_>>().join(" ")
+        );
+    }
+    if all_clamped_columns > 0 {
+        eprintln!(
+            "{} columns were below y=0 or above y=255 and were exported at y=0 or y=255",
+            all_clamped_columns
+        );
+    }
+}
+
+/// Returns the names of blocks that couldn't be exported,
+/// and the number of columns whose height was clamped.
+fn export_region(
+    region_pos: (i32, i32),
+    tile_paths: &[PathBuf],
+    region_path: &PathBuf,
+) -> Result<(BTreeSet<String>, usize), String> {
+    let tiles = tile_paths
+        .iter()
+        .map(read_tile)
+        .collect::<Result<Vec<_>, _>>()?;
+    let (region, unknown_blocks, clamped_columns) = tiles_to_xaero_region(region_pos, &tiles);
+    write_xaero_region(region_path, &region)?;
+    Ok((unknown_blocks, clamped_columns))
+}
@@ -0,0 +1,134 @@
+extern crate docopt;
+extern crate lodepng;
+extern crate serde;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use voxelmap_cache::colorizer::grid::draw_grid;
+use voxelmap_cache::draw::Canvas;
+use voxelmap_cache::tile::get_xz_from_tile_path;
+use voxelmap_cache::ProgressTracker;
+
+const USAGE: &str = "
+Usage: grid [-q] [--no-labels] <tiles-root> <output-root>
+
+Draws chunk, tile and region grid lines onto a rendered tileset,
+with world coordinate labels, at a density fitting each zoom level.
+
+tiles-root contains the zoom level directories `z0`, `z-1`, ...
+as created by `render` and `py/zoom.py`.
+The tiles are left untouched, so zooming out further still works;
+the gridded tiles are written to the same layout in output-root.
+
+Options:
+    -q, --quiet         Do not output info messages.
+    --no-labels         Only draw the lines, without coordinate labels.
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    arg_tiles_root: String,
+    arg_output_root: String,
+    flag_no_labels: bool,
+    flag_quiet: bool,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let zoom_dirs = get_zoom_dirs(Path::new(&args.arg_tiles_root)).unwrap_or_else(|e| {
+        eprintln!("Error while listing tileset root: {}", e);
+        std::process::exit(1);
+    });
+
+    let mut work = Vec::new();
+    for (zoom, dir) in zoom_dirs {
+        let entries = fs::read_dir(&dir).unwrap_or_else(|e| {
+            eprintln!("Error while listing {:?}: {:?}", dir, e);
+            std::process::exit(1);
+        });
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.extension().map_or(false, |ext| ext == "png") {
+                work.push((zoom, path));
+            }
+        }
+    }
+
+    let total_work = work.len();
+    let mut progress = ProgressTracker::new(total_work);
+    if verbose {
+        eprintln!("Drawing grid onto {} tiles", total_work);
+    }
+
+    for (work_done, (zoom, img_path)) in work.into_iter().enumerate() {
+        let out_dir = format!("{}/z{}", args.arg_output_root, zoom);
+        if let Err(e) = draw_grid_on_image(&img_path, &out_dir, zoom, !args.flag_no_labels) {
+            eprintln!("Failed drawing grid onto {:?} {}", img_path, e);
+        }
+
+        progress.progress_to(work_done);
+        if verbose {
+            progress.print_progress();
+        }
+    }
+}
+
+/// Zoom levels above 0 are skipped, their pixels are smaller than a block.
+fn get_zoom_dirs(tiles_root: &Path) -> Result<Vec<(i32, PathBuf)>, String> {
+    let mut zoom_dirs = Vec::new();
+    for entry in fs::read_dir(tiles_root).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let zoom = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| name.starts_with('z'))
+            .and_then(|name| name[1..].parse::<i32>().ok());
+        if let Some(zoom) = zoom {
+            if zoom <= 0 && path.is_dir() {
+                zoom_dirs.push((zoom, path));
+            }
+        }
+    }
+    zoom_dirs.sort();
+    Ok(zoom_dirs)
+}
+
+fn draw_grid_on_image(
+    img_path: &PathBuf,
+    out_dir: &str,
+    zoom: i32,
+    labels: bool,
+) -> Result<(), String> {
+    let (tile_x, tile_z) = get_xz_from_tile_path(img_path)?;
+    let bitmap = lodepng::decode32_file(img_path).map_err(|e| e.to_string())?;
+    let mut pixbuf: Vec<u32> = bitmap
+        .buffer
+        .iter()
+        .map(|p| p.r as u32 | (p.g as u32) << 8 | (p.b as u32) << 16 | (p.a as u32) << 24)
+        .collect();
+
+    // zoomed out tiles have the same size in pixels, but cover more blocks
+    let blocks_per_pixel = 1i64 << -zoom;
+    let x0 = tile_x as i64 * bitmap.width as i64 * blocks_per_pixel;
+    let z0 = tile_z as i64 * bitmap.height as i64 * blocks_per_pixel;
+    {
+        let mut canvas = Canvas::new(&mut pixbuf, bitmap.width, bitmap.height);
+        draw_grid(&mut canvas, x0, z0, blocks_per_pixel, labels);
+    }
+
+    fs::create_dir_all(out_dir).map_err(|e| e.to_string())?;
+    let out_path = format!(
+        "{}/{}",
+        out_dir,
+        img_path.file_name().unwrap().to_string_lossy()
+    );
+    lodepng::encode32_file(&out_path, &pixbuf[..], bitmap.width, bitmap.height)
+        .map_err(|e| e.to_string())
+}
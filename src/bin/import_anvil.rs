@@ -0,0 +1,127 @@
+extern crate docopt;
+extern crate serde;
+extern crate threadpool;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use threadpool::ThreadPool;
+use voxelmap_cache::anvil::{anvil_chunks_to_tiles, get_xz_from_anvil_path, read_anvil_region};
+use voxelmap_cache::tile::write_contrib_tiles;
+use voxelmap_cache::ProgressTracker;
+
+const USAGE: &str = "
+Usage: import_anvil [-q] [-t threads] [--contrib=<name>] <region-path> <output-path>
+
+Converts the regions of a Minecraft world to voxelmap caches.
+Chunks saved by 1.12 and earlier keep their numeric block ids as legacy block names,
+chunks saved by 1.13 and later get their block states and biome keys.
+Chunks that aren't fully generated are left out.
+
+region-path contains the world's region files in the format `r.<x>.<z>.mca`,
+for example `<world>/region/` for the overworld.
+
+output-path is a directory that will contain the voxelmap caches,
+two by two of them per region.
+
+Options:
+    -q, --quiet         Do not output info messages.
+    -t, --threads       Number of threads to use for parallel processing
+    --contrib=<name>    Name the caches `<x>,<z>,<name>.zip` instead of `<x>,<z>.zip`,
+                        so they can be merged with other contributions using merge_caches.
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_quiet: bool,
+    arg_threads: Option<usize>,
+    flag_contrib: Option<String>,
+    arg_region_path: String,
+    arg_output_path: String,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let region_paths: Vec<PathBuf> = fs::read_dir(&args.arg_region_path)
+        .unwrap_or_else(|e| {
+            eprintln!("Error while listing region directory: {:?}", e);
+            std::process::exit(1);
+        })
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| get_xz_from_anvil_path(path).is_ok())
+        .collect();
+
+    fs::create_dir_all(&args.arg_output_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to create output directory {:?} {:?}",
+            &args.arg_output_path, e
+        );
+        std::process::exit(1);
+    });
+
+    let total_work = region_paths.len();
+    let mut progress = ProgressTracker::new(total_work);
+    if verbose {
+        eprintln!(
+            "Converting {} regions to {:?}",
+            total_work, &args.arg_output_path
+        );
+    }
+
+    let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
+    let (tx, rx) = channel();
+
+    for region_path in region_paths.into_iter() {
+        let tx = tx.clone();
+        let output_path = args.arg_output_path.clone();
+        let contrib = args.flag_contrib.clone();
+        pool.execute(move || {
+            let result = convert_region(&region_path, &output_path, contrib.as_deref());
+            tx.send((region_path, result)).expect("Sending result");
+        });
+    }
+
+    let mut total_tiles = 0;
+    for work_done in 0..total_work {
+        let (region_path, result) = rx.recv().expect("Receiving next result");
+        match result {
+            Ok(num_tiles) => total_tiles += num_tiles,
+            Err(e) => eprintln!("Failed converting region {:?} {}", region_path, e),
+        }
+
+        progress.progress_to(work_done);
+        if verbose {
+            progress.print_progress();
+        }
+    }
+
+    if verbose {
+        eprintln!(
+            "Done converting. Wrote {} caches from {} regions",
+            total_tiles, total_work
+        );
+    }
+}
+
+/// Returns the number of caches written.
+fn convert_region(
+    region_path: &PathBuf,
+    output_path: &str,
+    contrib: Option<&str>,
+) -> Result<usize, String> {
+    let chunks = read_anvil_region(region_path)?;
+    let mtime = fs::metadata(region_path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| e.to_string())?;
+
+    let tiles = anvil_chunks_to_tiles(&chunks);
+    write_contrib_tiles(&tiles, output_path, contrib, mtime)?;
+    Ok(tiles.len())
+}
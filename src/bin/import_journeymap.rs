@@ -0,0 +1,161 @@
+extern crate docopt;
+extern crate lodepng;
+extern crate serde;
+extern crate threadpool;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use threadpool::ThreadPool;
+use voxelmap_cache::tile::get_xz_from_tile_path;
+use voxelmap_cache::{ProgressTracker, TILE_HEIGHT, TILE_WIDTH};
+
+const USAGE: &str = "
+Usage: import_journeymap [-q] [-t threads] [--map-type=<type>] <dimension-path> <output-path>
+
+Cuts JourneyMap's region images into images aligned to the voxelmap tiles.
+
+dimension-path is one dimension of a JourneyMap world, for example
+`journeymap/data/mp/<server>/overworld/` (or `DIM0/` in older versions).
+
+Only JourneyMap's images are read, its chunk data is not documented,
+so no voxelmap caches can be made from it.
+Each 512x512 region image becomes up to four 256x256 images `<x>,<z>.png` in output-path.
+
+Options:
+    -q, --quiet         Do not output info messages.
+    -t, --threads       Number of threads to use for parallel processing
+    --map-type=<type>   Which of JourneyMap's images to use: day, night, topo, biome [default: day]
+";
+
+/// JourneyMap's images cover 512x512 blocks.
+const JOURNEYMAP_REGION_WIDTH: usize = 512;
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_quiet: bool,
+    arg_threads: Option<usize>,
+    flag_map_type: String,
+    arg_dimension_path: String,
+    arg_output_path: String,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let dimension_path = Path::new(&args.arg_dimension_path);
+    let image_paths = list_region_paths(&dimension_path.join(&args.flag_map_type), "png");
+    if image_paths.is_empty() {
+        eprintln!(
+            "No {} images found in {:?}",
+            &args.flag_map_type, dimension_path
+        );
+        std::process::exit(1);
+    }
+
+    fs::create_dir_all(&args.arg_output_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to create output directory {:?} {:?}",
+            &args.arg_output_path, e
+        );
+        std::process::exit(1);
+    });
+
+    let total_work = image_paths.len();
+    let mut progress = ProgressTracker::new(total_work);
+    if verbose {
+        eprintln!(
+            "Cutting {} region images into {:?}",
+            total_work, &args.arg_output_path
+        );
+    }
+
+    let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
+    let (tx, rx) = channel();
+
+    for region_path in image_paths.into_iter() {
+        let tx = tx.clone();
+        let output_path = args.arg_output_path.clone();
+        pool.execute(move || {
+            let result = cut_image(&region_path, &output_path);
+            tx.send((region_path, result)).expect("Sending result");
+        });
+    }
+
+    let mut total_images = 0;
+    for work_done in 0..total_work {
+        let (region_path, result) = rx.recv().expect("Receiving next result");
+        match result {
+            Ok(num_images) => total_images += num_images,
+            Err(e) => eprintln!("Failed cutting region image {:?} {}", region_path, e),
+        }
+
+        progress.progress_to(work_done);
+        if verbose {
+            progress.print_progress();
+        }
+    }
+
+    if verbose {
+        eprintln!(
+            "Done cutting. Wrote {} images from {} regions",
+            total_images, total_work
+        );
+    }
+}
+
+/// Region files named `<x>,<z>.<extension>`, none if the directory doesn't exist.
+fn list_region_paths(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().map_or(false, |ext| ext == extension)
+                    && get_xz_from_tile_path(path).is_ok()
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Cuts the 512x512 region image into the four 256x256 tile images it covers,
+/// leaving out fully transparent ones. Returns the number of images written.
+fn cut_image(region_path: &PathBuf, images_path: &str) -> Result<usize, String> {
+    let (region_x, region_z) = get_xz_from_tile_path(region_path)?;
+    let bitmap = lodepng::decode32_file(region_path).map_err(|e| e.to_string())?;
+    if bitmap.width != JOURNEYMAP_REGION_WIDTH || bitmap.height != JOURNEYMAP_REGION_WIDTH {
+        return Err(format!(
+            "image is {}x{} instead of {}x{}",
+            bitmap.width, bitmap.height, JOURNEYMAP_REGION_WIDTH, JOURNEYMAP_REGION_WIDTH
+        ));
+    }
+
+    let tiles_per_region = JOURNEYMAP_REGION_WIDTH / TILE_WIDTH;
+    let mut num_images = 0;
+    for tile_dz in 0..tiles_per_region {
+        for tile_dx in 0..tiles_per_region {
+            let mut pixbuf = Vec::with_capacity(TILE_WIDTH * TILE_HEIGHT);
+            for z in 0..TILE_HEIGHT {
+                let start = (tile_dz * TILE_HEIGHT + z) * bitmap.width + tile_dx * TILE_WIDTH;
+                pixbuf.extend_from_slice(&bitmap.buffer[start..start + TILE_WIDTH]);
+            }
+            if pixbuf.iter().all(|pixel| pixel.a == 0) {
+                continue;
+            }
+
+            let tile_x = region_x * tiles_per_region as i32 + tile_dx as i32;
+            let tile_z = region_z * tiles_per_region as i32 + tile_dz as i32;
+            let img_path = format!("{}/{},{}.png", images_path, tile_x, tile_z);
+            lodepng::encode32_file(&img_path, &pixbuf[..], TILE_WIDTH, TILE_HEIGHT)
+                .map_err(|e| e.to_string())?;
+            num_images += 1;
+        }
+    }
+    Ok(num_images)
+}
@@ -0,0 +1,282 @@
+extern crate docopt;
+extern crate serde;
+extern crate serde_json;
+extern crate threadpool;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use threadpool::ThreadPool;
+use voxelmap_cache::mc::blocks::BlockNamePattern;
+use voxelmap_cache::tile::{
+    get_tile_paths_in_dirs, get_xz_from_tile_path, is_tile_pos_in_bounds, read_tile, LAYERS,
+};
+use voxelmap_cache::{
+    csv_quote, parse_bounds, ProgressTracker, TILE_COLUMNS, TILE_HEIGHT, TILE_WIDTH,
+};
+
+const USAGE: &str = "
+Usage: locate [-q] [-t threads] [--between=<bounds>] [--json] [--cluster] <cache-path> <query>...
+
+Prints the world coordinates of all columns containing a block matching any query,
+in any of the four layers, as CSV with the columns:
+x, z, layer, height, biome, block, query, count
+
+cache-path contains voxelmap caches in the format `<x>,<z>.zip`
+
+query is a block name, `*` matches anything, queries without namespace match any namespace.
+Properties in brackets must match too, others are ignored.
+Examples: minecraft:beacon '*_bed' 'water[level=0]' '*[waterlogged=true]'
+
+Options:
+    -q, --quiet         Do not output info messages.
+    -t, --threads       Number of threads to use for parallel processing
+    --between=<bounds>  Only search within this bounding box,
+                        format: w,n,e,s [default: -99999,-99999,99999,99999]
+    --json              Output a JSON array instead of CSV.
+    --cluster           Combine adjacent results for the same query into one,
+                        located at their center, with the number of combined results as count.
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_quiet: bool,
+    arg_threads: Option<usize>,
+    flag_between: String,
+    flag_json: bool,
+    flag_cluster: bool,
+    arg_cache_path: String,
+    arg_query: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Hit {
+    x: i32,
+    z: i32,
+    layer: &'static str,
+    height: i32,
+    biome: String,
+    block: String,
+    query: String,
+    count: usize,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let queries = args
+        .arg_query
+        .iter()
+        .map(|q| BlockNamePattern::new(q))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| {
+            eprintln!("Invalid query: {}", e);
+            std::process::exit(1);
+        });
+    let queries = Arc::new(queries);
+
+    let tile_paths = get_tile_paths_in_dirs(&vec![args.arg_cache_path.clone()], verbose)
+        .unwrap_or_else(|e| {
+            eprintln!("Error while listing cache directory: {:?}", e);
+            std::process::exit(1);
+        });
+
+    let bounds = parse_bounds(&args.flag_between).unwrap_or_else(|e| {
+        eprintln!("Invalid arg: --between={} {}", &args.flag_between, e);
+        std::process::exit(1);
+    });
+
+    let tile_paths: Vec<PathBuf> = tile_paths
+        .into_iter()
+        .filter(|path| is_tile_pos_in_bounds(get_xz_from_tile_path(path).unwrap(), &bounds))
+        .collect();
+
+    let total_work = tile_paths.len();
+    let mut progress = ProgressTracker::new(total_work);
+    if verbose {
+        eprintln!("Searching {:?} tiles", total_work)
+    }
+
+    let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
+    let (tx, rx) = channel();
+
+    let bounds = Arc::new(bounds);
+    for tile_path in tile_paths.into_iter() {
+        let tx = tx.clone();
+        let queries = queries.clone();
+        let bounds = bounds.clone();
+        pool.execute(move || {
+            let result = search_tile(&tile_path, &queries, &bounds);
+            tx.send((tile_path, result)).expect("Sending result");
+        });
+    }
+
+    let mut hits = Vec::new();
+
+    for work_done in 0..total_work {
+        let (tile_path, result) = rx.recv().expect("Receiving next result");
+        match result {
+            Ok(tile_hits) => hits.extend(tile_hits),
+            Err(msg) => eprintln!("Failed searching tile {:?} {}", tile_path, msg),
+        }
+
+        progress.progress_to(work_done);
+        if verbose {
+            progress.print_progress();
+        }
+    }
+
+    hits.sort_by_key(|hit| (hit.z, hit.x));
+    if args.flag_cluster {
+        hits = cluster_hits(hits);
+    }
+
+    if verbose {
+        eprintln!("Found {} results", hits.len());
+    }
+
+    if args.flag_json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&hits).expect("Serializing results")
+        );
+    } else {
+        println!("x,z,layer,height,biome,block,query,count");
+        for hit in &hits {
+            println!(
+                "{},{},{},{},{},{},{},{}",
+                hit.x,
+                hit.z,
+                hit.layer,
+                hit.height,
+                csv_quote(&hit.biome),
+                csv_quote(&hit.block),
+                csv_quote(&hit.query),
+                hit.count
+            );
+        }
+    }
+}
+
+/// Each column is reported at most once per query, in the first matching layer.
+fn search_tile(
+    tile_path: &PathBuf,
+    queries: &[BlockNamePattern],
+    bounds: &[i32],
+) -> Result<Vec<Hit>, String> {
+    let tile = read_tile(tile_path)?;
+    let (tile_x, tile_z) = get_xz_from_tile_path(tile_path)?;
+
+    // which queries match each block id of this tile
+    let matches: Vec<Vec<usize>> = (0..tile.names.len() as u16)
+        .map(|block_nr| {
+            let block_state = tile.get_block_state(block_nr);
+            (0..queries.len())
+                .filter(|query_nr| queries[*query_nr].matches(block_state))
+                .collect()
+        })
+        .collect();
+    if matches.iter().all(|m| m.is_empty()) {
+        return Ok(Vec::new());
+    }
+
+    let mut hits = Vec::new();
+    for column_nr in 0..TILE_COLUMNS {
+        let x = tile_x * TILE_WIDTH as i32 + (column_nr % TILE_WIDTH) as i32;
+        let z = tile_z * TILE_HEIGHT as i32 + (column_nr / TILE_WIDTH) as i32;
+        if x < bounds[0] || z < bounds[1] || x >= bounds[2] || z >= bounds[3] {
+            continue;
+        }
+        let mut found_queries = Vec::new();
+        for layer in LAYERS.iter() {
+            let block_nr = tile.get_layer_blockstate(*layer, column_nr);
+            if block_nr == 0 {
+                continue;
+            }
+            for query_nr in matches.get(block_nr as usize).unwrap_or(&Vec::new()) {
+                if found_queries.contains(query_nr) {
+                    continue;
+                }
+                found_queries.push(*query_nr);
+                hits.push(Hit {
+                    x,
+                    z,
+                    layer: layer.name(),
+                    height: tile.get_layer_height(*layer, column_nr),
+                    biome: tile.get_biome_name(column_nr).to_string(),
+                    block: tile.get_block_name(block_nr).to_string(),
+                    query: queries[*query_nr].as_str().to_string(),
+                    count: 1,
+                });
+            }
+        }
+    }
+    Ok(hits)
+}
+
+/// Combines hits of the same query that touch, also diagonally.
+/// The combined hit is at the rounded center of its parts,
+/// other fields are taken from the north-west-most part.
+fn cluster_hits(hits: Vec<Hit>) -> Vec<Hit> {
+    let mut parents: Vec<usize> = (0..hits.len()).collect();
+    fn find_root(parents: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        parents[i] = root;
+        root
+    }
+
+    let index_by_pos: HashMap<(&str, i32, i32), usize> = hits
+        .iter()
+        .enumerate()
+        .map(|(i, hit)| ((hit.query.as_str(), hit.x, hit.z), i))
+        .collect();
+    for (i, hit) in hits.iter().enumerate() {
+        for dz in -1..=1 {
+            for dx in -1..=1 {
+                let key = (hit.query.as_str(), hit.x + dx, hit.z + dz);
+                if let Some(&j) = index_by_pos.get(&key) {
+                    let root_i = find_root(&mut parents, i);
+                    let root_j = find_root(&mut parents, j);
+                    parents[root_i.max(root_j)] = root_i.min(root_j);
+                }
+            }
+        }
+    }
+
+    // hits are sorted north to south, so the root is the north-west-most part
+    let mut clusters: Vec<(Hit, i64, i64)> = Vec::new();
+    let mut cluster_by_root = HashMap::new();
+    for i in 0..hits.len() {
+        let root = find_root(&mut parents, i);
+        let cluster_nr = *cluster_by_root.entry(root).or_insert_with(|| {
+            let mut hit = hits[root].clone();
+            hit.count = 0;
+            clusters.push((hit, 0, 0));
+            clusters.len() - 1
+        });
+        let (cluster, sum_x, sum_z) = &mut clusters[cluster_nr];
+        cluster.count += 1;
+        *sum_x += hits[i].x as i64;
+        *sum_z += hits[i].z as i64;
+    }
+
+    clusters
+        .into_iter()
+        .map(|(mut hit, sum_x, sum_z)| {
+            let count = hit.count as f64;
+            hit.x = (sum_x as f64 / count).round() as i32;
+            hit.z = (sum_z as f64 / count).round() as i32;
+            hit
+        })
+        .collect()
+}
@@ -19,13 +19,12 @@ use threadpool::ThreadPool;
 use voxelmap_cache::tile::{
     first_column_nr_of_chunk_nr, get_contrib_from_tile_path, get_tile_paths_in_dirs,
     get_xz_from_tile_path, is_tile_pos_in_bounds, read_tile, write_tile, KeysMap, Tile, TilePos,
-    COLUMN_BYTES_MODERN,
 };
 use voxelmap_cache::{
     parse_bounds, ProgressTracker, CHUNK_HEIGHT, CHUNK_WIDTH, TILE_CHUNKS, TILE_COLUMNS, TILE_WIDTH,
 };
 
-const USAGE: &'static str = "
+const USAGE: &str = "
 Usage: merge_caches [-q] [-t threads] [--between=<bounds>] <output-path> <cache-path>...
 
 cache-path contains voxelmap caches in the format
@@ -70,7 +69,7 @@ fn main() {
 
     let mut tile_paths_by_pos = Box::new(HashMap::new());
     for tile_path in &tile_paths {
-        let pos = get_xz_from_tile_path(&tile_path).expect("getting pos from tile path");
+        let pos = get_xz_from_tile_path(tile_path).expect("getting pos from tile path");
         tile_paths_by_pos
             .entry(pos)
             .or_insert_with(Vec::new)
@@ -79,7 +78,7 @@ fn main() {
 
     // start with most intense tile positions first (most contribs per tile pos)
     let mut paths_sorted: Vec<(TilePos, Vec<PathBuf>)> = tile_paths_by_pos.into_iter().collect();
-    paths_sorted.sort_by(|(_, a), (_, b)| b.len().cmp(&a.len()));
+    paths_sorted.sort_by_key(|(_, paths)| std::cmp::Reverse(paths.len()));
 
     fs::create_dir_all(&args.arg_output_path).unwrap_or_else(|e| {
         println!(
@@ -179,15 +178,7 @@ pub fn merge_tile_from_contribs(
     let mut used = Vec::new();
     let mut skipped = Vec::new();
 
-    let mut out_tile = Box::new(Tile {
-        // source: Some(out_path.clone()),
-        // pos: get_xz_from_tile_path(&out_path).ok(),
-        pos: None,
-        data: vec![0; TILE_COLUMNS * COLUMN_BYTES_MODERN],
-        keys: HashMap::new(),
-        names: vec![], // HACK: unused
-        source: None,
-    });
+    let mut out_tile = Box::new(Tile::new(None));
 
     let mut num_chunks_left = TILE_CHUNKS;
     let mut chunks_done = vec![false; num_chunks_left];
@@ -207,7 +198,7 @@ pub fn merge_tile_from_contribs(
             }
         };
 
-        if num_chunks_left <= 0 {
+        if num_chunks_left == 0 {
             break;
         }
     }
@@ -229,21 +220,28 @@ pub fn merge_tile_from_contribs(
 fn merge_two_tiles(
     out_tile: &mut Tile,
     under_tile: &Tile,
-    chunks_done: &mut Vec<bool>,
+    chunks_done: &mut [bool],
 ) -> Result<usize, String> {
     let mut converter = merge_keys_and_build_converter(&mut out_tile.keys, &under_tile.keys);
+    let biome_converter = merge_biome_keys_and_build_converter(out_tile, under_tile);
 
     let mut chunks_processed = 0;
 
-    for chunk_nr in 0..TILE_CHUNKS {
-        if chunks_done[chunk_nr] || under_tile.is_chunk_empty(chunk_nr) {
+    for (chunk_nr, done) in chunks_done.iter_mut().enumerate() {
+        if *done || under_tile.is_chunk_empty(chunk_nr) {
             continue;
         }
 
-        copy_convert_chunk(&mut converter, out_tile, under_tile, chunk_nr)
-            .map_err(|e| e.to_string())?;
+        copy_convert_chunk(
+            &mut converter,
+            &biome_converter,
+            out_tile,
+            under_tile,
+            chunk_nr,
+        )
+        .map_err(|e| e.to_string())?;
 
-        chunks_done[chunk_nr] = true;
+        *done = true;
         chunks_processed += 1;
     }
 
@@ -266,8 +264,41 @@ fn merge_keys_and_build_converter(keys_out: &mut KeysMap, keys_in: &KeysMap) ->
     converter
 }
 
+/// `None` if both tiles have legacy biome ids, which are copied as they are.
+/// Otherwise the output gets biome keys, so tiles with biome keys can be merged with legacy ones.
+fn merge_biome_keys_and_build_converter(
+    out_tile: &mut Tile,
+    under_tile: &Tile,
+) -> Option<BlockIdConverter> {
+    if out_tile.biome_keys.is_empty() && under_tile.biome_keys.is_empty() {
+        return None;
+    }
+    out_tile.use_biome_keys();
+    if !under_tile.biome_keys.is_empty() {
+        return Some(merge_keys_and_build_converter(
+            &mut out_tile.biome_keys,
+            &under_tile.biome_keys,
+        ));
+    }
+    // only the legacy biomes that occur, to keep the keys short
+    let mut legacy_keys = KeysMap::new();
+    for column_nr in 0..TILE_COLUMNS {
+        if under_tile.is_col_empty(column_nr) {
+            continue;
+        }
+        if let Some(biome) = under_tile.get_biome(column_nr) {
+            legacy_keys.insert(biome.name.to_string(), under_tile.get_biome_id(column_nr));
+        }
+    }
+    Some(merge_keys_and_build_converter(
+        &mut out_tile.biome_keys,
+        &legacy_keys,
+    ))
+}
+
 fn copy_convert_chunk(
     converter: &mut BlockIdConverter,
+    biome_converter: &Option<BlockIdConverter>,
     out_tile: &mut Tile,
     under_tile: &Tile,
     chunk_nr: usize,
@@ -288,7 +319,16 @@ fn copy_convert_chunk(
             out_tile.set_transparent_light(column_nr, under_tile.get_transparent_light(column_nr));
             out_tile.set_foliage_height(column_nr, under_tile.get_foliage_height(column_nr));
             out_tile.set_foliage_light(column_nr, under_tile.get_foliage_light(column_nr));
-            out_tile.set_biome_id(column_nr, under_tile.get_biome_id(column_nr));
+            let biome_id = under_tile.get_biome_id(column_nr);
+            out_tile.set_biome_id(
+                column_nr,
+                match biome_converter {
+                    Some(biome_converter) => {
+                        biome_converter.get(biome_id as usize).cloned().unwrap_or(0)
+                    }
+                    None => biome_id,
+                },
+            );
 
             out_tile.set_blockstate(
                 column_nr,
@@ -0,0 +1,393 @@
+extern crate docopt;
+extern crate lodepng;
+extern crate serde;
+extern crate serde_json;
+extern crate threadpool;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use threadpool::ThreadPool;
+use voxelmap_cache::area::{read_areas, Area};
+use voxelmap_cache::ccnatural::{get_naturality_color, Naturality, NATURALITY_VALUES};
+use voxelmap_cache::colorizer::get_column_naturality;
+use voxelmap_cache::naturality::NaturalityRules;
+use voxelmap_cache::palette::Gradient;
+use voxelmap_cache::tile::{
+    get_tile_paths_in_dirs, get_xz_from_tile_path, is_tile_pos_in_bounds, read_tile,
+};
+use voxelmap_cache::{
+    csv_quote, parse_bounds, ProgressTracker, CHUNK_HEIGHT, CHUNK_WIDTH, TILE_CHUNKS, TILE_COLUMNS,
+    TILE_HEIGHT, TILE_WIDTH,
+};
+
+const USAGE: &str = "
+Usage: naturality_stats [-q] [-t threads] [--json] [--areas=<file>] [--naturality=<file>] [--between=<bounds>] <cache-path> <output-path>
+
+Classifies every column like the `naturality` render mode,
+and counts how many columns are of each naturality, per area, tile and chunk.
+
+cache-path contains voxelmap caches in the format `<x>,<z>.zip`
+
+output-path is a directory that will contain `areas.csv`, `tiles.csv` and `chunks.csv`
+(`.json` with --json), and `built.png`, a heatmap of the fraction of built columns
+with one pixel per chunk, whose top left corner is the chunk named in `built.txt`
+
+Options:
+    -q, --quiet             Do not output info messages.
+    -t, --threads           Number of threads to use for parallel processing
+    --json                  Write JSON instead of CSV.
+    --areas=<file>          Named areas to count, a JSON list of
+                            `{\"name\": ..., \"polygon\": [[x, z], ...]}`
+                            or `{\"name\": ..., \"bounds\": \"w,n,e,s\"}`,
+                            or a GeoJSON FeatureCollection of polygons
+    --naturality=<file>     Naturality rules (TSV or JSON), as for `render -o naturality=<file>`
+    --between=<bounds>      Only count tiles at least partially within this bounding box,
+                            format: w,n,e,s [default: -99999,-99999,99999,99999]
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_json: bool,
+    flag_areas: Option<String>,
+    flag_naturality: Option<String>,
+    flag_between: String,
+    arg_cache_path: String,
+    arg_output_path: String,
+    flag_quiet: bool,
+    arg_threads: Option<usize>,
+}
+
+/// number of columns, by `Naturality as usize`
+type NaturalityCounts = [usize; 6];
+
+fn merge_counts_into(counts: &mut NaturalityCounts, other: &NaturalityCounts) {
+    for (count, other_count) in counts.iter_mut().zip(other.iter()) {
+        *count += other_count;
+    }
+}
+
+fn get_built_fraction(counts: &NaturalityCounts) -> f64 {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    counts[Naturality::Built as usize] as f64 / total as f64
+}
+
+struct TileStats {
+    pos: (i32, i32),
+    /// by chunk number within the tile, x + z * 16
+    chunks: Vec<NaturalityCounts>,
+    /// by area index
+    areas: Vec<NaturalityCounts>,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let bounds = parse_bounds(&args.flag_between).unwrap_or_else(|e| {
+        eprintln!("Invalid arg: --between={} {}", &args.flag_between, e);
+        std::process::exit(1);
+    });
+
+    let areas = match &args.flag_areas {
+        Some(path) => read_areas(Path::new(path)).unwrap_or_else(|e| {
+            eprintln!("Error while reading areas: {}", e);
+            std::process::exit(1);
+        }),
+        None => Vec::new(),
+    };
+
+    let mut rules = NaturalityRules::new();
+    if let Some(path) = &args.flag_naturality {
+        rules.read(Path::new(path)).unwrap_or_else(|e| {
+            eprintln!("Error while reading naturality rules: {}", e);
+            std::process::exit(1);
+        });
+    }
+
+    let tile_paths = get_tile_paths_in_dirs(&vec![args.arg_cache_path.clone()], verbose)
+        .unwrap_or_else(|e| {
+            eprintln!("Error while listing cache directory: {:?}", e);
+            std::process::exit(1);
+        });
+    let tile_paths: Vec<PathBuf> = tile_paths
+        .into_iter()
+        .filter(|path| is_tile_pos_in_bounds(get_xz_from_tile_path(path).unwrap(), &bounds))
+        .collect();
+
+    fs::create_dir_all(&args.arg_output_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to create output directory {:?} {:?}",
+            &args.arg_output_path, e
+        );
+        std::process::exit(1);
+    });
+
+    let total_work = tile_paths.len();
+    let mut progress = ProgressTracker::new(total_work);
+    if verbose {
+        eprintln!("Counting naturality in {:?} tiles", total_work)
+    }
+
+    let rules = Arc::new(rules);
+    let areas = Arc::new(areas);
+    let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
+    let (tx, rx) = channel();
+
+    for tile_path in tile_paths.into_iter() {
+        let tx = tx.clone();
+        let rules = rules.clone();
+        let areas = areas.clone();
+        pool.execute(move || {
+            let result = count_tile(&tile_path, &rules, &areas);
+            tx.send((tile_path, result)).expect("Sending result");
+        });
+    }
+
+    let mut area_counts: Vec<NaturalityCounts> = vec![[0; 6]; areas.len()];
+    let mut tile_counts: BTreeMap<(i32, i32), NaturalityCounts> = BTreeMap::new();
+    let mut chunk_counts: BTreeMap<(i32, i32), NaturalityCounts> = BTreeMap::new();
+
+    for work_done in 0..total_work {
+        let (tile_path, result) = rx.recv().expect("Receiving next result");
+        let stats = match result {
+            Ok(stats) => stats,
+            Err(msg) => {
+                eprintln!("Failed counting tile {:?} {}", tile_path, msg);
+                continue;
+            }
+        };
+
+        let (tile_x, tile_z) = stats.pos;
+        let chunks_per_row = (TILE_WIDTH / CHUNK_WIDTH) as i32;
+        let mut counts = [0; 6];
+        for (chunk_nr, chunk) in stats.chunks.iter().enumerate() {
+            if chunk.iter().sum::<usize>() == 0 {
+                continue;
+            }
+            merge_counts_into(&mut counts, chunk);
+            let chunk_x = tile_x * chunks_per_row + chunk_nr as i32 % chunks_per_row;
+            let chunk_z = tile_z * chunks_per_row + chunk_nr as i32 / chunks_per_row;
+            chunk_counts.insert((chunk_x, chunk_z), *chunk);
+        }
+        if counts.iter().sum::<usize>() > 0 {
+            merge_counts_into(tile_counts.entry(stats.pos).or_insert([0; 6]), &counts);
+        }
+        for (total, counts) in area_counts.iter_mut().zip(stats.areas.iter()) {
+            merge_counts_into(total, counts);
+        }
+
+        progress.progress_to(work_done);
+        if verbose {
+            progress.print_progress();
+        }
+    }
+
+    let output_path = Path::new(&args.arg_output_path);
+    let area_rows: Vec<(Vec<Value>, NaturalityCounts)> = areas
+        .iter()
+        .zip(area_counts.iter())
+        .map(|(area, counts)| (vec![Value::from(area.name.as_str())], *counts))
+        .collect();
+    let tile_rows = get_pos_rows(&tile_counts);
+    let chunk_rows = get_pos_rows(&chunk_counts);
+    let results = vec![
+        write_stats(output_path, "areas", &["name"], &area_rows, args.flag_json),
+        write_stats(
+            output_path,
+            "tiles",
+            &["x", "z"],
+            &tile_rows,
+            args.flag_json,
+        ),
+        write_stats(
+            output_path,
+            "chunks",
+            &["x", "z"],
+            &chunk_rows,
+            args.flag_json,
+        ),
+        write_heatmap(output_path, &chunk_counts),
+    ];
+    for result in results {
+        if let Err(e) = result {
+            eprintln!("Failed writing statistics: {}", e);
+        }
+    }
+
+    if verbose {
+        for (block_name, count) in rules.unclassified_blocks() {
+            eprintln!("No naturality rule for {} ({} times)", block_name, count);
+        }
+        let time_total = progress.elapsed();
+        eprintln!(
+            "Done counting. Took {}:{:02} for all {} tiles",
+            time_total.as_secs() / 60,
+            time_total.as_secs() % 60,
+            total_work,
+        );
+    };
+}
+
+fn count_tile(
+    tile_path: &PathBuf,
+    rules: &NaturalityRules,
+    areas: &[Area],
+) -> Result<TileStats, String> {
+    let tile = read_tile(tile_path).map_err(|e| e.to_string())?;
+    let pos = get_xz_from_tile_path(tile_path)?;
+    let (tile_x, tile_z) = pos;
+
+    let mut stats = TileStats {
+        pos,
+        chunks: vec![[0; 6]; TILE_CHUNKS],
+        areas: vec![[0; 6]; areas.len()],
+    };
+    let nearby_areas: Vec<usize> = (0..areas.len())
+        .filter(|area_nr| areas[*area_nr].may_overlap_tile(pos))
+        .collect();
+
+    for column_nr in 0..TILE_COLUMNS {
+        let naturality = match get_column_naturality(&tile, column_nr, rules) {
+            Some(naturality) => naturality as usize,
+            None => continue,
+        };
+        let x = column_nr % TILE_WIDTH;
+        let z = column_nr / TILE_WIDTH;
+        let chunk_nr = x / CHUNK_WIDTH + z / CHUNK_HEIGHT * (TILE_WIDTH / CHUNK_WIDTH);
+        stats.chunks[chunk_nr][naturality] += 1;
+
+        let world_x = tile_x * TILE_WIDTH as i32 + x as i32;
+        let world_z = tile_z * TILE_HEIGHT as i32 + z as i32;
+        for area_nr in &nearby_areas {
+            if areas[*area_nr].contains_column(world_x, world_z) {
+                stats.areas[*area_nr][naturality] += 1;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+fn get_pos_rows(
+    counts: &BTreeMap<(i32, i32), NaturalityCounts>,
+) -> Vec<(Vec<Value>, NaturalityCounts)> {
+    counts
+        .iter()
+        .map(|((x, z), counts)| (vec![Value::from(*x), Value::from(*z)], *counts))
+        .collect()
+}
+
+fn get_naturality_name(naturality: &Naturality) -> String {
+    format!("{:?}", naturality).to_lowercase()
+}
+
+/// Writes `<name>.csv` or `<name>.json`, with the key columns followed by
+/// the count of each naturality, the total and the built fraction.
+fn write_stats(
+    output_path: &Path,
+    name: &str,
+    key_names: &[&str],
+    rows: &[(Vec<Value>, NaturalityCounts)],
+    json: bool,
+) -> Result<(), String> {
+    let path = output_path.join(format!("{}.{}", name, if json { "json" } else { "csv" }));
+    let text = if json {
+        let objects: Vec<Value> = rows
+            .iter()
+            .map(|(keys, counts)| {
+                let mut object = Map::new();
+                for (key_name, key) in key_names.iter().zip(keys.iter()) {
+                    object.insert(key_name.to_string(), key.clone());
+                }
+                for naturality in NATURALITY_VALUES.iter() {
+                    let count = counts[*naturality as usize];
+                    object.insert(get_naturality_name(naturality), Value::from(count));
+                }
+                object.insert(
+                    "total".to_string(),
+                    Value::from(counts.iter().sum::<usize>()),
+                );
+                object.insert(
+                    "built_fraction".to_string(),
+                    Value::from(get_built_fraction(counts)),
+                );
+                Value::Object(object)
+            })
+            .collect();
+        serde_json::to_string_pretty(&objects).expect("serializing statistics")
+    } else {
+        let mut header: Vec<String> = key_names.iter().map(|k| k.to_string()).collect();
+        header.extend(NATURALITY_VALUES.iter().map(get_naturality_name));
+        header.push("total".to_string());
+        header.push("built_fraction".to_string());
+        let mut lines = vec![header.join(",")];
+        for (keys, counts) in rows {
+            let mut fields: Vec<String> = keys
+                .iter()
+                .map(|key| match key {
+                    Value::String(s) => csv_quote(s),
+                    _ => key.to_string(),
+                })
+                .collect();
+            fields.extend(counts.iter().map(|count| count.to_string()));
+            fields.push(counts.iter().sum::<usize>().to_string());
+            fields.push(format!("{:.4}", get_built_fraction(counts)));
+            lines.push(fields.join(","));
+        }
+        lines.join("\n") + "\n"
+    };
+    fs::write(&path, text).map_err(|e| format!("{:?} {}", path, e))
+}
+
+/// `built.png` with one pixel per chunk, from the natural to the built color,
+/// transparent where nothing was counted. `built.txt` has the chunk at its top left corner.
+fn write_heatmap(
+    output_path: &Path,
+    chunk_counts: &BTreeMap<(i32, i32), NaturalityCounts>,
+) -> Result<(), String> {
+    if chunk_counts.is_empty() {
+        return Ok(());
+    }
+    let min_x = chunk_counts.keys().map(|(x, _)| *x).min().unwrap();
+    let max_x = chunk_counts.keys().map(|(x, _)| *x).max().unwrap();
+    let min_z = chunk_counts.keys().map(|(_, z)| *z).min().unwrap();
+    let max_z = chunk_counts.keys().map(|(_, z)| *z).max().unwrap();
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_z - min_z + 1) as usize;
+
+    let gradient = Gradient::new(vec![
+        (0, get_naturality_color(&Naturality::Natural)),
+        (100, get_naturality_color(&Naturality::Built)),
+    ])
+    .expect("gradient has stops");
+    let mut pixbuf = vec![0; width * height];
+    for ((x, z), counts) in chunk_counts.iter() {
+        let percent = (get_built_fraction(counts) * 100.0).round() as i32;
+        pixbuf[(z - min_z) as usize * width + (x - min_x) as usize] = gradient.color_at(percent);
+    }
+
+    let img_path = output_path.join("built.png");
+    lodepng::encode32_file(&img_path, &pixbuf[..], width, height)
+        .map_err(|e| format!("{:?} {}", img_path, e))?;
+    let origin_path = output_path.join("built.txt");
+    let origin = format!(
+        "top left chunk {},{} (block {},{}), one pixel per chunk\n",
+        min_x,
+        min_z,
+        min_x * CHUNK_WIDTH as i32,
+        min_z * CHUNK_HEIGHT as i32
+    );
+    fs::write(&origin_path, origin).map_err(|e| format!("{:?} {}", origin_path, e))
+}
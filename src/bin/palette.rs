@@ -1,32 +1,88 @@
+extern crate docopt;
 extern crate lodepng;
+extern crate serde;
 extern crate voxelmap_cache;
 
-use std::time;
-use voxelmap_cache::colorizer::*;
+use docopt::Docopt;
+use serde::Deserialize;
+use std::fs;
+use voxelmap_cache::colorizer::{parse_colorizer_options, ColorizerRegistry};
+use voxelmap_cache::legend::{draw_legend, legend_to_json};
+
+const USAGE: &str = "
+Usage: palette [--palette=<file>] [--option=<kv>...] <output-path> [<mode>...]
+
+Draws a labelled legend image `<mode>.png` and writes a legend file `<mode>.json`
+for each given render mode, or for all modes if none are given.
+
+output-path is a directory that will contain the legends
+
+Options:
+    --palette=<file>    JSON file with custom gradients, see palette.rs
+    -o, --option=<kv>   Colorizer option in the format key=value, can be repeated
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_palette: Option<String>,
+    flag_option: Vec<String>,
+    arg_output_path: String,
+    arg_mode: Vec<String>,
+}
 
 fn main() {
-    let unix_time = time::SystemTime::now()
-        .duration_since(time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let img_path = std::env::args()
-        .skip(1)
-        .next()
-        .unwrap_or("palette_{t}.png".to_string())
-        .replace("{t}", &unix_time.to_string());
-
-    let mut pixbuf = [0_u32; 100 * 256];
-    for h in 1..257_usize {
-        let color_land = get_land_color(h as u8);
-        for x in 0..50 {
-            pixbuf[x + 100 * (256 - h) as usize] = color_land;
-        }
-        let color_water = get_sea_color(h as u8);
-        for x in 50..100 {
-            pixbuf[x + 100 * (256 - h) as usize] = color_water;
-        }
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+
+    let mut options = parse_colorizer_options(&args.flag_option).unwrap_or_else(|e| {
+        eprintln!("Invalid arg: {}", e);
+        std::process::exit(1);
+    });
+    if let Some(palette_path) = &args.flag_palette {
+        options.insert("palette".to_string(), palette_path.clone());
     }
 
-    print!("Saving as {}\n", img_path);
-    lodepng::encode32_file(img_path, &pixbuf, 100, 256).unwrap();
+    let registry = ColorizerRegistry::with_builtins();
+    let modes: Vec<String> = if args.arg_mode.is_empty() {
+        registry.names().iter().map(|s| s.to_string()).collect()
+    } else {
+        args.arg_mode.clone()
+    };
+
+    fs::create_dir_all(&args.arg_output_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to create output directory {:?} {:?}",
+            &args.arg_output_path, e
+        );
+        std::process::exit(1);
+    });
+
+    for mode in modes {
+        let colorizer = match registry.create(&mode, &options) {
+            Ok(colorizer) => colorizer,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", mode, e);
+                continue;
+            }
+        };
+        let legend = match colorizer.legend() {
+            Some(legend) => legend,
+            None => {
+                eprintln!("No legend for {}", mode);
+                continue;
+            }
+        };
+
+        let json_path = format!("{}/{}.json", args.arg_output_path, mode);
+        fs::write(&json_path, legend_to_json(&legend))
+            .unwrap_or_else(|_| panic!("Writing legend {}", json_path));
+
+        let img_path = format!("{}/{}.png", args.arg_output_path, mode);
+        let (pixbuf, width, height) = draw_legend(&legend);
+        lodepng::encode32_file(&img_path, &pixbuf, width, height)
+            .unwrap_or_else(|_| panic!("Encoding legend {}", img_path));
+
+        println!("Saved {} and {}", img_path, json_path);
+    }
 }
@@ -0,0 +1,177 @@
+extern crate docopt;
+extern crate serde;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use voxelmap_cache::colorizer::get_column_naturality;
+use voxelmap_cache::naturality::NaturalityRules;
+use voxelmap_cache::tile::{
+    column_nr_of_pos, get_contrib_from_tile_path, get_tile_paths_in_dirs, get_xz_from_tile_path,
+    read_tile, Tile, LAYERS,
+};
+use voxelmap_cache::{get_mtime_or_0, TILE_HEIGHT, TILE_WIDTH};
+
+const USAGE: &str = "
+Usage: query [-v] [--naturality=<file>] <pos> <cache-path>...
+
+Prints everything known about the column at world coordinates `pos` (format: x,z),
+side by side for every tile containing it, most recently modified first.
+Use `query -- -123,45 cache/` if x is negative.
+
+cache-path contains voxelmap caches in the format
+`<x>,<z>,<contrib-name>.zip` or just `<x>,<z>.zip`
+
+Options:
+    -v, --verbose           Report ignored files in the cache directories.
+    --naturality=<file>     Naturality rules (TSV or JSON), as for `render -o naturality=<file>`
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_verbose: bool,
+    flag_naturality: Option<String>,
+    arg_pos: String,
+    arg_cache_path: Vec<String>,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+
+    let (x, z) = parse_pos(&args.arg_pos).unwrap_or_else(|e| {
+        eprintln!("Invalid pos {:?}: {}", &args.arg_pos, e);
+        std::process::exit(1);
+    });
+
+    let mut rules = NaturalityRules::new();
+    if let Some(path) = &args.flag_naturality {
+        rules.read(Path::new(path)).unwrap_or_else(|e| {
+            eprintln!("Error while reading naturality rules: {}", e);
+            std::process::exit(1);
+        });
+    }
+
+    let tile_pos = (
+        x.div_euclid(TILE_WIDTH as i32),
+        z.div_euclid(TILE_HEIGHT as i32),
+    );
+    let column_nr = column_nr_of_pos(
+        x.rem_euclid(TILE_WIDTH as i32) as usize,
+        z.rem_euclid(TILE_HEIGHT as i32) as usize,
+    );
+
+    let tile_paths = get_tile_paths_in_dirs(&args.arg_cache_path, args.flag_verbose)
+        .unwrap_or_else(|e| {
+            eprintln!("Error while listing cache directory: {:?}", e);
+            std::process::exit(1);
+        });
+
+    let mut tile_paths: Vec<PathBuf> = tile_paths
+        .into_iter()
+        .filter(|path| get_xz_from_tile_path(path) == Ok(tile_pos))
+        .collect();
+    // most recent first, like merge_caches prefers them
+    tile_paths.sort_by_key(|path| u64::MAX - get_mtime_or_0(path));
+
+    println!(
+        "Column {},{} is column {} in tile {},{}",
+        x, z, column_nr, tile_pos.0, tile_pos.1
+    );
+    if tile_paths.is_empty() {
+        println!("No tiles found");
+        return;
+    }
+
+    let mut table: Vec<Vec<String>> = vec![
+        vec!["source".to_string()],
+        vec!["modified".to_string()],
+        vec!["biome".to_string()],
+    ];
+    for layer in LAYERS.iter() {
+        table.push(vec![format!("{} height", layer.name())]);
+        table.push(vec![format!("{} block", layer.name())]);
+        table.push(vec![format!("{} light", layer.name())]);
+    }
+    table.push(vec!["naturality".to_string()]);
+
+    for tile_path in &tile_paths {
+        let source = get_contrib_from_tile_path(tile_path).unwrap_or_else(|_| {
+            tile_path
+                .parent()
+                .map(|dir| dir.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let values = match read_tile(tile_path) {
+            Ok(tile) => describe_column(&tile, column_nr, &rules),
+            Err(e) => vec![format!("unreadable: {}", e)],
+        };
+        table[0].push(source);
+        table[1].push(get_mtime_or_0(tile_path).to_string());
+        for (row_nr, row) in table.iter_mut().enumerate().skip(2) {
+            row.push(values.get(row_nr - 2).cloned().unwrap_or_default());
+        }
+    }
+
+    print_table(&table);
+}
+
+/// Values for the table rows after source and modified.
+fn describe_column(tile: &Tile, column_nr: usize, rules: &NaturalityRules) -> Vec<String> {
+    if tile.is_col_empty(column_nr) {
+        return vec!["empty".to_string()];
+    }
+    let mut values = vec![format!(
+        "{} ({})",
+        tile.get_biome_name(column_nr),
+        tile.get_biome_id(column_nr)
+    )];
+    for layer in LAYERS.iter() {
+        let block_nr = tile.get_layer_blockstate(*layer, column_nr);
+        let light = tile.get_layer_light(*layer, column_nr);
+        values.push(tile.get_layer_height(*layer, column_nr).to_string());
+        values.push(match block_nr {
+            0 => "none".to_string(),
+            _ => format!("{} ({})", tile.get_block_name(block_nr), block_nr),
+        });
+        values.push(format!("block {}, sky {}", light & 0xf, light >> 4));
+    }
+    values.push(match get_column_naturality(tile, column_nr, rules) {
+        Some(naturality) => format!("{:?}", naturality),
+        None => "none".to_string(),
+    });
+    values
+}
+
+fn print_table(table: &Vec<Vec<String>>) {
+    let num_columns = table.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..num_columns)
+        .map(|column| {
+            table
+                .iter()
+                .map(|row| row.get(column).map(|s| s.chars().count()).unwrap_or(0))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in table {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| format!("{:width$}", cell, width = widths[column]))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn parse_pos(pos_str: &str) -> Result<(i32, i32), String> {
+    let mut split = pos_str.splitn(2, ",");
+    let x = split.next().unwrap_or("").trim().parse::<i32>();
+    let z = split.next().unwrap_or("").trim().parse::<i32>();
+    match (x, z) {
+        (Ok(x), Ok(z)) => Ok((x, z)),
+        _ => Err("should be: x,z".to_string()),
+    }
+}
@@ -7,30 +7,36 @@ extern crate zip;
 
 use docopt::Docopt;
 use serde::Deserialize;
-use std::iter::FromIterator;
 use std::path::PathBuf;
 use std::sync::mpsc::channel;
 use std::sync::Arc;
 use std::{fs, time::Duration};
 use threadpool::ThreadPool;
-use voxelmap_cache::colorizer::Colorizer;
-use voxelmap_cache::mc::blocks::BLOCK_STRINGS_ARR;
-use voxelmap_cache::tile::{
-    get_tile_paths_in_dirs, get_xz_from_tile_path, is_tile_pos_in_bounds, read_tile, KeysMap,
-    NamesVec,
+use voxelmap_cache::colorizer::{parse_colorizer_options, ColorizerRegistry};
+use voxelmap_cache::render::{
+    get_render_order, render_tile, LayerSelector, RenderConfig, TileCache,
 };
-use voxelmap_cache::{parse_bounds, ProgressTracker, TILE_COLUMNS, TILE_HEIGHT, TILE_WIDTH};
+use voxelmap_cache::tile::{get_tile_paths_in_dirs, get_xz_from_tile_path, is_tile_pos_in_bounds};
+use voxelmap_cache::{parse_bounds, ProgressTracker, TILE_HEIGHT, TILE_WIDTH};
 
-const USAGE: &'static str = "
-Usage: render [-q] [-t threads] [--between=<bounds>] <cache-path> <output-path> (simple | light | biome | height | height-bw | naturality | terrain)
+const USAGE: &str = "
+Usage: render [-q] [-t threads] [--between=<bounds>] [--option=<kv>...] [--overlay=<mode>...] [--layer=<layer>] <cache-path> <output-path> <mode>
 
 cache-path contains voxelmap caches in the format `<x>,<z>.zip`
 
 output-path is a directory that will contain the rendered tiles
 
+mode is the name of a colorizer: simple, map, light, biome, height, height-bw, naturality,
+contour, slope, highlight, spawn, grid, waypoints
+
 Options:
     -q, --quiet         Do not output info messages.
     -t, --threads       Number of threads to use for parallel processing
+    -o, --option=<kv>   Colorizer option in the format key=value, can be repeated
+    --overlay=<mode>    Draw this mode over the main mode, can be repeated
+    --layer=<layer>     Colorize this layer instead of the top surface:
+                        surface, ocean-floor, transparent, foliage,
+                        or x-ray for the lowest non-empty layer [default: surface]
     --between=<bounds>  Only render tiles at least partially within this bounding box,
                         format: w,n,e,s [default: -99999,-99999,99999,99999]
 ";
@@ -40,46 +46,16 @@ Options:
 #[derive(Debug, Deserialize)]
 struct Args {
     flag_between: String,
+    flag_option: Vec<String>,
+    flag_overlay: Vec<String>,
+    flag_layer: String,
     arg_output_path: String,
     arg_cache_path: String,
+    arg_mode: String,
     flag_quiet: bool,
-    cmd_simple: bool,
-    cmd_light: bool,
-    cmd_biome: bool,
-    cmd_height: bool,
-    cmd_height_bw: bool,
-    cmd_naturality: bool,
-    cmd_terrain: bool,
     arg_threads: Option<usize>,
 }
 
-impl Args {
-    fn get_colorizer(&self) -> Colorizer {
-        if self.cmd_simple {
-            Colorizer::Simple
-        } else if self.cmd_light {
-            Colorizer::Light
-        } else if self.cmd_biome {
-            Colorizer::Biome
-        } else if self.cmd_height {
-            Colorizer::Height
-        } else if self.cmd_height_bw {
-            Colorizer::HeightBW
-        } else if self.cmd_naturality {
-            Colorizer::Naturality
-        } else {
-            panic!("Unknown colorizer selected")
-        }
-    }
-}
-
-#[derive(Debug)]
-struct RenderConfig {
-    colorizer: Colorizer,
-    global_keys: KeysMap,
-    global_names: NamesVec,
-}
-
 #[derive(Debug)]
 struct OutputConfig<'a> {
     output_path: &'a String,
@@ -102,10 +78,40 @@ fn main() {
         std::process::exit(1);
     });
 
-    let tile_paths: Vec<PathBuf> = tile_paths
-        .into_iter()
-        .filter(|path| is_tile_pos_in_bounds(get_xz_from_tile_path(path).unwrap(), &bounds))
+    let options = parse_colorizer_options(&args.flag_option).unwrap_or_else(|e| {
+        eprintln!("Invalid arg: {}", e);
+        std::process::exit(1);
+    });
+    let layer = LayerSelector::from_name(&args.flag_layer).unwrap_or_else(|| {
+        eprintln!("Invalid arg: --layer={}", &args.flag_layer);
+        std::process::exit(1);
+    });
+    let registry = ColorizerRegistry::with_builtins();
+    let colorizer = registry
+        .create(&args.arg_mode, &options)
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        });
+    let overlays: Vec<_> = args
+        .flag_overlay
+        .iter()
+        .map(|mode| {
+            registry.create(mode, &options).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            })
+        })
+        .collect();
+
+    let all_tile_paths: Vec<PathBuf> = tile_paths.into_iter().collect();
+    let mut tile_paths: Vec<PathBuf> = all_tile_paths
+        .iter()
+        .filter(|&path| is_tile_pos_in_bounds(get_xz_from_tile_path(path).unwrap(), &bounds))
+        .cloned()
         .collect();
+    // so the neighbors of each tile are read only about once
+    tile_paths.sort_by_key(|path| get_render_order(get_xz_from_tile_path(path).unwrap()));
 
     fs::create_dir_all(&args.arg_output_path).unwrap_or_else(|e| {
         eprintln!(
@@ -124,13 +130,19 @@ fn main() {
         )
     }
 
-    let render_config = Arc::new(RenderConfig {
-        colorizer: args.get_colorizer(),
-        global_keys: build_global_keys_map(),
-        global_names: BLOCK_STRINGS_ARR.iter().map(|x| x.to_string()).collect(),
-    });
+    let mut render_config = RenderConfig::new(colorizer, options);
+    render_config.overlays = overlays;
+    render_config.layer = layer;
+    let num_threads = args.arg_threads.unwrap_or(4);
+    render_config.tile_cache = TileCache::for_threads(num_threads);
+    for tile_path in &all_tile_paths {
+        if let Ok(pos) = get_xz_from_tile_path(tile_path) {
+            render_config.tile_paths.insert(pos, tile_path.clone());
+        }
+    }
+    let render_config = Arc::new(render_config);
 
-    let pool = ThreadPool::new(args.arg_threads.unwrap_or(4));
+    let pool = ThreadPool::new(num_threads);
     let (tx, rx) = channel();
 
     for tile_path in tile_paths.into_iter() {
@@ -172,13 +184,18 @@ fn main() {
             "Done rendering. Took {}:{:02} for all {} tiles, {}ms per tile",
             total_min, total_sec, total_work, tile_ms,
         );
+        let colorizers = Some(&render_config.colorizer)
+            .into_iter()
+            .chain(render_config.overlays.iter());
+        for colorizer in colorizers {
+            for line in colorizer.report() {
+                eprintln!("{}", line);
+            }
+        }
     };
 }
 
-fn process_result(
-    result_with_path: (PathBuf, Result<Vec<u32>, String>),
-    config: &OutputConfig,
-) -> () {
+fn process_result(result_with_path: (PathBuf, Result<Vec<u32>, String>), config: &OutputConfig) {
     let (tile_path, result) = result_with_path;
     if let Err(msg) = result {
         eprintln!("Failed rendering tile {:?} {}", tile_path, msg);
@@ -188,34 +205,10 @@ fn process_result(
     let (x, z) = get_xz_from_tile_path(&tile_path).expect("Getting tile position");
     let img_path = format!("{}/{:?},{:?}.png", config.output_path, x, z);
 
-    fs::create_dir_all(config.output_path).expect(&format!(
-        "Creating containing directory for tile {}",
-        img_path
-    ));
+    fs::create_dir_all(config.output_path)
+        .unwrap_or_else(|_| panic!("Creating containing directory for tile {}", img_path));
 
     let pixbuf = result.expect("error already handled");
     lodepng::encode32_file(&img_path, &pixbuf[..], TILE_WIDTH, TILE_HEIGHT)
-        .expect(&format!("Encoding tile {}", img_path));
-}
-
-fn render_tile(tile_path: &PathBuf, config: &RenderConfig) -> Result<Vec<u32>, String> {
-    let tile = read_tile(tile_path).map_err(|e| e.to_string())?;
-    let mut pixbuf = vec![0_u32; TILE_COLUMNS];
-
-    let get_column_color = config.colorizer.get_column_color_fn();
-    for i in 0..TILE_COLUMNS {
-        pixbuf[i] = get_column_color(&tile, i);
-    }
-
-    Ok(pixbuf)
-}
-
-fn build_global_keys_map() -> KeysMap {
-    KeysMap::from_iter(
-        BLOCK_STRINGS_ARR
-            .iter()
-            .enumerate()
-            .rev()
-            .map(|(i, s)| (s.to_string(), i as u16)),
-    )
+        .unwrap_or_else(|_| panic!("Encoding tile {}", img_path));
 }
@@ -11,7 +11,7 @@ use voxelmap_cache::buf_rw::UUID;
 use voxelmap_cache::mc::packet::{ChunkData, McPacket};
 use voxelmap_cache::replay::{read_info, read_replay};
 
-const USAGE: &'static str = "
+const USAGE: &str = "
 Usage: replay [-q] [--filter=<ids>] [--follow=<uuid>] [--server=<address>] <path>
 
 path points to a (date).mcpr file like found in .minecraft/replay_recordings/
@@ -97,15 +97,16 @@ fn main() {
     let verbose = !args.flag_quiet;
     let filter_ids = args.get_id_filter().expect("Malformed filter argument");
 
-    let mut followed = args.flag_follow.as_ref().map(|ref uuid_str| {
-        match UUID::from_str(uuid_str) {
+    let mut followed = args
+        .flag_follow
+        .as_ref()
+        .map(|uuid_str| match UUID::from_str(uuid_str) {
             Ok(uuid) => Player::new(uuid),
             Err(e) => {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
-        }
-    });
+        });
 
     if let Some(ref address) = args.flag_server {
         let replay_info = read_info(&args.arg_path).expect("Reading replay info");
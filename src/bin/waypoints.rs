@@ -0,0 +1,118 @@
+extern crate docopt;
+extern crate serde;
+extern crate voxelmap_cache;
+
+use docopt::Docopt;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use voxelmap_cache::parse_bounds;
+use voxelmap_cache::waypoint::{
+    dedup_waypoints, get_waypoint_paths, read_waypoints, waypoints_to_geojson, waypoints_to_json,
+};
+
+const USAGE: &str = "
+Usage: waypoints [-q] [--between=<bounds>] [--dimension=<dim>] [--merge-distance=<blocks>] [--include-disabled] [--geojson] <output-path> <points-path>...
+
+Collects the waypoints of all contributors into one JSON file,
+combining the waypoints that several contributors have.
+
+points-path is a waypoints file, or a contributor directory that is searched for them:
+VoxelMap `.points` files, Xaero `dim%*/*.txt` files, or JourneyMap `waypoints/*.json` files.
+The waypoints are tagged with the name of the file or directory as their source.
+
+Options:
+    -q, --quiet                 Do not output info messages.
+    --between=<bounds>          Only include waypoints within this bounding box,
+                                format: w,n,e,s [default: -99999,-99999,99999,99999]
+    --dimension=<dim>           Only include waypoints in this dimension,
+                                overworld, the_nether, the_end, ... [default: overworld]
+    --merge-distance=<blocks>   Combine waypoints of the same name
+                                up to this many blocks apart [default: 8]
+    --include-disabled          Also include waypoints that are hidden in VoxelMap.
+    --geojson                   Write a GeoJSON FeatureCollection for CivMap
+                                instead of a plain list.
+";
+
+#[derive(Debug, Deserialize)]
+struct Args {
+    flag_between: String,
+    flag_dimension: String,
+    flag_merge_distance: i32,
+    flag_include_disabled: bool,
+    flag_geojson: bool,
+    flag_quiet: bool,
+    arg_output_path: String,
+    arg_points_path: Vec<String>,
+}
+
+fn main() {
+    let args: Args = Docopt::new(USAGE)
+        .and_then(|d| d.deserialize())
+        .unwrap_or_else(|e| e.exit());
+    let verbose = !args.flag_quiet;
+
+    let bounds = parse_bounds(&args.flag_between).unwrap_or_else(|e| {
+        eprintln!("Invalid arg: --between={} {}", &args.flag_between, e);
+        std::process::exit(1);
+    });
+
+    let mut waypoints = Vec::new();
+    for contrib_path in &args.arg_points_path {
+        let contrib_path = Path::new(contrib_path);
+        let contrib = contrib_path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let points_paths = get_waypoint_paths(contrib_path).unwrap_or_else(|e| {
+            eprintln!("Error while listing {:?}: {}", contrib_path, e);
+            std::process::exit(1);
+        });
+        for points_path in points_paths {
+            match read_waypoints(&points_path) {
+                Ok(file_waypoints) => {
+                    if verbose {
+                        eprintln!(
+                            "Read {} waypoints from {:?}",
+                            file_waypoints.len(),
+                            points_path
+                        );
+                    }
+                    waypoints.extend(file_waypoints.into_iter().map(|mut waypoint| {
+                        waypoint.sources = vec![contrib.clone()];
+                        waypoint
+                    }));
+                }
+                Err(e) => eprintln!("Failed reading waypoints {:?} {}", points_path, e),
+            }
+        }
+    }
+
+    let total_read = waypoints.len();
+    let waypoints: Vec<_> = waypoints
+        .into_iter()
+        .filter(|waypoint| args.flag_include_disabled || waypoint.enabled)
+        .filter(|waypoint| waypoint.is_in_dimension(&args.flag_dimension))
+        .filter(|waypoint| waypoint.is_in_bounds(&bounds))
+        .collect();
+    let waypoints = dedup_waypoints(waypoints, args.flag_merge_distance);
+
+    let json = if args.flag_geojson {
+        waypoints_to_geojson(&waypoints)
+    } else {
+        waypoints_to_json(&waypoints)
+    };
+    fs::write(&args.arg_output_path, json).unwrap_or_else(|e| {
+        eprintln!("Failed writing {:?} {:?}", &args.arg_output_path, e);
+        std::process::exit(1);
+    });
+
+    if verbose {
+        eprintln!(
+            "Wrote {} of {} waypoints to {:?}",
+            waypoints.len(),
+            total_read,
+            &args.arg_output_path
+        );
+    }
+}
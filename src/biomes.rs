@@ -294,9 +294,9 @@ pub const BIOME_COLOR_TABLE: [u32; 256] = [
 
 
 
-const unknown_biome_name: &'static str = "?UNKNOWN_BIOME?";
+pub const UNKNOWN_BIOME_NAME: &str = "?UNKNOWN_BIOME?";
 
-pub const BIOME_NAMES: [&'static str; 256] = [
+pub const BIOME_NAMES: [&str; 256] = [
 "Ocean", // 0
 "Plains", // 1
 "Desert", // 2
@@ -337,94 +337,94 @@ pub const BIOME_NAMES: [&'static str; 256] = [
 "Mesa", // 37
 "Mesa Plateau F", // 38
 "Mesa Plateau", // 39
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
 "Plains M", // 128
 "Sunflower Plains", // 129
 "Desert M", // 130
@@ -432,31 +432,31 @@ unknown_biome_name,
 "Flower Forest", // 132
 "Taiga M", // 133
 "Swampland M", // 134
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
 "Ice Plains Spikes", // 140
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
 "Jungle M", // 149
-unknown_biome_name,
+UNKNOWN_BIOME_NAME,
 "JungleEdge M", // 151
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
 "Birch Forest M", // 155
 "Birch Forest Hills M", // 156
 "Roofed Forest M", // 157
 "Cold Taiga M", // 158
-unknown_biome_name,
+UNKNOWN_BIOME_NAME,
 "Mega Spruce Taiga", // 160
 "Redwood Taiga Hills M", // 161
 "Extreme Hills+ M", // 162
@@ -465,92 +465,107 @@ unknown_biome_name,
 "Mesa (Bryce)", // 165
 "Mesa Plateau F M", // 166
 "Mesa Plateau M", // 167
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
-unknown_biome_name,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
+UNKNOWN_BIOME_NAME,
 ];
+
+/// Finds the id of a biome by its registry name such as `minecraft:frozen_ocean`,
+/// ignoring case, spaces and underscores. Biomes renamed since 1.12 are not found.
+pub fn get_biome_id_by_name(name: &str) -> Option<u8> {
+    let normalize = |name: &str| {
+        name.trim_start_matches("minecraft:")
+            .replace([' ', '_'], "")
+            .to_lowercase()
+    };
+    let name = normalize(name);
+    BIOME_NAMES
+        .iter()
+        .position(|biome_name| *biome_name != UNKNOWN_BIOME_NAME && normalize(biome_name) == name)
+        .map(|biome_id| biome_id as u8)
+}
@@ -1,10 +1,10 @@
 extern crate rustc_serialize;
 
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{BigEndian, ReadBytesExt};
 use std::fmt;
-use std::io::{Cursor, Read, Write};
+use std::io::{Cursor, Read};
 
-error_chain!{
+error_chain! {
     types {
         BufErr, BufErrorKind, BufResult;
     }
@@ -19,6 +19,7 @@ error_chain!{
 pub struct UUID(u64, u64);
 
 impl UUID {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<UUID, BufErr> {
         use self::rustc_serialize::hex::FromHex;
         let s = match s.len() {
@@ -56,6 +57,7 @@ impl BufReader {
         }
     }
 
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.len
     }
@@ -11,6 +11,15 @@ pub enum Naturality {
     Unknown,
 }
 
+pub const NATURALITY_VALUES: [Naturality; 6] = [
+    Naturality::Natural,
+    Naturality::Pond,
+    Naturality::Exploited,
+    Naturality::Planted,
+    Naturality::Built,
+    Naturality::Unknown,
+];
+
 pub fn get_naturality_color(n: &Naturality) -> u32 {
     match n {
         Naturality::Natural     => 0xff448844, // dark green muted
@@ -24,92 +33,163 @@ pub fn get_naturality_color(n: &Naturality) -> u32 {
 
 // CCNATURAL_COLORS_BLOCKS_BIOME must be checked before CCNATURAL_COLORS_BLOCK_DEFAULT
 // the split is just to make this file shorter
+// block names are modern (1.13+), legacy names are flattened when tiles are read
 
 lazy_static! {
     pub static ref CCNATURAL_COLORS_BLOCK_DEFAULT: HashMap<&'static str, Naturality> = HashMap::from_iter([
 ("acacia_door", Naturality::Built),
 ("acacia_fence", Naturality::Built),
 ("acacia_fence_gate", Naturality::Built),
+("acacia_leaves", Naturality::Planted),
+("acacia_log", Naturality::Unknown),
+("acacia_planks", Naturality::Built),
+("acacia_sapling", Naturality::Built),
+("acacia_slab", Naturality::Built),
 ("acacia_stairs", Naturality::Built),
+("acacia_wood", Naturality::Unknown),
 ("activator_rail", Naturality::Built),
+("allium", Naturality::Unknown),
+("andesite", Naturality::Exploited),
 ("anvil", Naturality::Built),
+("azure_bluet", Naturality::Unknown),
 ("beacon", Naturality::Built),
-("bed", Naturality::Built),
 ("bedrock", Naturality::Exploited),
 ("beetroots", Naturality::Planted),
 ("birch_door", Naturality::Built),
 ("birch_fence", Naturality::Built),
 ("birch_fence_gate", Naturality::Built),
+("birch_leaves", Naturality::Natural),
+("birch_log", Naturality::Unknown),
+("birch_planks", Naturality::Built),
+("birch_sapling", Naturality::Built),
+("birch_slab", Naturality::Built),
 ("birch_stairs", Naturality::Built),
+("birch_wood", Naturality::Unknown),
+("black_carpet", Naturality::Built),
+("black_concrete", Naturality::Built),
+("black_concrete_powder", Naturality::Built),
 ("black_glazed_terracotta", Naturality::Built),
+("black_stained_glass", Naturality::Built),
+("black_stained_glass_pane", Naturality::Built),
+("black_terracotta", Naturality::Built),
+("black_wool", Naturality::Built),
+("blue_carpet", Naturality::Built),
+("blue_concrete", Naturality::Built),
+("blue_concrete_powder", Naturality::Built),
 ("blue_glazed_terracotta", Naturality::Built),
+("blue_orchid", Naturality::Unknown),
+("blue_stained_glass", Naturality::Built),
+("blue_stained_glass_pane", Naturality::Built),
+("blue_terracotta", Naturality::Built),
+("blue_wool", Naturality::Built),
 ("bone_block", Naturality::Built),
 ("bookshelf", Naturality::Built),
 ("brewing_stand", Naturality::Built),
-("brick_block", Naturality::Built),
+("brick_slab", Naturality::Built),
 ("brick_stairs", Naturality::Built),
+("bricks", Naturality::Built),
+("brown_carpet", Naturality::Built),
+("brown_concrete", Naturality::Built),
+("brown_concrete_powder", Naturality::Built),
 ("brown_glazed_terracotta", Naturality::Built),
 ("brown_mushroom", Naturality::Unknown),
 ("brown_mushroom_block", Naturality::Built),
+("brown_stained_glass", Naturality::Built),
+("brown_stained_glass_pane", Naturality::Built),
+("brown_terracotta", Naturality::Built),
+("brown_wool", Naturality::Built),
 ("cactus", Naturality::Planted),
 ("cake", Naturality::Built),
-("carpet", Naturality::Built),
 ("carrots", Naturality::Planted),
+("carved_pumpkin", Naturality::Planted),
 ("cauldron", Naturality::Built),
 ("chest", Naturality::Built),
+("chipped_anvil", Naturality::Built),
+("chiseled_quartz_block", Naturality::Built),
+("chiseled_red_sandstone", Naturality::Built),
+("chiseled_sandstone", Naturality::Built),
+("chiseled_stone_bricks", Naturality::Built),
 ("chorus_flower", Naturality::Planted),
 ("chorus_plant", Naturality::Planted),
 ("clay", Naturality::Unknown),
 ("coal_block", Naturality::Built),
 ("coal_ore", Naturality::Exploited),
+("coarse_dirt", Naturality::Exploited),
 ("cobblestone", Naturality::Built),
+("cobblestone_slab", Naturality::Built),
+("cobblestone_stairs", Naturality::Built),
 ("cobblestone_wall", Naturality::Built),
+("cobweb", Naturality::Built),
 ("cocoa", Naturality::Planted),
-("concrete", Naturality::Built),
-("concrete_powder", Naturality::Built),
+("comparator", Naturality::Built),
+("cracked_stone_bricks", Naturality::Built),
 ("crafting_table", Naturality::Built),
+("cut_red_sandstone", Naturality::Built),
+("cut_sandstone", Naturality::Built),
+("cyan_carpet", Naturality::Built),
+("cyan_concrete", Naturality::Built),
+("cyan_concrete_powder", Naturality::Built),
+("cyan_stained_glass", Naturality::Built),
+("cyan_stained_glass_pane", Naturality::Built),
+("cyan_terracotta", Naturality::Built),
+("cyan_wool", Naturality::Built),
+("damaged_anvil", Naturality::Built),
+("dandelion", Naturality::Built),
 ("dark_oak_door", Naturality::Built),
 ("dark_oak_fence", Naturality::Built),
 ("dark_oak_fence_gate", Naturality::Built),
+("dark_oak_leaves", Naturality::Planted),
+("dark_oak_log", Naturality::Unknown),
+("dark_oak_planks", Naturality::Built),
+("dark_oak_sapling", Naturality::Built),
+("dark_oak_slab", Naturality::Built),
 ("dark_oak_stairs", Naturality::Built),
+("dark_oak_wood", Naturality::Unknown),
+("dark_prismarine", Naturality::Unknown),
 ("daylight_detector", Naturality::Built),
-("daylight_detector_inverted", Naturality::Built),
-("deadbush", Naturality::Unknown),
+("dead_bush", Naturality::Unknown),
 ("detector_rail", Naturality::Built),
 ("diamond_block", Naturality::Built),
 ("diamond_ore", Naturality::Exploited),
+("diorite", Naturality::Exploited),
 ("dirt", Naturality::Exploited),
+("dirt_path", Naturality::Built),
 ("dispenser", Naturality::Built),
-("double_plant", Naturality::Unknown),
-("double_stone_slab", Naturality::Built),
-("double_stone_slab2", Naturality::Built),
-("double_wooden_slab", Naturality::Built),
 ("dropper", Naturality::Built),
 ("emerald_block", Naturality::Built),
 ("enchanting_table", Naturality::Built),
-("end_bricks", Naturality::Built),
 ("end_rod", Naturality::Built),
 ("end_stone", Naturality::Built),
+("end_stone_bricks", Naturality::Built),
 ("farmland", Naturality::Planted),
-("fence", Naturality::Built),
-("fence_gate", Naturality::Built),
+("fern", Naturality::Natural),
 ("fire", Naturality::Unknown),
 ("flower_pot", Naturality::Built),
-("flowing_lava", Naturality::Unknown),
-("flowing_water", Naturality::Unknown),
 ("furnace", Naturality::Built),
 ("glass", Naturality::Built),
 ("glass_pane", Naturality::Built),
 ("glowstone", Naturality::Built),
 ("gold_block", Naturality::Built),
 ("gold_ore", Naturality::Exploited),
-("golden_rail", Naturality::Built),
-("grass", Naturality::Natural),
-("grass_path", Naturality::Built),
+("granite", Naturality::Exploited),
+("grass_block", Naturality::Natural),
 ("gravel", Naturality::Exploited),
+("gray_carpet", Naturality::Built),
+("gray_concrete", Naturality::Built),
+("gray_concrete_powder", Naturality::Built),
 ("gray_glazed_terracotta", Naturality::Built),
+("gray_stained_glass", Naturality::Built),
+("gray_stained_glass_pane", Naturality::Built),
+("gray_terracotta", Naturality::Built),
+("gray_wool", Naturality::Built),
+("green_carpet", Naturality::Built),
+("green_concrete", Naturality::Built),
+("green_concrete_powder", Naturality::Built),
 ("green_glazed_terracotta", Naturality::Built),
-("hardened_clay", Naturality::Built),
+("green_stained_glass", Naturality::Built),
+("green_stained_glass_pane", Naturality::Built),
+("green_terracotta", Naturality::Built),
+("green_wool", Naturality::Built),
 ("hay_block", Naturality::Built),
 ("heavy_weighted_pressure_plate", Naturality::Built),
 ("hopper", Naturality::Built),
@@ -119,133 +199,390 @@ lazy_static! {
 ("iron_door", Naturality::Built),
 ("iron_ore", Naturality::Exploited),
 ("iron_trapdoor", Naturality::Built),
+("jack_o_lantern", Naturality::Built),
 ("jukebox", Naturality::Built),
 ("jungle_door", Naturality::Built),
 ("jungle_fence", Naturality::Built),
 ("jungle_fence_gate", Naturality::Built),
+("jungle_leaves", Naturality::Natural),
+("jungle_log", Naturality::Unknown),
+("jungle_planks", Naturality::Built),
+("jungle_sapling", Naturality::Built),
+("jungle_slab", Naturality::Built),
 ("jungle_stairs", Naturality::Built),
+("jungle_wood", Naturality::Unknown),
 ("ladder", Naturality::Built),
 ("lapis_block", Naturality::Built),
 ("lapis_ore", Naturality::Exploited),
+("large_fern", Naturality::Unknown),
 ("lava", Naturality::Unknown),
-("leaves", Naturality::Natural),
-("leaves2", Naturality::Planted),
 ("lever", Naturality::Built),
+("light_blue_carpet", Naturality::Built),
+("light_blue_concrete", Naturality::Built),
+("light_blue_concrete_powder", Naturality::Built),
 ("light_blue_glazed_terracotta", Naturality::Built),
+("light_blue_stained_glass", Naturality::Built),
+("light_blue_stained_glass_pane", Naturality::Built),
+("light_blue_terracotta", Naturality::Built),
+("light_blue_wool", Naturality::Built),
+("light_gray_carpet", Naturality::Built),
+("light_gray_concrete", Naturality::Built),
+("light_gray_concrete_powder", Naturality::Built),
+("light_gray_glazed_terracotta", Naturality::Built),
+("light_gray_stained_glass", Naturality::Built),
+("light_gray_stained_glass_pane", Naturality::Built),
+("light_gray_terracotta", Naturality::Built),
+("light_gray_wool", Naturality::Built),
 ("light_weighted_pressure_plate", Naturality::Built),
+("lilac", Naturality::Unknown),
+("lily_pad", Naturality::Built),
+("lime_carpet", Naturality::Built),
+("lime_concrete", Naturality::Built),
+("lime_concrete_powder", Naturality::Built),
 ("lime_glazed_terracotta", Naturality::Built),
-("lit_pumpkin", Naturality::Built),
-("lit_redstone_lamp", Naturality::Built),
-("lit_redstone_ore", Naturality::Exploited),
-("log", Naturality::Unknown),
-("log2", Naturality::Unknown),
-("magma", Naturality::Built),
-("melon_block", Naturality::Planted),
+("lime_stained_glass", Naturality::Built),
+("lime_stained_glass_pane", Naturality::Built),
+("lime_terracotta", Naturality::Built),
+("lime_wool", Naturality::Built),
+("magenta_carpet", Naturality::Built),
+("magenta_concrete", Naturality::Built),
+("magenta_concrete_powder", Naturality::Built),
+("magenta_stained_glass", Naturality::Built),
+("magenta_stained_glass_pane", Naturality::Built),
+("magenta_terracotta", Naturality::Built),
+("magenta_wool", Naturality::Built),
+("magma_block", Naturality::Built),
+("melon", Naturality::Planted),
 ("melon_stem", Naturality::Planted),
-("mob_spawner", Naturality::Unknown),
 ("mossy_cobblestone", Naturality::Exploited),
+("mossy_cobblestone_wall", Naturality::Built),
+("mossy_stone_bricks", Naturality::Built),
+("mushroom_stem", Naturality::Built),
 ("mycelium", Naturality::Built),
-("nether_brick", Naturality::Built),
 ("nether_brick_fence", Naturality::Built),
+("nether_brick_slab", Naturality::Built),
 ("nether_brick_stairs", Naturality::Built),
+("nether_bricks", Naturality::Built),
+("nether_quartz_ore", Naturality::Built),
 ("nether_wart", Naturality::Planted),
 ("nether_wart_block", Naturality::Built),
 ("netherrack", Naturality::Built),
-("noteblock", Naturality::Built),
+("note_block", Naturality::Built),
+("oak_button", Naturality::Built),
+("oak_door", Naturality::Built),
+("oak_fence", Naturality::Built),
+("oak_fence_gate", Naturality::Built),
+("oak_leaves", Naturality::Natural),
+("oak_log", Naturality::Unknown),
+("oak_planks", Naturality::Built),
+("oak_pressure_plate", Naturality::Built),
+("oak_sapling", Naturality::Built),
+("oak_sign", Naturality::Built),
+("oak_slab", Naturality::Built),
 ("oak_stairs", Naturality::Built),
+("oak_trapdoor", Naturality::Built),
+("oak_wall_sign", Naturality::Built),
+("oak_wood", Naturality::Unknown),
 ("observer", Naturality::Built),
 ("obsidian", Naturality::Built),
+("orange_carpet", Naturality::Built),
+("orange_concrete", Naturality::Built),
+("orange_concrete_powder", Naturality::Built),
 ("orange_glazed_terracotta", Naturality::Built),
+("orange_stained_glass", Naturality::Built),
+("orange_stained_glass_pane", Naturality::Built),
+("orange_terracotta", Naturality::Built),
+("orange_tulip", Naturality::Unknown),
+("orange_wool", Naturality::Built),
+("oxeye_daisy", Naturality::Unknown),
 ("packed_ice", Naturality::Built),
+("peony", Naturality::Unknown),
+("petrified_oak_slab", Naturality::Built),
+("pink_carpet", Naturality::Built),
+("pink_concrete", Naturality::Built),
+("pink_concrete_powder", Naturality::Built),
 ("pink_glazed_terracotta", Naturality::Built),
+("pink_stained_glass", Naturality::Built),
+("pink_stained_glass_pane", Naturality::Built),
+("pink_terracotta", Naturality::Built),
+("pink_tulip", Naturality::Unknown),
+("pink_wool", Naturality::Built),
 ("piston", Naturality::Built),
 ("piston_head", Naturality::Built),
-("planks", Naturality::Built),
+("podzol", Naturality::Exploited),
+("polished_andesite", Naturality::Exploited),
+("polished_diorite", Naturality::Exploited),
+("polished_granite", Naturality::Exploited),
+("poppy", Naturality::Unknown),
 ("potatoes", Naturality::Planted),
-("powered_repeater", Naturality::Built),
+("powered_rail", Naturality::Built),
 ("prismarine", Naturality::Unknown),
-("pumpkin", Naturality::Planted),
+("prismarine_bricks", Naturality::Unknown),
 ("pumpkin_stem", Naturality::Planted),
+("purple_carpet", Naturality::Built),
+("purple_concrete", Naturality::Built),
+("purple_concrete_powder", Naturality::Built),
 ("purple_glazed_terracotta", Naturality::Built),
+("purple_stained_glass", Naturality::Built),
+("purple_stained_glass_pane", Naturality::Built),
+("purple_terracotta", Naturality::Built),
+("purple_wool", Naturality::Built),
 ("purpur_block", Naturality::Built),
-("purpur_double_slab", Naturality::Built),
 ("purpur_pillar", Naturality::Built),
 ("purpur_slab", Naturality::Built),
 ("purpur_stairs", Naturality::Built),
 ("quartz_block", Naturality::Built),
-("quartz_ore", Naturality::Built),
+("quartz_pillar", Naturality::Built),
+("quartz_slab", Naturality::Built),
 ("quartz_stairs", Naturality::Built),
 ("rail", Naturality::Built),
-("red_flower", Naturality::Unknown),
+("red_bed", Naturality::Built),
+("red_carpet", Naturality::Built),
+("red_concrete", Naturality::Built),
+("red_concrete_powder", Naturality::Built),
 ("red_glazed_terracotta", Naturality::Built),
 ("red_mushroom", Naturality::Built),
 ("red_mushroom_block", Naturality::Built),
-("red_nether_brick", Naturality::Built),
+("red_nether_bricks", Naturality::Built),
+("red_sand", Naturality::Unknown),
 ("red_sandstone", Naturality::Built),
+("red_sandstone_slab", Naturality::Built),
 ("red_sandstone_stairs", Naturality::Built),
+("red_stained_glass", Naturality::Built),
+("red_stained_glass_pane", Naturality::Built),
+("red_terracotta", Naturality::Built),
+("red_tulip", Naturality::Unknown),
+("red_wool", Naturality::Built),
 ("redstone_block", Naturality::Built),
 ("redstone_lamp", Naturality::Built),
 ("redstone_ore", Naturality::Exploited),
 ("redstone_torch", Naturality::Built),
+("redstone_wall_torch", Naturality::Built),
 ("redstone_wire", Naturality::Built),
-("reeds", Naturality::Planted),
+("repeater", Naturality::Built),
+("rose_bush", Naturality::Unknown),
 ("sand", Naturality::Unknown),
 ("sandstone", Naturality::Built),
+("sandstone_slab", Naturality::Built),
 ("sandstone_stairs", Naturality::Built),
-("sapling", Naturality::Built),
 ("sea_lantern", Naturality::Built),
-("silver_glazed_terracotta", Naturality::Built),
-("skull", Naturality::Built),
-("slime", Naturality::Built),
+("short_grass", Naturality::Natural),
+("skeleton_skull", Naturality::Built),
+("skeleton_wall_skull", Naturality::Built),
+("slime_block", Naturality::Built),
+("smooth_quartz", Naturality::Built),
+("smooth_red_sandstone", Naturality::Built),
+("smooth_sandstone", Naturality::Built),
+("smooth_stone", Naturality::Built),
+("smooth_stone_slab", Naturality::Built),
 ("snow", Naturality::Built),
-("snow_layer", Naturality::Built),
+("snow_block", Naturality::Built),
 ("soul_sand", Naturality::Built),
+("spawner", Naturality::Unknown),
 ("spruce_door", Naturality::Built),
 ("spruce_fence", Naturality::Built),
 ("spruce_fence_gate", Naturality::Built),
+("spruce_leaves", Naturality::Natural),
+("spruce_log", Naturality::Unknown),
+("spruce_planks", Naturality::Built),
+("spruce_sapling", Naturality::Built),
+("spruce_slab", Naturality::Built),
 ("spruce_stairs", Naturality::Built),
-("stained_glass", Naturality::Built),
-("stained_glass_pane", Naturality::Built),
-("stained_hardened_clay", Naturality::Built),
-("standing_banner", Naturality::Built),
-("standing_sign", Naturality::Built),
+("spruce_wood", Naturality::Unknown),
 ("sticky_piston", Naturality::Built),
 ("stone", Naturality::Exploited),
+("stone_brick_slab", Naturality::Built),
 ("stone_brick_stairs", Naturality::Built),
+("stone_bricks", Naturality::Built),
 ("stone_button", Naturality::Built),
 ("stone_pressure_plate", Naturality::Built),
-("stone_slab", Naturality::Built),
-("stone_slab2", Naturality::Built),
-("stone_stairs", Naturality::Built),
-("stonebrick", Naturality::Built),
-("tallgrass", Naturality::Natural),
+("sugar_cane", Naturality::Planted),
+("sunflower", Naturality::Unknown),
+("tall_grass", Naturality::Unknown),
+("terracotta", Naturality::Built),
 ("tnt", Naturality::Built),
 ("torch", Naturality::Built),
-("trapdoor", Naturality::Built),
 ("trapped_chest", Naturality::Built),
 ("tripwire", Naturality::Built),
 ("tripwire_hook", Naturality::Built),
-("unlit_redstone_torch", Naturality::Built),
-("unpowered_comparator", Naturality::Built),
-("unpowered_repeater", Naturality::Built),
 ("vine", Naturality::Unknown),
-("wall_banner", Naturality::Built),
-("wall_sign", Naturality::Built),
+("wall_torch", Naturality::Built),
 ("water", Naturality::Pond),
-("waterlily", Naturality::Built),
-("web", Naturality::Built),
 ("wheat", Naturality::Planted),
+("white_banner", Naturality::Built),
+("white_carpet", Naturality::Built),
+("white_concrete", Naturality::Built),
+("white_concrete_powder", Naturality::Built),
 ("white_glazed_terracotta", Naturality::Built),
-("wooden_button", Naturality::Built),
-("wooden_door", Naturality::Built),
-("wooden_pressure_plate", Naturality::Built),
-("wooden_slab", Naturality::Built),
-("wool", Naturality::Built),
-("yellow_flower", Naturality::Built),
+("white_stained_glass", Naturality::Built),
+("white_stained_glass_pane", Naturality::Built),
+("white_terracotta", Naturality::Built),
+("white_tulip", Naturality::Unknown),
+("white_wall_banner", Naturality::Built),
+("white_wool", Naturality::Built),
+("yellow_carpet", Naturality::Built),
+("yellow_concrete", Naturality::Built),
+("yellow_concrete_powder", Naturality::Built),
 ("yellow_glazed_terracotta", Naturality::Built),
+("yellow_stained_glass", Naturality::Built),
+("yellow_stained_glass_pane", Naturality::Built),
+("yellow_terracotta", Naturality::Built),
+("yellow_wool", Naturality::Built),
     ].iter().map(|(n,c)| (*n, *c)));
 
     pub static ref CCNATURAL_COLORS_BLOCK_BIOME: HashMap<(&'static str, u8), Naturality> = HashMap::from_iter([
+(("acacia_leaves", 0), Naturality::Built),
+(("acacia_leaves", 12), Naturality::Natural),
+(("acacia_leaves", 13), Naturality::Unknown),
+(("acacia_leaves", 130), Naturality::Unknown),
+(("acacia_leaves", 132), Naturality::Unknown),
+(("acacia_leaves", 134), Naturality::Unknown),
+(("acacia_leaves", 157), Naturality::Natural),
+(("acacia_leaves", 163), Naturality::Natural),
+(("acacia_leaves", 17), Naturality::Natural),
+(("acacia_leaves", 20), Naturality::Unknown),
+(("acacia_leaves", 22), Naturality::Unknown),
+(("acacia_leaves", 24), Naturality::Built),
+(("acacia_leaves", 26), Naturality::Unknown),
+(("acacia_leaves", 27), Naturality::Natural),
+(("acacia_leaves", 29), Naturality::Natural),
+(("acacia_leaves", 3), Naturality::Unknown),
+(("acacia_leaves", 30), Naturality::Unknown),
+(("acacia_leaves", 35), Naturality::Natural),
+(("acacia_leaves", 36), Naturality::Natural),
+(("acacia_leaves", 37), Naturality::Unknown),
+(("acacia_leaves", 5), Naturality::Unknown),
+(("acacia_leaves", 7), Naturality::Natural),
+(("acacia_leaves", 8), Naturality::Built),
+(("acacia_log", 129), Naturality::Exploited),
+(("acacia_log", 157), Naturality::Exploited),
+(("acacia_log", 163), Naturality::Exploited),
+(("acacia_log", 29), Naturality::Exploited),
+(("acacia_log", 35), Naturality::Natural),
+(("acacia_log", 8), Naturality::Built),
+(("acacia_wood", 129), Naturality::Exploited),
+(("acacia_wood", 157), Naturality::Exploited),
+(("acacia_wood", 163), Naturality::Exploited),
+(("acacia_wood", 29), Naturality::Exploited),
+(("acacia_wood", 35), Naturality::Natural),
+(("acacia_wood", 8), Naturality::Built),
+(("allium", 0), Naturality::Built),
+(("allium", 1), Naturality::Natural),
+(("allium", 129), Naturality::Natural),
+(("allium", 13), Naturality::Natural),
+(("allium", 130), Naturality::Built),
+(("allium", 132), Naturality::Natural),
+(("allium", 157), Naturality::Natural),
+(("allium", 166), Naturality::Built),
+(("allium", 167), Naturality::Built),
+(("allium", 18), Naturality::Natural),
+(("allium", 2), Naturality::Built),
+(("allium", 20), Naturality::Natural),
+(("allium", 24), Naturality::Built),
+(("allium", 29), Naturality::Natural),
+(("allium", 3), Naturality::Natural),
+(("allium", 38), Naturality::Built),
+(("allium", 4), Naturality::Natural),
+(("allium", 5), Naturality::Natural),
+(("allium", 7), Naturality::Built),
+(("andesite", 13), Naturality::Natural),
+(("andesite", 8), Naturality::Built),
+(("azure_bluet", 0), Naturality::Built),
+(("azure_bluet", 1), Naturality::Natural),
+(("azure_bluet", 129), Naturality::Natural),
+(("azure_bluet", 13), Naturality::Natural),
+(("azure_bluet", 130), Naturality::Built),
+(("azure_bluet", 132), Naturality::Natural),
+(("azure_bluet", 157), Naturality::Natural),
+(("azure_bluet", 166), Naturality::Built),
+(("azure_bluet", 167), Naturality::Built),
+(("azure_bluet", 18), Naturality::Natural),
+(("azure_bluet", 2), Naturality::Built),
+(("azure_bluet", 20), Naturality::Natural),
+(("azure_bluet", 24), Naturality::Built),
+(("azure_bluet", 29), Naturality::Natural),
+(("azure_bluet", 3), Naturality::Natural),
+(("azure_bluet", 38), Naturality::Built),
+(("azure_bluet", 4), Naturality::Natural),
+(("azure_bluet", 5), Naturality::Natural),
+(("azure_bluet", 7), Naturality::Built),
+(("birch_leaves", 0), Naturality::Built),
+(("birch_leaves", 13), Naturality::Planted),
+(("birch_leaves", 130), Naturality::Unknown),
+(("birch_leaves", 131), Naturality::Unknown),
+(("birch_leaves", 134), Naturality::Unknown),
+(("birch_leaves", 14), Naturality::Planted),
+(("birch_leaves", 15), Naturality::Unknown),
+(("birch_leaves", 162), Naturality::Unknown),
+(("birch_leaves", 163), Naturality::Planted),
+(("birch_leaves", 19), Naturality::Unknown),
+(("birch_leaves", 2), Naturality::Planted),
+(("birch_leaves", 23), Naturality::Unknown),
+(("birch_leaves", 24), Naturality::Built),
+(("birch_leaves", 26), Naturality::Unknown),
+(("birch_leaves", 30), Naturality::Unknown),
+(("birch_leaves", 33), Naturality::Unknown),
+(("birch_leaves", 36), Naturality::Unknown),
+(("birch_leaves", 37), Naturality::Unknown),
+(("birch_leaves", 39), Naturality::Planted),
+(("birch_leaves", 8), Naturality::Built),
+(("birch_log", 0), Naturality::Built),
+(("birch_log", 1), Naturality::Exploited),
+(("birch_log", 129), Naturality::Exploited),
+(("birch_log", 132), Naturality::Exploited),
+(("birch_log", 149), Naturality::Exploited),
+(("birch_log", 157), Naturality::Exploited),
+(("birch_log", 18), Naturality::Exploited),
+(("birch_log", 21), Naturality::Exploited),
+(("birch_log", 22), Naturality::Exploited),
+(("birch_log", 3), Naturality::Exploited),
+(("birch_log", 35), Naturality::Planted),
+(("birch_log", 4), Naturality::Exploited),
+(("birch_log", 5), Naturality::Exploited),
+(("birch_log", 6), Naturality::Exploited),
+(("birch_log", 8), Naturality::Built),
+(("birch_wood", 0), Naturality::Built),
+(("birch_wood", 1), Naturality::Exploited),
+(("birch_wood", 129), Naturality::Exploited),
+(("birch_wood", 132), Naturality::Exploited),
+(("birch_wood", 149), Naturality::Exploited),
+(("birch_wood", 157), Naturality::Exploited),
+(("birch_wood", 18), Naturality::Exploited),
+(("birch_wood", 21), Naturality::Exploited),
+(("birch_wood", 22), Naturality::Exploited),
+(("birch_wood", 3), Naturality::Exploited),
+(("birch_wood", 35), Naturality::Planted),
+(("birch_wood", 4), Naturality::Exploited),
+(("birch_wood", 5), Naturality::Exploited),
+(("birch_wood", 6), Naturality::Exploited),
+(("birch_wood", 8), Naturality::Built),
+(("black_terracotta", 166), Naturality::Natural),
+(("black_terracotta", 167), Naturality::Natural),
+(("black_terracotta", 38), Naturality::Natural),
+(("black_terracotta", 39), Naturality::Natural),
+(("blue_orchid", 0), Naturality::Built),
+(("blue_orchid", 1), Naturality::Natural),
+(("blue_orchid", 129), Naturality::Natural),
+(("blue_orchid", 13), Naturality::Natural),
+(("blue_orchid", 130), Naturality::Built),
+(("blue_orchid", 132), Naturality::Natural),
+(("blue_orchid", 157), Naturality::Natural),
+(("blue_orchid", 166), Naturality::Built),
+(("blue_orchid", 167), Naturality::Built),
+(("blue_orchid", 18), Naturality::Natural),
+(("blue_orchid", 2), Naturality::Built),
+(("blue_orchid", 20), Naturality::Natural),
+(("blue_orchid", 24), Naturality::Built),
+(("blue_orchid", 29), Naturality::Natural),
+(("blue_orchid", 3), Naturality::Natural),
+(("blue_orchid", 38), Naturality::Built),
+(("blue_orchid", 4), Naturality::Natural),
+(("blue_orchid", 5), Naturality::Natural),
+(("blue_orchid", 7), Naturality::Built),
+(("blue_terracotta", 166), Naturality::Natural),
+(("blue_terracotta", 167), Naturality::Natural),
+(("blue_terracotta", 38), Naturality::Natural),
+(("blue_terracotta", 39), Naturality::Natural),
 (("brown_mushroom", 14), Naturality::Natural),
 (("brown_mushroom", 4), Naturality::Natural),
 (("brown_mushroom", 6), Naturality::Natural),
@@ -253,8 +590,21 @@ lazy_static! {
 (("brown_mushroom_block", 157), Naturality::Natural),
 (("brown_mushroom_block", 29), Naturality::Natural),
 (("brown_mushroom_block", 4), Naturality::Natural),
+(("brown_terracotta", 166), Naturality::Natural),
+(("brown_terracotta", 167), Naturality::Natural),
+(("brown_terracotta", 38), Naturality::Natural),
+(("brown_terracotta", 39), Naturality::Natural),
 (("cactus", 130), Naturality::Natural),
 (("cactus", 2), Naturality::Natural),
+(("chiseled_red_sandstone", 166), Naturality::Exploited),
+(("chiseled_red_sandstone", 167), Naturality::Exploited),
+(("chiseled_red_sandstone", 2), Naturality::Exploited),
+(("chiseled_red_sandstone", 35), Naturality::Unknown),
+(("chiseled_red_sandstone", 38), Naturality::Exploited),
+(("chiseled_red_sandstone", 39), Naturality::Exploited),
+(("chiseled_sandstone", 130), Naturality::Exploited),
+(("chiseled_sandstone", 16), Naturality::Exploited),
+(("chiseled_sandstone", 2), Naturality::Exploited),
 (("clay", 0), Naturality::Natural),
 (("clay", 11), Naturality::Exploited),
 (("clay", 12), Naturality::Exploited),
@@ -271,19 +621,117 @@ lazy_static! {
 (("clay", 4), Naturality::Exploited),
 (("clay", 6), Naturality::Natural),
 (("clay", 7), Naturality::Natural),
+(("coarse_dirt", 0), Naturality::Natural),
+(("coarse_dirt", 11), Naturality::Natural),
+(("coarse_dirt", 12), Naturality::Unknown),
+(("coarse_dirt", 131), Naturality::Unknown),
+(("coarse_dirt", 134), Naturality::Unknown),
+(("coarse_dirt", 15), Naturality::Unknown),
+(("coarse_dirt", 155), Naturality::Unknown),
+(("coarse_dirt", 156), Naturality::Unknown),
+(("coarse_dirt", 162), Naturality::Unknown),
+(("coarse_dirt", 163), Naturality::Natural),
+(("coarse_dirt", 164), Naturality::Unknown),
+(("coarse_dirt", 165), Naturality::Unknown),
+(("coarse_dirt", 166), Naturality::Natural),
+(("coarse_dirt", 17), Naturality::Unknown),
+(("coarse_dirt", 19), Naturality::Unknown),
+(("coarse_dirt", 24), Naturality::Natural),
+(("coarse_dirt", 25), Naturality::Unknown),
+(("coarse_dirt", 26), Naturality::Unknown),
+(("coarse_dirt", 28), Naturality::Unknown),
+(("coarse_dirt", 3), Naturality::Natural),
+(("coarse_dirt", 30), Naturality::Unknown),
+(("coarse_dirt", 31), Naturality::Unknown),
+(("coarse_dirt", 32), Naturality::Unknown),
+(("coarse_dirt", 33), Naturality::Unknown),
+(("coarse_dirt", 36), Naturality::Unknown),
+(("coarse_dirt", 37), Naturality::Unknown),
+(("coarse_dirt", 38), Naturality::Natural),
+(("coarse_dirt", 4), Naturality::Natural),
+(("coarse_dirt", 6), Naturality::Natural),
+(("coarse_dirt", 7), Naturality::Natural),
+(("coarse_dirt", 8), Naturality::Built),
+(("cobweb", 4), Naturality::Natural),
 (("cocoa", 149), Naturality::Natural),
 (("cocoa", 21), Naturality::Natural),
-(("deadbush", 1), Naturality::Built),
-(("deadbush", 12), Naturality::Built),
-(("deadbush", 130), Naturality::Natural),
-(("deadbush", 166), Naturality::Natural),
-(("deadbush", 167), Naturality::Natural),
-(("deadbush", 2), Naturality::Natural),
-(("deadbush", 24), Naturality::Built),
-(("deadbush", 29), Naturality::Built),
-(("deadbush", 38), Naturality::Natural),
-(("deadbush", 39), Naturality::Natural),
-(("deadbush", 7), Naturality::Built),
+(("cut_red_sandstone", 166), Naturality::Exploited),
+(("cut_red_sandstone", 167), Naturality::Exploited),
+(("cut_red_sandstone", 2), Naturality::Exploited),
+(("cut_red_sandstone", 35), Naturality::Unknown),
+(("cut_red_sandstone", 38), Naturality::Exploited),
+(("cut_red_sandstone", 39), Naturality::Exploited),
+(("cut_sandstone", 130), Naturality::Exploited),
+(("cut_sandstone", 16), Naturality::Exploited),
+(("cut_sandstone", 2), Naturality::Exploited),
+(("cyan_terracotta", 166), Naturality::Natural),
+(("cyan_terracotta", 167), Naturality::Natural),
+(("cyan_terracotta", 38), Naturality::Natural),
+(("cyan_terracotta", 39), Naturality::Natural),
+(("dandelion", 1), Naturality::Natural),
+(("dandelion", 129), Naturality::Natural),
+(("dandelion", 13), Naturality::Natural),
+(("dandelion", 133), Naturality::Unknown),
+(("dandelion", 149), Naturality::Unknown),
+(("dandelion", 157), Naturality::Unknown),
+(("dandelion", 158), Naturality::Unknown),
+(("dandelion", 163), Naturality::Natural),
+(("dandelion", 18), Naturality::Natural),
+(("dandelion", 20), Naturality::Natural),
+(("dandelion", 29), Naturality::Unknown),
+(("dandelion", 3), Naturality::Unknown),
+(("dandelion", 34), Naturality::Unknown),
+(("dandelion", 35), Naturality::Natural),
+(("dandelion", 4), Naturality::Unknown),
+(("dandelion", 5), Naturality::Natural),
+(("dark_oak_leaves", 0), Naturality::Built),
+(("dark_oak_leaves", 12), Naturality::Natural),
+(("dark_oak_leaves", 13), Naturality::Unknown),
+(("dark_oak_leaves", 130), Naturality::Unknown),
+(("dark_oak_leaves", 132), Naturality::Unknown),
+(("dark_oak_leaves", 134), Naturality::Unknown),
+(("dark_oak_leaves", 157), Naturality::Natural),
+(("dark_oak_leaves", 163), Naturality::Natural),
+(("dark_oak_leaves", 17), Naturality::Natural),
+(("dark_oak_leaves", 20), Naturality::Unknown),
+(("dark_oak_leaves", 22), Naturality::Unknown),
+(("dark_oak_leaves", 24), Naturality::Built),
+(("dark_oak_leaves", 26), Naturality::Unknown),
+(("dark_oak_leaves", 27), Naturality::Natural),
+(("dark_oak_leaves", 29), Naturality::Natural),
+(("dark_oak_leaves", 3), Naturality::Unknown),
+(("dark_oak_leaves", 30), Naturality::Unknown),
+(("dark_oak_leaves", 35), Naturality::Natural),
+(("dark_oak_leaves", 36), Naturality::Natural),
+(("dark_oak_leaves", 37), Naturality::Unknown),
+(("dark_oak_leaves", 5), Naturality::Unknown),
+(("dark_oak_leaves", 7), Naturality::Natural),
+(("dark_oak_leaves", 8), Naturality::Built),
+(("dark_oak_log", 129), Naturality::Exploited),
+(("dark_oak_log", 157), Naturality::Exploited),
+(("dark_oak_log", 163), Naturality::Exploited),
+(("dark_oak_log", 29), Naturality::Exploited),
+(("dark_oak_log", 35), Naturality::Natural),
+(("dark_oak_log", 8), Naturality::Built),
+(("dark_oak_wood", 129), Naturality::Exploited),
+(("dark_oak_wood", 157), Naturality::Exploited),
+(("dark_oak_wood", 163), Naturality::Exploited),
+(("dark_oak_wood", 29), Naturality::Exploited),
+(("dark_oak_wood", 35), Naturality::Natural),
+(("dark_oak_wood", 8), Naturality::Built),
+(("dead_bush", 1), Naturality::Built),
+(("dead_bush", 12), Naturality::Built),
+(("dead_bush", 130), Naturality::Natural),
+(("dead_bush", 166), Naturality::Natural),
+(("dead_bush", 167), Naturality::Natural),
+(("dead_bush", 2), Naturality::Natural),
+(("dead_bush", 24), Naturality::Built),
+(("dead_bush", 29), Naturality::Built),
+(("dead_bush", 38), Naturality::Natural),
+(("dead_bush", 39), Naturality::Natural),
+(("dead_bush", 7), Naturality::Built),
+(("diorite", 13), Naturality::Natural),
+(("diorite", 8), Naturality::Built),
 (("dirt", 0), Naturality::Natural),
 (("dirt", 11), Naturality::Natural),
 (("dirt", 12), Naturality::Unknown),
@@ -315,37 +763,60 @@ lazy_static! {
 (("dirt", 6), Naturality::Natural),
 (("dirt", 7), Naturality::Natural),
 (("dirt", 8), Naturality::Built),
-(("flowing_lava", 8), Naturality::Natural),
+(("fern", 0), Naturality::Built),
+(("fern", 11), Naturality::Unknown),
+(("fern", 12), Naturality::Unknown),
+(("fern", 130), Naturality::Unknown),
+(("fern", 131), Naturality::Unknown),
+(("fern", 140), Naturality::Unknown),
+(("fern", 16), Naturality::Unknown),
+(("fern", 167), Naturality::Unknown),
+(("fern", 2), Naturality::Unknown),
+(("fern", 21), Naturality::Unknown),
+(("fern", 22), Naturality::Unknown),
+(("fern", 24), Naturality::Unknown),
+(("fern", 30), Naturality::Unknown),
+(("fern", 32), Naturality::Unknown),
+(("fern", 33), Naturality::Unknown),
+(("fern", 36), Naturality::Unknown),
+(("fern", 39), Naturality::Unknown),
+(("fern", 7), Naturality::Unknown),
 (("glowstone", 8), Naturality::Natural),
-(("grass", 0), Naturality::Built),
-(("grass", 11), Naturality::Unknown),
-(("grass", 12), Naturality::Unknown),
-(("grass", 130), Naturality::Built),
-(("grass", 131), Naturality::Unknown),
-(("grass", 134), Naturality::Unknown),
-(("grass", 14), Naturality::Built),
-(("grass", 140), Naturality::Unknown),
-(("grass", 15), Naturality::Unknown),
-(("grass", 162), Naturality::Unknown),
-(("grass", 19), Naturality::Unknown),
-(("grass", 2), Naturality::Built),
-(("grass", 24), Naturality::Built),
-(("grass", 25), Naturality::Unknown),
-(("grass", 26), Naturality::Unknown),
-(("grass", 28), Naturality::Unknown),
-(("grass", 30), Naturality::Unknown),
-(("grass", 32), Naturality::Unknown),
-(("grass", 33), Naturality::Unknown),
-(("grass", 36), Naturality::Unknown),
-(("grass", 37), Naturality::Unknown),
-(("grass", 39), Naturality::Planted),
-(("grass", 7), Naturality::Unknown),
-(("grass", 8), Naturality::Built),
+(("granite", 13), Naturality::Natural),
+(("granite", 8), Naturality::Built),
+(("grass_block", 0), Naturality::Built),
+(("grass_block", 11), Naturality::Unknown),
+(("grass_block", 12), Naturality::Unknown),
+(("grass_block", 130), Naturality::Built),
+(("grass_block", 131), Naturality::Unknown),
+(("grass_block", 134), Naturality::Unknown),
+(("grass_block", 14), Naturality::Built),
+(("grass_block", 140), Naturality::Unknown),
+(("grass_block", 15), Naturality::Unknown),
+(("grass_block", 162), Naturality::Unknown),
+(("grass_block", 19), Naturality::Unknown),
+(("grass_block", 2), Naturality::Built),
+(("grass_block", 24), Naturality::Built),
+(("grass_block", 25), Naturality::Unknown),
+(("grass_block", 26), Naturality::Unknown),
+(("grass_block", 28), Naturality::Unknown),
+(("grass_block", 30), Naturality::Unknown),
+(("grass_block", 32), Naturality::Unknown),
+(("grass_block", 33), Naturality::Unknown),
+(("grass_block", 36), Naturality::Unknown),
+(("grass_block", 37), Naturality::Unknown),
+(("grass_block", 39), Naturality::Planted),
+(("grass_block", 7), Naturality::Unknown),
+(("grass_block", 8), Naturality::Built),
 (("gravel", 24), Naturality::Natural),
-(("hardened_clay", 166), Naturality::Natural),
-(("hardened_clay", 167), Naturality::Natural),
-(("hardened_clay", 38), Naturality::Natural),
-(("hardened_clay", 39), Naturality::Natural),
+(("gray_terracotta", 166), Naturality::Natural),
+(("gray_terracotta", 167), Naturality::Natural),
+(("gray_terracotta", 38), Naturality::Natural),
+(("gray_terracotta", 39), Naturality::Natural),
+(("green_terracotta", 166), Naturality::Natural),
+(("green_terracotta", 167), Naturality::Natural),
+(("green_terracotta", 38), Naturality::Natural),
+(("green_terracotta", 39), Naturality::Natural),
 (("ice", 11), Naturality::Natural),
 (("ice", 13), Naturality::Natural),
 (("ice", 133), Naturality::Natural),
@@ -354,119 +825,325 @@ lazy_static! {
 (("ice", 20), Naturality::Unknown),
 (("ice", 3), Naturality::Unknown),
 (("ice", 34), Naturality::Unknown),
+(("jungle_leaves", 0), Naturality::Built),
+(("jungle_leaves", 13), Naturality::Planted),
+(("jungle_leaves", 130), Naturality::Unknown),
+(("jungle_leaves", 131), Naturality::Unknown),
+(("jungle_leaves", 134), Naturality::Unknown),
+(("jungle_leaves", 14), Naturality::Planted),
+(("jungle_leaves", 15), Naturality::Unknown),
+(("jungle_leaves", 162), Naturality::Unknown),
+(("jungle_leaves", 163), Naturality::Planted),
+(("jungle_leaves", 19), Naturality::Unknown),
+(("jungle_leaves", 2), Naturality::Planted),
+(("jungle_leaves", 23), Naturality::Unknown),
+(("jungle_leaves", 24), Naturality::Built),
+(("jungle_leaves", 26), Naturality::Unknown),
+(("jungle_leaves", 30), Naturality::Unknown),
+(("jungle_leaves", 33), Naturality::Unknown),
+(("jungle_leaves", 36), Naturality::Unknown),
+(("jungle_leaves", 37), Naturality::Unknown),
+(("jungle_leaves", 39), Naturality::Planted),
+(("jungle_leaves", 8), Naturality::Built),
+(("jungle_log", 0), Naturality::Built),
+(("jungle_log", 1), Naturality::Exploited),
+(("jungle_log", 129), Naturality::Exploited),
+(("jungle_log", 132), Naturality::Exploited),
+(("jungle_log", 149), Naturality::Exploited),
+(("jungle_log", 157), Naturality::Exploited),
+(("jungle_log", 18), Naturality::Exploited),
+(("jungle_log", 21), Naturality::Exploited),
+(("jungle_log", 22), Naturality::Exploited),
+(("jungle_log", 3), Naturality::Exploited),
+(("jungle_log", 35), Naturality::Planted),
+(("jungle_log", 4), Naturality::Exploited),
+(("jungle_log", 5), Naturality::Exploited),
+(("jungle_log", 6), Naturality::Exploited),
+(("jungle_log", 8), Naturality::Built),
+(("jungle_wood", 0), Naturality::Built),
+(("jungle_wood", 1), Naturality::Exploited),
+(("jungle_wood", 129), Naturality::Exploited),
+(("jungle_wood", 132), Naturality::Exploited),
+(("jungle_wood", 149), Naturality::Exploited),
+(("jungle_wood", 157), Naturality::Exploited),
+(("jungle_wood", 18), Naturality::Exploited),
+(("jungle_wood", 21), Naturality::Exploited),
+(("jungle_wood", 22), Naturality::Exploited),
+(("jungle_wood", 3), Naturality::Exploited),
+(("jungle_wood", 35), Naturality::Planted),
+(("jungle_wood", 4), Naturality::Exploited),
+(("jungle_wood", 5), Naturality::Exploited),
+(("jungle_wood", 6), Naturality::Exploited),
+(("jungle_wood", 8), Naturality::Built),
 (("lava", 8), Naturality::Natural),
-(("leaves", 0), Naturality::Built),
-(("leaves", 13), Naturality::Planted),
-(("leaves", 130), Naturality::Unknown),
-(("leaves", 131), Naturality::Unknown),
-(("leaves", 134), Naturality::Unknown),
-(("leaves", 14), Naturality::Planted),
-(("leaves", 15), Naturality::Unknown),
-(("leaves", 162), Naturality::Unknown),
-(("leaves", 163), Naturality::Planted),
-(("leaves", 19), Naturality::Unknown),
-(("leaves", 2), Naturality::Planted),
-(("leaves", 23), Naturality::Unknown),
-(("leaves", 24), Naturality::Built),
-(("leaves", 26), Naturality::Unknown),
-(("leaves", 30), Naturality::Unknown),
-(("leaves", 33), Naturality::Unknown),
-(("leaves", 36), Naturality::Unknown),
-(("leaves", 37), Naturality::Unknown),
-(("leaves", 39), Naturality::Planted),
-(("leaves", 8), Naturality::Built),
-(("leaves2", 0), Naturality::Built),
-(("leaves2", 12), Naturality::Natural),
-(("leaves2", 13), Naturality::Unknown),
-(("leaves2", 130), Naturality::Unknown),
-(("leaves2", 132), Naturality::Unknown),
-(("leaves2", 134), Naturality::Unknown),
-(("leaves2", 157), Naturality::Natural),
-(("leaves2", 163), Naturality::Natural),
-(("leaves2", 17), Naturality::Natural),
-(("leaves2", 20), Naturality::Unknown),
-(("leaves2", 22), Naturality::Unknown),
-(("leaves2", 24), Naturality::Built),
-(("leaves2", 26), Naturality::Unknown),
-(("leaves2", 27), Naturality::Natural),
-(("leaves2", 29), Naturality::Natural),
-(("leaves2", 3), Naturality::Unknown),
-(("leaves2", 30), Naturality::Unknown),
-(("leaves2", 35), Naturality::Natural),
-(("leaves2", 36), Naturality::Natural),
-(("leaves2", 37), Naturality::Unknown),
-(("leaves2", 5), Naturality::Unknown),
-(("leaves2", 7), Naturality::Natural),
-(("leaves2", 8), Naturality::Built),
-(("log", 0), Naturality::Built),
-(("log", 1), Naturality::Exploited),
-(("log", 129), Naturality::Exploited),
-(("log", 132), Naturality::Exploited),
-(("log", 149), Naturality::Exploited),
-(("log", 157), Naturality::Exploited),
-(("log", 18), Naturality::Exploited),
-(("log", 21), Naturality::Exploited),
-(("log", 22), Naturality::Exploited),
-(("log", 3), Naturality::Exploited),
-(("log", 35), Naturality::Planted),
-(("log", 4), Naturality::Exploited),
-(("log", 5), Naturality::Exploited),
-(("log", 6), Naturality::Exploited),
-(("log", 8), Naturality::Built),
-(("log2", 129), Naturality::Exploited),
-(("log2", 157), Naturality::Exploited),
-(("log2", 163), Naturality::Exploited),
-(("log2", 29), Naturality::Exploited),
-(("log2", 35), Naturality::Natural),
-(("log2", 8), Naturality::Built),
-(("magma", 8), Naturality::Natural),
-(("melon_block", 149), Naturality::Unknown),
-(("melon_block", 21), Naturality::Unknown),
-(("melon_block", 22), Naturality::Unknown),
+(("light_blue_terracotta", 166), Naturality::Natural),
+(("light_blue_terracotta", 167), Naturality::Natural),
+(("light_blue_terracotta", 38), Naturality::Natural),
+(("light_blue_terracotta", 39), Naturality::Natural),
+(("light_gray_terracotta", 166), Naturality::Natural),
+(("light_gray_terracotta", 167), Naturality::Natural),
+(("light_gray_terracotta", 38), Naturality::Natural),
+(("light_gray_terracotta", 39), Naturality::Natural),
+(("lily_pad", 0), Naturality::Unknown),
+(("lily_pad", 16), Naturality::Unknown),
+(("lily_pad", 6), Naturality::Natural),
+(("lily_pad", 7), Naturality::Unknown),
+(("lime_terracotta", 166), Naturality::Natural),
+(("lime_terracotta", 167), Naturality::Natural),
+(("lime_terracotta", 38), Naturality::Natural),
+(("lime_terracotta", 39), Naturality::Natural),
+(("magenta_terracotta", 166), Naturality::Natural),
+(("magenta_terracotta", 167), Naturality::Natural),
+(("magenta_terracotta", 38), Naturality::Natural),
+(("magenta_terracotta", 39), Naturality::Natural),
+(("magma_block", 8), Naturality::Natural),
+(("melon", 149), Naturality::Unknown),
+(("melon", 21), Naturality::Unknown),
+(("melon", 22), Naturality::Unknown),
 (("mossy_cobblestone", 149), Naturality::Unknown),
 (("mossy_cobblestone", 21), Naturality::Unknown),
 (("mossy_cobblestone", 22), Naturality::Unknown),
 (("mossy_cobblestone", 8), Naturality::Built),
+(("mushroom_stem", 14), Naturality::Natural),
+(("mushroom_stem", 157), Naturality::Natural),
+(("mushroom_stem", 29), Naturality::Natural),
+(("mushroom_stem", 4), Naturality::Natural),
 (("mycelium", 14), Naturality::Natural),
+(("nether_quartz_ore", 8), Naturality::Exploited),
 (("netherrack", 8), Naturality::Natural),
+(("oak_leaves", 0), Naturality::Built),
+(("oak_leaves", 13), Naturality::Planted),
+(("oak_leaves", 130), Naturality::Unknown),
+(("oak_leaves", 131), Naturality::Unknown),
+(("oak_leaves", 134), Naturality::Unknown),
+(("oak_leaves", 14), Naturality::Planted),
+(("oak_leaves", 15), Naturality::Unknown),
+(("oak_leaves", 162), Naturality::Unknown),
+(("oak_leaves", 163), Naturality::Planted),
+(("oak_leaves", 19), Naturality::Unknown),
+(("oak_leaves", 2), Naturality::Planted),
+(("oak_leaves", 23), Naturality::Unknown),
+(("oak_leaves", 24), Naturality::Built),
+(("oak_leaves", 26), Naturality::Unknown),
+(("oak_leaves", 30), Naturality::Unknown),
+(("oak_leaves", 33), Naturality::Unknown),
+(("oak_leaves", 36), Naturality::Unknown),
+(("oak_leaves", 37), Naturality::Unknown),
+(("oak_leaves", 39), Naturality::Planted),
+(("oak_leaves", 8), Naturality::Built),
+(("oak_log", 0), Naturality::Built),
+(("oak_log", 1), Naturality::Exploited),
+(("oak_log", 129), Naturality::Exploited),
+(("oak_log", 132), Naturality::Exploited),
+(("oak_log", 149), Naturality::Exploited),
+(("oak_log", 157), Naturality::Exploited),
+(("oak_log", 18), Naturality::Exploited),
+(("oak_log", 21), Naturality::Exploited),
+(("oak_log", 22), Naturality::Exploited),
+(("oak_log", 3), Naturality::Exploited),
+(("oak_log", 35), Naturality::Planted),
+(("oak_log", 4), Naturality::Exploited),
+(("oak_log", 5), Naturality::Exploited),
+(("oak_log", 6), Naturality::Exploited),
+(("oak_log", 8), Naturality::Built),
+(("oak_wood", 0), Naturality::Built),
+(("oak_wood", 1), Naturality::Exploited),
+(("oak_wood", 129), Naturality::Exploited),
+(("oak_wood", 132), Naturality::Exploited),
+(("oak_wood", 149), Naturality::Exploited),
+(("oak_wood", 157), Naturality::Exploited),
+(("oak_wood", 18), Naturality::Exploited),
+(("oak_wood", 21), Naturality::Exploited),
+(("oak_wood", 22), Naturality::Exploited),
+(("oak_wood", 3), Naturality::Exploited),
+(("oak_wood", 35), Naturality::Planted),
+(("oak_wood", 4), Naturality::Exploited),
+(("oak_wood", 5), Naturality::Exploited),
+(("oak_wood", 6), Naturality::Exploited),
+(("oak_wood", 8), Naturality::Built),
 (("obsidian", 8), Naturality::Natural),
+(("orange_terracotta", 166), Naturality::Natural),
+(("orange_terracotta", 167), Naturality::Natural),
+(("orange_terracotta", 38), Naturality::Natural),
+(("orange_terracotta", 39), Naturality::Natural),
+(("orange_tulip", 0), Naturality::Built),
+(("orange_tulip", 1), Naturality::Natural),
+(("orange_tulip", 129), Naturality::Natural),
+(("orange_tulip", 13), Naturality::Natural),
+(("orange_tulip", 130), Naturality::Built),
+(("orange_tulip", 132), Naturality::Natural),
+(("orange_tulip", 157), Naturality::Natural),
+(("orange_tulip", 166), Naturality::Built),
+(("orange_tulip", 167), Naturality::Built),
+(("orange_tulip", 18), Naturality::Natural),
+(("orange_tulip", 2), Naturality::Built),
+(("orange_tulip", 20), Naturality::Natural),
+(("orange_tulip", 24), Naturality::Built),
+(("orange_tulip", 29), Naturality::Natural),
+(("orange_tulip", 3), Naturality::Natural),
+(("orange_tulip", 38), Naturality::Built),
+(("orange_tulip", 4), Naturality::Natural),
+(("orange_tulip", 5), Naturality::Natural),
+(("orange_tulip", 7), Naturality::Built),
+(("oxeye_daisy", 0), Naturality::Built),
+(("oxeye_daisy", 1), Naturality::Natural),
+(("oxeye_daisy", 129), Naturality::Natural),
+(("oxeye_daisy", 13), Naturality::Natural),
+(("oxeye_daisy", 130), Naturality::Built),
+(("oxeye_daisy", 132), Naturality::Natural),
+(("oxeye_daisy", 157), Naturality::Natural),
+(("oxeye_daisy", 166), Naturality::Built),
+(("oxeye_daisy", 167), Naturality::Built),
+(("oxeye_daisy", 18), Naturality::Natural),
+(("oxeye_daisy", 2), Naturality::Built),
+(("oxeye_daisy", 20), Naturality::Natural),
+(("oxeye_daisy", 24), Naturality::Built),
+(("oxeye_daisy", 29), Naturality::Natural),
+(("oxeye_daisy", 3), Naturality::Natural),
+(("oxeye_daisy", 38), Naturality::Built),
+(("oxeye_daisy", 4), Naturality::Natural),
+(("oxeye_daisy", 5), Naturality::Natural),
+(("oxeye_daisy", 7), Naturality::Built),
 (("packed_ice", 11), Naturality::Unknown),
 (("packed_ice", 140), Naturality::Natural),
-(("quartz_ore", 8), Naturality::Exploited),
-(("red_flower", 0), Naturality::Built),
-(("red_flower", 1), Naturality::Natural),
-(("red_flower", 129), Naturality::Natural),
-(("red_flower", 13), Naturality::Natural),
-(("red_flower", 130), Naturality::Built),
-(("red_flower", 132), Naturality::Natural),
-(("red_flower", 157), Naturality::Natural),
-(("red_flower", 166), Naturality::Built),
-(("red_flower", 167), Naturality::Built),
-(("red_flower", 18), Naturality::Natural),
-(("red_flower", 2), Naturality::Built),
-(("red_flower", 20), Naturality::Natural),
-(("red_flower", 24), Naturality::Built),
-(("red_flower", 29), Naturality::Natural),
-(("red_flower", 3), Naturality::Natural),
-(("red_flower", 38), Naturality::Built),
-(("red_flower", 4), Naturality::Natural),
-(("red_flower", 5), Naturality::Natural),
-(("red_flower", 7), Naturality::Built),
+(("pink_terracotta", 166), Naturality::Natural),
+(("pink_terracotta", 167), Naturality::Natural),
+(("pink_terracotta", 38), Naturality::Natural),
+(("pink_terracotta", 39), Naturality::Natural),
+(("pink_tulip", 0), Naturality::Built),
+(("pink_tulip", 1), Naturality::Natural),
+(("pink_tulip", 129), Naturality::Natural),
+(("pink_tulip", 13), Naturality::Natural),
+(("pink_tulip", 130), Naturality::Built),
+(("pink_tulip", 132), Naturality::Natural),
+(("pink_tulip", 157), Naturality::Natural),
+(("pink_tulip", 166), Naturality::Built),
+(("pink_tulip", 167), Naturality::Built),
+(("pink_tulip", 18), Naturality::Natural),
+(("pink_tulip", 2), Naturality::Built),
+(("pink_tulip", 20), Naturality::Natural),
+(("pink_tulip", 24), Naturality::Built),
+(("pink_tulip", 29), Naturality::Natural),
+(("pink_tulip", 3), Naturality::Natural),
+(("pink_tulip", 38), Naturality::Built),
+(("pink_tulip", 4), Naturality::Natural),
+(("pink_tulip", 5), Naturality::Natural),
+(("pink_tulip", 7), Naturality::Built),
+(("podzol", 0), Naturality::Natural),
+(("podzol", 11), Naturality::Natural),
+(("podzol", 12), Naturality::Unknown),
+(("podzol", 131), Naturality::Unknown),
+(("podzol", 134), Naturality::Unknown),
+(("podzol", 15), Naturality::Unknown),
+(("podzol", 155), Naturality::Unknown),
+(("podzol", 156), Naturality::Unknown),
+(("podzol", 162), Naturality::Unknown),
+(("podzol", 163), Naturality::Natural),
+(("podzol", 164), Naturality::Unknown),
+(("podzol", 165), Naturality::Unknown),
+(("podzol", 166), Naturality::Natural),
+(("podzol", 17), Naturality::Unknown),
+(("podzol", 19), Naturality::Unknown),
+(("podzol", 24), Naturality::Natural),
+(("podzol", 25), Naturality::Unknown),
+(("podzol", 26), Naturality::Unknown),
+(("podzol", 28), Naturality::Unknown),
+(("podzol", 3), Naturality::Natural),
+(("podzol", 30), Naturality::Unknown),
+(("podzol", 31), Naturality::Unknown),
+(("podzol", 32), Naturality::Unknown),
+(("podzol", 33), Naturality::Unknown),
+(("podzol", 36), Naturality::Unknown),
+(("podzol", 37), Naturality::Unknown),
+(("podzol", 38), Naturality::Natural),
+(("podzol", 4), Naturality::Natural),
+(("podzol", 6), Naturality::Natural),
+(("podzol", 7), Naturality::Natural),
+(("podzol", 8), Naturality::Built),
+(("polished_andesite", 13), Naturality::Natural),
+(("polished_andesite", 8), Naturality::Built),
+(("polished_diorite", 13), Naturality::Natural),
+(("polished_diorite", 8), Naturality::Built),
+(("polished_granite", 13), Naturality::Natural),
+(("polished_granite", 8), Naturality::Built),
+(("poppy", 0), Naturality::Built),
+(("poppy", 1), Naturality::Natural),
+(("poppy", 129), Naturality::Natural),
+(("poppy", 13), Naturality::Natural),
+(("poppy", 130), Naturality::Built),
+(("poppy", 132), Naturality::Natural),
+(("poppy", 157), Naturality::Natural),
+(("poppy", 166), Naturality::Built),
+(("poppy", 167), Naturality::Built),
+(("poppy", 18), Naturality::Natural),
+(("poppy", 2), Naturality::Built),
+(("poppy", 20), Naturality::Natural),
+(("poppy", 24), Naturality::Built),
+(("poppy", 29), Naturality::Natural),
+(("poppy", 3), Naturality::Natural),
+(("poppy", 38), Naturality::Built),
+(("poppy", 4), Naturality::Natural),
+(("poppy", 5), Naturality::Natural),
+(("poppy", 7), Naturality::Built),
+(("purple_terracotta", 166), Naturality::Natural),
+(("purple_terracotta", 167), Naturality::Natural),
+(("purple_terracotta", 38), Naturality::Natural),
+(("purple_terracotta", 39), Naturality::Natural),
 (("red_mushroom", 14), Naturality::Natural),
 (("red_mushroom", 4), Naturality::Natural),
 (("red_mushroom", 6), Naturality::Natural),
 (("red_mushroom_block", 14), Naturality::Natural),
 (("red_mushroom_block", 157), Naturality::Natural),
 (("red_mushroom_block", 29), Naturality::Natural),
+(("red_sand", 0), Naturality::Natural),
+(("red_sand", 1), Naturality::Pond),
+(("red_sand", 11), Naturality::Exploited),
+(("red_sand", 13), Naturality::Exploited),
+(("red_sand", 130), Naturality::Natural),
+(("red_sand", 132), Naturality::Pond),
+(("red_sand", 16), Naturality::Natural),
+(("red_sand", 166), Naturality::Pond),
+(("red_sand", 167), Naturality::Pond),
+(("red_sand", 18), Naturality::Pond),
+(("red_sand", 2), Naturality::Natural),
+(("red_sand", 24), Naturality::Natural),
+(("red_sand", 3), Naturality::Pond),
+(("red_sand", 38), Naturality::Pond),
+(("red_sand", 39), Naturality::Pond),
+(("red_sand", 4), Naturality::Pond),
+(("red_sand", 5), Naturality::Pond),
+(("red_sand", 6), Naturality::Natural),
+(("red_sand", 7), Naturality::Natural),
+(("red_sand", 8), Naturality::Built),
 (("red_sandstone", 166), Naturality::Exploited),
 (("red_sandstone", 167), Naturality::Exploited),
 (("red_sandstone", 2), Naturality::Exploited),
 (("red_sandstone", 35), Naturality::Unknown),
 (("red_sandstone", 38), Naturality::Exploited),
 (("red_sandstone", 39), Naturality::Exploited),
-(("reeds", 16), Naturality::Natural),
-(("reeds", 6), Naturality::Natural),
-(("reeds", 7), Naturality::Natural),
+(("red_terracotta", 166), Naturality::Natural),
+(("red_terracotta", 167), Naturality::Natural),
+(("red_terracotta", 38), Naturality::Natural),
+(("red_terracotta", 39), Naturality::Natural),
+(("red_tulip", 0), Naturality::Built),
+(("red_tulip", 1), Naturality::Natural),
+(("red_tulip", 129), Naturality::Natural),
+(("red_tulip", 13), Naturality::Natural),
+(("red_tulip", 130), Naturality::Built),
+(("red_tulip", 132), Naturality::Natural),
+(("red_tulip", 157), Naturality::Natural),
+(("red_tulip", 166), Naturality::Built),
+(("red_tulip", 167), Naturality::Built),
+(("red_tulip", 18), Naturality::Natural),
+(("red_tulip", 2), Naturality::Built),
+(("red_tulip", 20), Naturality::Natural),
+(("red_tulip", 24), Naturality::Built),
+(("red_tulip", 29), Naturality::Natural),
+(("red_tulip", 3), Naturality::Natural),
+(("red_tulip", 38), Naturality::Built),
+(("red_tulip", 4), Naturality::Natural),
+(("red_tulip", 5), Naturality::Natural),
+(("red_tulip", 7), Naturality::Built),
 (("sand", 0), Naturality::Natural),
 (("sand", 1), Naturality::Pond),
 (("sand", 11), Naturality::Exploited),
@@ -490,51 +1167,104 @@ lazy_static! {
 (("sandstone", 130), Naturality::Exploited),
 (("sandstone", 16), Naturality::Exploited),
 (("sandstone", 2), Naturality::Exploited),
-(("snow", 13), Naturality::Exploited),
-(("snow", 140), Naturality::Exploited),
-(("snow", 20), Naturality::Unknown),
-(("snow", 34), Naturality::Unknown),
-(("snow_layer", 11), Naturality::Natural),
-(("snow_layer", 12), Naturality::Unknown),
-(("snow_layer", 13), Naturality::Natural),
-(("snow_layer", 131), Naturality::Unknown),
-(("snow_layer", 133), Naturality::Natural),
-(("snow_layer", 140), Naturality::Natural),
-(("snow_layer", 158), Naturality::Natural),
-(("snow_layer", 162), Naturality::Unknown),
-(("snow_layer", 20), Naturality::Natural),
-(("snow_layer", 26), Naturality::Unknown),
-(("snow_layer", 3), Naturality::Natural),
-(("snow_layer", 30), Naturality::Unknown),
-(("snow_layer", 32), Naturality::Unknown),
-(("snow_layer", 34), Naturality::Natural),
-(("snow_layer", 5), Naturality::Unknown),
+(("short_grass", 0), Naturality::Built),
+(("short_grass", 11), Naturality::Unknown),
+(("short_grass", 12), Naturality::Unknown),
+(("short_grass", 130), Naturality::Unknown),
+(("short_grass", 131), Naturality::Unknown),
+(("short_grass", 140), Naturality::Unknown),
+(("short_grass", 16), Naturality::Unknown),
+(("short_grass", 167), Naturality::Unknown),
+(("short_grass", 2), Naturality::Unknown),
+(("short_grass", 21), Naturality::Unknown),
+(("short_grass", 22), Naturality::Unknown),
+(("short_grass", 24), Naturality::Unknown),
+(("short_grass", 30), Naturality::Unknown),
+(("short_grass", 32), Naturality::Unknown),
+(("short_grass", 33), Naturality::Unknown),
+(("short_grass", 36), Naturality::Unknown),
+(("short_grass", 39), Naturality::Unknown),
+(("short_grass", 7), Naturality::Unknown),
+(("snow", 11), Naturality::Natural),
+(("snow", 12), Naturality::Unknown),
+(("snow", 13), Naturality::Natural),
+(("snow", 131), Naturality::Unknown),
+(("snow", 133), Naturality::Natural),
+(("snow", 140), Naturality::Natural),
+(("snow", 158), Naturality::Natural),
+(("snow", 162), Naturality::Unknown),
+(("snow", 20), Naturality::Natural),
+(("snow", 26), Naturality::Unknown),
+(("snow", 3), Naturality::Natural),
+(("snow", 30), Naturality::Unknown),
+(("snow", 32), Naturality::Unknown),
+(("snow", 34), Naturality::Natural),
+(("snow", 5), Naturality::Unknown),
+(("snow_block", 13), Naturality::Exploited),
+(("snow_block", 140), Naturality::Exploited),
+(("snow_block", 20), Naturality::Unknown),
+(("snow_block", 34), Naturality::Unknown),
 (("soul_sand", 4), Naturality::Natural),
 (("soul_sand", 8), Naturality::Natural),
-(("stained_hardened_clay", 166), Naturality::Natural),
-(("stained_hardened_clay", 167), Naturality::Natural),
-(("stained_hardened_clay", 38), Naturality::Natural),
-(("stained_hardened_clay", 39), Naturality::Natural),
+(("spruce_leaves", 0), Naturality::Built),
+(("spruce_leaves", 13), Naturality::Planted),
+(("spruce_leaves", 130), Naturality::Unknown),
+(("spruce_leaves", 131), Naturality::Unknown),
+(("spruce_leaves", 134), Naturality::Unknown),
+(("spruce_leaves", 14), Naturality::Planted),
+(("spruce_leaves", 15), Naturality::Unknown),
+(("spruce_leaves", 162), Naturality::Unknown),
+(("spruce_leaves", 163), Naturality::Planted),
+(("spruce_leaves", 19), Naturality::Unknown),
+(("spruce_leaves", 2), Naturality::Planted),
+(("spruce_leaves", 23), Naturality::Unknown),
+(("spruce_leaves", 24), Naturality::Built),
+(("spruce_leaves", 26), Naturality::Unknown),
+(("spruce_leaves", 30), Naturality::Unknown),
+(("spruce_leaves", 33), Naturality::Unknown),
+(("spruce_leaves", 36), Naturality::Unknown),
+(("spruce_leaves", 37), Naturality::Unknown),
+(("spruce_leaves", 39), Naturality::Planted),
+(("spruce_leaves", 8), Naturality::Built),
+(("spruce_log", 0), Naturality::Built),
+(("spruce_log", 1), Naturality::Exploited),
+(("spruce_log", 129), Naturality::Exploited),
+(("spruce_log", 132), Naturality::Exploited),
+(("spruce_log", 149), Naturality::Exploited),
+(("spruce_log", 157), Naturality::Exploited),
+(("spruce_log", 18), Naturality::Exploited),
+(("spruce_log", 21), Naturality::Exploited),
+(("spruce_log", 22), Naturality::Exploited),
+(("spruce_log", 3), Naturality::Exploited),
+(("spruce_log", 35), Naturality::Planted),
+(("spruce_log", 4), Naturality::Exploited),
+(("spruce_log", 5), Naturality::Exploited),
+(("spruce_log", 6), Naturality::Exploited),
+(("spruce_log", 8), Naturality::Built),
+(("spruce_wood", 0), Naturality::Built),
+(("spruce_wood", 1), Naturality::Exploited),
+(("spruce_wood", 129), Naturality::Exploited),
+(("spruce_wood", 132), Naturality::Exploited),
+(("spruce_wood", 149), Naturality::Exploited),
+(("spruce_wood", 157), Naturality::Exploited),
+(("spruce_wood", 18), Naturality::Exploited),
+(("spruce_wood", 21), Naturality::Exploited),
+(("spruce_wood", 22), Naturality::Exploited),
+(("spruce_wood", 3), Naturality::Exploited),
+(("spruce_wood", 35), Naturality::Planted),
+(("spruce_wood", 4), Naturality::Exploited),
+(("spruce_wood", 5), Naturality::Exploited),
+(("spruce_wood", 6), Naturality::Exploited),
+(("spruce_wood", 8), Naturality::Built),
 (("stone", 13), Naturality::Natural),
 (("stone", 8), Naturality::Built),
-(("tallgrass", 0), Naturality::Built),
-(("tallgrass", 11), Naturality::Unknown),
-(("tallgrass", 12), Naturality::Unknown),
-(("tallgrass", 130), Naturality::Unknown),
-(("tallgrass", 131), Naturality::Unknown),
-(("tallgrass", 140), Naturality::Unknown),
-(("tallgrass", 16), Naturality::Unknown),
-(("tallgrass", 167), Naturality::Unknown),
-(("tallgrass", 2), Naturality::Unknown),
-(("tallgrass", 21), Naturality::Unknown),
-(("tallgrass", 22), Naturality::Unknown),
-(("tallgrass", 24), Naturality::Unknown),
-(("tallgrass", 30), Naturality::Unknown),
-(("tallgrass", 32), Naturality::Unknown),
-(("tallgrass", 33), Naturality::Unknown),
-(("tallgrass", 36), Naturality::Unknown),
-(("tallgrass", 39), Naturality::Unknown),
-(("tallgrass", 7), Naturality::Unknown),
+(("sugar_cane", 16), Naturality::Natural),
+(("sugar_cane", 6), Naturality::Natural),
+(("sugar_cane", 7), Naturality::Natural),
+(("terracotta", 166), Naturality::Natural),
+(("terracotta", 167), Naturality::Natural),
+(("terracotta", 38), Naturality::Natural),
+(("terracotta", 39), Naturality::Natural),
 (("vine", 0), Naturality::Planted),
 (("vine", 129), Naturality::Planted),
 (("vine", 149), Naturality::Natural),
@@ -568,26 +1298,32 @@ lazy_static! {
 (("water", 33), Naturality::Unknown),
 (("water", 36), Naturality::Unknown),
 (("water", 37), Naturality::Unknown),
-(("waterlily", 0), Naturality::Unknown),
-(("waterlily", 16), Naturality::Unknown),
-(("waterlily", 6), Naturality::Natural),
-(("waterlily", 7), Naturality::Unknown),
-(("web", 4), Naturality::Natural),
-(("yellow_flower", 1), Naturality::Natural),
-(("yellow_flower", 129), Naturality::Natural),
-(("yellow_flower", 13), Naturality::Natural),
-(("yellow_flower", 133), Naturality::Unknown),
-(("yellow_flower", 149), Naturality::Unknown),
-(("yellow_flower", 157), Naturality::Unknown),
-(("yellow_flower", 158), Naturality::Unknown),
-(("yellow_flower", 163), Naturality::Natural),
-(("yellow_flower", 18), Naturality::Natural),
-(("yellow_flower", 20), Naturality::Natural),
-(("yellow_flower", 29), Naturality::Unknown),
-(("yellow_flower", 3), Naturality::Unknown),
-(("yellow_flower", 34), Naturality::Unknown),
-(("yellow_flower", 35), Naturality::Natural),
-(("yellow_flower", 4), Naturality::Unknown),
-(("yellow_flower", 5), Naturality::Natural),
+(("white_terracotta", 166), Naturality::Natural),
+(("white_terracotta", 167), Naturality::Natural),
+(("white_terracotta", 38), Naturality::Natural),
+(("white_terracotta", 39), Naturality::Natural),
+(("white_tulip", 0), Naturality::Built),
+(("white_tulip", 1), Naturality::Natural),
+(("white_tulip", 129), Naturality::Natural),
+(("white_tulip", 13), Naturality::Natural),
+(("white_tulip", 130), Naturality::Built),
+(("white_tulip", 132), Naturality::Natural),
+(("white_tulip", 157), Naturality::Natural),
+(("white_tulip", 166), Naturality::Built),
+(("white_tulip", 167), Naturality::Built),
+(("white_tulip", 18), Naturality::Natural),
+(("white_tulip", 2), Naturality::Built),
+(("white_tulip", 20), Naturality::Natural),
+(("white_tulip", 24), Naturality::Built),
+(("white_tulip", 29), Naturality::Natural),
+(("white_tulip", 3), Naturality::Natural),
+(("white_tulip", 38), Naturality::Built),
+(("white_tulip", 4), Naturality::Natural),
+(("white_tulip", 5), Naturality::Natural),
+(("white_tulip", 7), Naturality::Built),
+(("yellow_terracotta", 166), Naturality::Natural),
+(("yellow_terracotta", 167), Naturality::Natural),
+(("yellow_terracotta", 38), Naturality::Natural),
+(("yellow_terracotta", 39), Naturality::Natural),
     ].iter().map(|((n,b),c)| ((*n, *b), *c)));
 }
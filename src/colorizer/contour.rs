@@ -0,0 +1,118 @@
+use super::{
+    get_block_registry_option, get_color_option, get_ground_height, get_int_option, is_water,
+    Colorizer, ColorizerOptions, TileContext,
+};
+use crate::legend::Legend;
+use crate::mc::block_registry::BlockRegistry;
+use crate::TILE_WIDTH;
+
+const LAND_LINE_COLOR: u32 = 0xff_32_4a_6e; // #6e4a32 brown
+const WATER_LINE_COLOR: u32 = 0xff_80_30_10; // #103080 dark blue
+const MINOR_LINE_ALPHA: u32 = 0x88;
+
+/// Topographic contour lines (isohypses) of the ground height,
+/// following the ocean floor under water.
+/// Transparent between the lines, so it can be drawn over other modes.
+///
+/// Options:
+/// - `contour-interval`: height difference between lines, default 10
+/// - `contour-index`: every Nth line is an index line, drawn thicker and opaque, default 5
+/// - `contour-color`, `contour-water-color`: line color over land/water, as `#rrggbb`
+/// - `blocks`: blocks file, see `BlockRegistry::add_rules`
+pub struct ContourColorizer {
+    pub interval: i32,
+    pub index_every: i32,
+    pub land_color: u32,
+    pub water_color: u32,
+    pub blocks: BlockRegistry,
+}
+
+impl ContourColorizer {
+    pub fn new(options: &ColorizerOptions) -> Result<Self, String> {
+        let interval = get_int_option(options, "contour-interval", 10)?;
+        let index_every = get_int_option(options, "contour-index", 5)?;
+        if interval < 1 || index_every < 1 {
+            return Err("contour-interval and contour-index must be positive".to_string());
+        }
+        Ok(Self {
+            interval,
+            index_every,
+            land_color: get_color_option(options, "contour-color", LAND_LINE_COLOR)?,
+            water_color: get_color_option(options, "contour-water-color", WATER_LINE_COLOR)?,
+            blocks: get_block_registry_option(options)?,
+        })
+    }
+
+    fn get_level(&self, height: i32) -> i32 {
+        height.div_euclid(self.interval)
+    }
+
+    /// true if any line between the two levels is an index line
+    fn crosses_index_line(&self, low_level: i32, high_level: i32) -> bool {
+        let next_index_level = (low_level.div_euclid(self.index_every) + 1) * self.index_every;
+        next_index_level <= high_level
+    }
+}
+
+impl Colorizer for ContourColorizer {
+    fn needs_neighbors(&self) -> bool {
+        true
+    }
+
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        let tile = ctx.tile;
+        if tile.is_col_empty(column_nr) {
+            return 0;
+        }
+        let level = self.get_level(get_ground_height(tile, column_nr, &self.blocks));
+        let x = (column_nr % TILE_WIDTH) as i32;
+        let z = (column_nr / TILE_WIDTH) as i32;
+
+        // lines are drawn on the upper side of a level change,
+        // index lines additionally on the lower side, making them two pixels wide
+        let mut is_line = false;
+        let mut is_index_line = false;
+        for (dx, dz) in &[(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            let (other_tile, other_column_nr) = match ctx.column_at(x + dx, z + dz) {
+                Some(column) => column,
+                None => continue,
+            };
+            if other_tile.is_col_empty(other_column_nr) {
+                continue;
+            }
+            let other_level =
+                self.get_level(get_ground_height(other_tile, other_column_nr, &self.blocks));
+            if other_level < level {
+                is_line = true;
+                is_index_line |= self.crosses_index_line(other_level, level);
+            } else if other_level > level {
+                is_index_line |= self.crosses_index_line(level, other_level);
+            }
+        }
+
+        let color = if is_water(tile, column_nr, &self.blocks) {
+            self.water_color
+        } else {
+            self.land_color
+        };
+        if is_index_line {
+            color
+        } else if is_line {
+            color & 0x00_ff_ff_ff | MINOR_LINE_ALPHA << 24
+        } else {
+            0
+        }
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let minor_label = format!("Every {} blocks", self.interval);
+        let index_label = format!("Every {} blocks", self.interval * self.index_every);
+        let minor_alpha = MINOR_LINE_ALPHA << 24;
+        Some(
+            Legend::new("Contour lines")
+                .with_entry(&minor_label, self.land_color & 0x00_ff_ff_ff | minor_alpha)
+                .with_entry(&index_label, self.land_color)
+                .with_entry("Under water", self.water_color),
+        )
+    }
+}
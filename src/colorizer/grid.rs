@@ -0,0 +1,137 @@
+use super::{get_bool_option, Colorizer, ColorizerOptions, TileContext};
+use crate::draw::{blend_over, Canvas, LINE_HEIGHT};
+use crate::legend::Legend;
+use crate::{CHUNK_WIDTH, REGION_WIDTH, TILE_COLUMNS, TILE_HEIGHT, TILE_WIDTH};
+
+const CHUNK_LINE_COLOR: u32 = 0x30_00_00_00;
+const TILE_LINE_COLOR: u32 = 0x60_00_00_00;
+const REGION_LINE_COLOR: u32 = 0xa0_00_00_00;
+const LABEL_COLOR: u32 = 0xff_ff_ff_ff;
+const LABEL_OUTLINE_COLOR: u32 = 0xff_00_00_00;
+
+/// lines closer than this many pixels are left out
+const MIN_LINE_SPACING: i64 = 8;
+/// labeled lines are at least this many pixels apart
+const MIN_LABEL_SPACING: i64 = 128;
+
+/// The coarsest kind of line that passes through the pixel at this world coordinate, if any.
+fn get_line_color(world_coord: i64, blocks_per_pixel: i64) -> Option<u32> {
+    let line_kinds = [
+        (REGION_WIDTH as i64, REGION_LINE_COLOR),
+        (TILE_WIDTH as i64, TILE_LINE_COLOR),
+        (CHUNK_WIDTH as i64, CHUNK_LINE_COLOR),
+    ];
+    line_kinds
+        .iter()
+        .filter(|(spacing, _)| spacing / blocks_per_pixel >= MIN_LINE_SPACING)
+        .find(|(spacing, _)| world_coord.rem_euclid(*spacing) < blocks_per_pixel)
+        .map(|(_, color)| *color)
+}
+
+/// Draws chunk, tile and region grid lines onto a map image,
+/// labeling some of them with their world coordinates.
+/// The image's top left corner is at world coordinates `x0,z0`,
+/// and each pixel is `blocks_per_pixel` blocks wide (1 at zoom 0, 2 at zoom -1, ...).
+/// Lines too dense for the zoom level are skipped.
+pub fn draw_grid(canvas: &mut Canvas, x0: i64, z0: i64, blocks_per_pixel: i64, labels: bool) {
+    let (width, height) = (canvas.width as i64, canvas.height as i64);
+    for px in 0..width {
+        if let Some(color) = get_line_color(x0 + px * blocks_per_pixel, blocks_per_pixel) {
+            for py in 0..height {
+                canvas.blend_pixel(px as i32, py as i32, color);
+            }
+        }
+    }
+    for py in 0..height {
+        if let Some(color) = get_line_color(z0 + py * blocks_per_pixel, blocks_per_pixel) {
+            for px in 0..width {
+                canvas.blend_pixel(px as i32, py as i32, color);
+            }
+        }
+    }
+
+    if !labels {
+        return;
+    }
+    let mut label_spacing = TILE_WIDTH as i64;
+    while label_spacing / blocks_per_pixel < MIN_LABEL_SPACING {
+        label_spacing *= 2;
+    }
+    let label_at = |world_coord: i64| -> Option<i64> {
+        let offset = world_coord.rem_euclid(label_spacing);
+        if offset < blocks_per_pixel {
+            Some(world_coord - offset)
+        } else {
+            None
+        }
+    };
+    // x labels go one line lower, so they don't cover the z labels at intersections
+    for px in 0..width {
+        if let Some(x) = label_at(x0 + px * blocks_per_pixel) {
+            let label = format!("x {}", x);
+            let (lx, ly) = (px as i32 + 3, 3 + LINE_HEIGHT as i32);
+            canvas.draw_text_outlined(lx, ly, &label, LABEL_COLOR, LABEL_OUTLINE_COLOR);
+        }
+    }
+    for py in 0..height {
+        if let Some(z) = label_at(z0 + py * blocks_per_pixel) {
+            let label = format!("z {}", z);
+            let (lx, ly) = (3, py as i32 + 3);
+            canvas.draw_text_outlined(lx, ly, &label, LABEL_COLOR, LABEL_OUTLINE_COLOR);
+        }
+    }
+}
+
+/// Transparent except for the grid, meant as an overlay.
+///
+/// Options:
+/// - `grid-labels`: label the tile lines with world coordinates, default true
+pub struct GridColorizer {
+    pub labels: bool,
+}
+
+impl GridColorizer {
+    pub fn new(options: &ColorizerOptions) -> Result<Self, String> {
+        Ok(Self {
+            labels: get_bool_option(options, "grid-labels", true)?,
+        })
+    }
+}
+
+impl Colorizer for GridColorizer {
+    /// Only the lines, labels are drawn by `colorize_tile`.
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        let (tile_x, tile_z) = match ctx.tile.pos {
+            Some(pos) => pos,
+            None => return 0,
+        };
+        let x = tile_x as i64 * TILE_WIDTH as i64 + (column_nr % TILE_WIDTH) as i64;
+        let z = tile_z as i64 * TILE_HEIGHT as i64 + (column_nr / TILE_WIDTH) as i64;
+        // vertical lines first, like `draw_grid`
+        [get_line_color(x, 1), get_line_color(z, 1)]
+            .iter()
+            .filter_map(|color| *color)
+            .fold(0, blend_over)
+    }
+
+    fn colorize_tile(&self, ctx: &TileContext) -> Vec<u32> {
+        let mut pixbuf = vec![0; TILE_COLUMNS];
+        if let Some((tile_x, tile_z)) = ctx.tile.pos {
+            let x0 = tile_x as i64 * TILE_WIDTH as i64;
+            let z0 = tile_z as i64 * TILE_HEIGHT as i64;
+            let mut canvas = Canvas::new(&mut pixbuf, TILE_WIDTH, TILE_HEIGHT);
+            draw_grid(&mut canvas, x0, z0, 1, self.labels);
+        }
+        pixbuf
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let white = 0xff_ff_ff_ff;
+        Some(
+            Legend::new("Grid")
+                .with_entry("Chunk (16 blocks)", blend_over(white, CHUNK_LINE_COLOR))
+                .with_entry("Tile (256 blocks)", blend_over(white, TILE_LINE_COLOR))
+                .with_entry("Region (512 blocks)", blend_over(white, REGION_LINE_COLOR)),
+        )
+    }
+}
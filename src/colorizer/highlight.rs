@@ -0,0 +1,232 @@
+use super::{
+    colorize_simple, get_block_registry_option, get_int_option, Colorizer, ColorizerOptions,
+    TileContext,
+};
+use crate::legend::Legend;
+use crate::mc::block_registry::BlockRegistry;
+use crate::mc::blocks::BlockNamePattern;
+use crate::palette::parse_color;
+use crate::tile::{column_nr_of_pos, Tile};
+use crate::{TILE_COLUMNS, TILE_HEIGHT, TILE_WIDTH};
+
+const DEFAULT_HIGHLIGHT_COLOR: u32 = 0xff_00_00_ff; // #ff0000 red
+
+/// Highlights columns containing certain blocks in any of their four layers,
+/// everything else is drawn as a dimmed land/water map.
+///
+/// Options:
+/// - `highlight`: comma separated `pattern=#rrggbb` pairs, the color is optional.
+///   Patterns are matched against block names, and may contain `*`.
+///   Patterns without namespace match any namespace.
+///   Properties in brackets must match too, others are ignored.
+///   Example: `minecraft:beacon=#ff00ff,*_bed=#00ff00,chest,*[waterlogged=true]`
+/// - `highlight-radius`: draw each found block as a square of this radius, default 2
+/// - `blocks`: blocks file for the land/water map, see `BlockRegistry::add_rules`
+pub struct HighlightColorizer {
+    /// earlier patterns take precedence
+    pub patterns: Vec<BlockPattern>,
+    pub radius: i32,
+    pub blocks: BlockRegistry,
+}
+
+pub struct BlockPattern {
+    pub pattern: BlockNamePattern,
+    pub color: u32,
+}
+
+impl BlockPattern {
+    /// Accepts `pattern` or `pattern=#rrggbb`.
+    pub fn parse(pattern_str: &str, default_color: u32) -> Result<Self, String> {
+        let pattern_str = pattern_str.trim();
+        // the color comes after the properties, which contain `=` too
+        let properties_end = pattern_str.rfind("]").map_or(0, |i| i + 1);
+        let (pattern, color) = match pattern_str[properties_end..].find("=") {
+            Some(i) => (
+                &pattern_str[..properties_end + i],
+                Some(&pattern_str[properties_end + i + 1..]),
+            ),
+            None => (pattern_str, None),
+        };
+        let pattern = BlockNamePattern::new(pattern)?;
+        let color = match color {
+            Some(color_str) => parse_color(color_str)?,
+            None => default_color,
+        };
+        Ok(Self { pattern, color })
+    }
+}
+
+/// Splits at commas outside of brackets, which separate properties.
+fn split_patterns(patterns: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for (i, c) in patterns.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(&patterns[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(&patterns[start..]);
+    split
+}
+
+/// For each block id of a tile, the index of the first pattern matching it.
+type BlockMatches = Vec<Option<usize>>;
+
+impl HighlightColorizer {
+    pub fn new(options: &ColorizerOptions) -> Result<Self, String> {
+        let patterns = split_patterns(
+            options
+                .get("highlight")
+                .ok_or("highlight mode needs blocks to search for, as -o highlight=<patterns>")?,
+        )
+        .iter()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| BlockPattern::parse(s, DEFAULT_HIGHLIGHT_COLOR))
+        .collect::<Result<Vec<_>, _>>()?;
+        let radius = get_int_option(options, "highlight-radius", 2)?;
+        if !(0..=64).contains(&radius) {
+            return Err("highlight-radius must be between 0 and 64".to_string());
+        }
+        Ok(Self {
+            patterns,
+            radius,
+            blocks: get_block_registry_option(options)?,
+        })
+    }
+
+    /// The index of the first pattern matching the block.
+    fn get_block_match(&self, tile: &Tile, block_nr: u16) -> Option<usize> {
+        let block_state = tile.get_block_state(block_nr);
+        self.patterns
+            .iter()
+            .position(|p| p.pattern.matches(block_state))
+    }
+
+    fn get_block_matches(&self, tile: &Tile) -> BlockMatches {
+        (0..tile.names.len() as u16)
+            .map(|block_nr| self.get_block_match(tile, block_nr))
+            .collect()
+    }
+
+    /// `block_match` is `get_block_match`, or a lookup in the tile's `BlockMatches`.
+    fn get_column_match<F: Fn(u16) -> Option<usize>>(
+        &self,
+        tile: &Tile,
+        column_nr: usize,
+        block_match: F,
+    ) -> Option<usize> {
+        let steps_block_getters: [fn(&Tile, usize) -> u16; 4] = [
+            Tile::get_blockstate,
+            Tile::get_ocean_floor_blockstate,
+            Tile::get_transparent_blockstate,
+            Tile::get_foliage_blockstate,
+        ];
+        steps_block_getters
+            .iter()
+            .filter_map(|get_block_nr| {
+                let block_nr = get_block_nr(tile, column_nr);
+                if block_nr == 0 {
+                    return None;
+                }
+                block_match(block_nr)
+            })
+            .min()
+    }
+
+    fn get_background_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        let color = colorize_simple(ctx.tile, column_nr, &self.blocks);
+        if color == 0 {
+            return 0;
+        }
+        // darken to 40%
+        let mut dimmed = color & 0xff_00_00_00;
+        for shift in &[0, 8, 16] {
+            dimmed |= ((color >> shift & 0xff) * 2 / 5) << shift;
+        }
+        dimmed
+    }
+}
+
+impl Colorizer for HighlightColorizer {
+    fn needs_neighbors(&self) -> bool {
+        self.radius > 0
+    }
+
+    /// Only the column itself, without the radius of `colorize_tile`.
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        let block_match = |block_nr| self.get_block_match(ctx.tile, block_nr);
+        match self.get_column_match(ctx.tile, column_nr, block_match) {
+            Some(pattern_nr) => self.patterns[pattern_nr].color,
+            None => self.get_background_color(ctx, column_nr),
+        }
+    }
+
+    fn colorize_tile(&self, ctx: &TileContext) -> Vec<u32> {
+        // find all matches in this tile and along the borders of its neighbors
+        let w = TILE_WIDTH as i32;
+        let h = TILE_HEIGHT as i32;
+        let r = self.radius;
+        let mut found = Vec::new();
+        for tile_dz in -1..=1 {
+            for tile_dx in -1..=1 {
+                let tile = match ctx.neighbor(tile_dx, tile_dz) {
+                    Some(tile) => tile,
+                    None => continue,
+                };
+                let matches = self.get_block_matches(tile);
+                if matches.iter().all(|m| m.is_none()) {
+                    continue;
+                }
+                let block_match =
+                    |block_nr: u16| matches.get(block_nr as usize).cloned().unwrap_or(None);
+                // the part of this tile that is at most `r` away from the rendered tile
+                let x_range = (tile_dx * w).max(-r)..(tile_dx * w + w).min(w + r);
+                let z_range = (tile_dz * h).max(-r)..(tile_dz * h + h).min(h + r);
+                for z in z_range {
+                    for x in x_range.clone() {
+                        let column_nr = column_nr_of_pos(
+                            (x - tile_dx * w) as usize,
+                            (z - tile_dz * h) as usize,
+                        );
+                        if let Some(pattern_nr) =
+                            self.get_column_match(tile, column_nr, block_match)
+                        {
+                            found.push((pattern_nr, x, z));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut pixbuf: Vec<u32> = (0..TILE_COLUMNS)
+            .map(|column_nr| self.get_background_color(ctx, column_nr))
+            .collect();
+
+        // draw lower priority patterns first, so higher priority ones end up on top
+        found.sort_by_key(|&(pattern_nr, _, _)| std::cmp::Reverse(pattern_nr));
+        for (pattern_nr, x, z) in found {
+            let color = self.patterns[pattern_nr].color;
+            for pz in (z - r).max(0)..(z + r + 1).min(h) {
+                for px in (x - r).max(0)..(x + r + 1).min(w) {
+                    pixbuf[column_nr_of_pos(px as usize, pz as usize)] = color;
+                }
+            }
+        }
+        pixbuf
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let mut legend = Legend::new("Highlighted blocks");
+        for pattern in &self.patterns {
+            legend = legend.with_entry(pattern.pattern.as_str(), pattern.color);
+        }
+        Some(legend)
+    }
+}
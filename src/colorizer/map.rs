@@ -0,0 +1,115 @@
+use super::{
+    get_block_registry_option, get_surface_block_info, Colorizer, ColorizerOptions, TileContext,
+    S_LAND,
+};
+use crate::mc::block_registry::BlockRegistry;
+use crate::mc::blocks::BlockState;
+use crate::tile::Tile;
+use crate::TILE_WIDTH;
+
+/// brightness in 1/255, as on in-game maps
+const SHADE_DARK: u32 = 180;
+const SHADE_NORMAL: u32 = 220;
+const SHADE_BRIGHT: u32 = 255;
+
+/// Looks like an in-game map: each column gets the map color of its foliage or surface block,
+/// shaded by the height difference to its northern neighbor. Water gets darker with depth.
+///
+/// Options:
+/// - `blocks`: blocks file, see `BlockRegistry::add_rules` and `BlockInfo::map_color`
+pub struct MapColorizer {
+    pub blocks: BlockRegistry,
+    water_color: u32,
+}
+
+impl MapColorizer {
+    pub fn new(options: &ColorizerOptions) -> Result<Self, String> {
+        let blocks = get_block_registry_option(options)?;
+        let water_color = blocks
+            .get_block_info(BlockState::intern("minecraft:water[level=0]"))
+            .map_color;
+        Ok(Self {
+            blocks,
+            water_color,
+        })
+    }
+
+    /// Height of the top visible block, including foliage.
+    fn get_top_height(&self, tile: &Tile, column_nr: usize) -> i32 {
+        let foliage_nr = tile.get_foliage_blockstate(column_nr);
+        if foliage_nr != 0 {
+            tile.get_height(column_nr)
+                .max(tile.get_foliage_height(column_nr))
+        } else {
+            tile.get_height(column_nr)
+        }
+    }
+
+    fn get_base_color(&self, tile: &Tile, column_nr: usize) -> u32 {
+        let foliage_nr = tile.get_foliage_blockstate(column_nr);
+        if foliage_nr != 0 {
+            let foliage = self.blocks.get_block_info(tile.get_block_state(foliage_nr));
+            if foliage.map_color != 0 {
+                return foliage.map_color;
+            }
+        }
+        let surface = get_surface_block_info(tile, column_nr, &self.blocks);
+        if surface.map_color != 0 {
+            surface.map_color
+        } else {
+            S_LAND
+        }
+    }
+}
+
+impl Colorizer for MapColorizer {
+    fn needs_neighbors(&self) -> bool {
+        true
+    }
+
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        let tile = ctx.tile;
+        if tile.is_col_empty(column_nr) {
+            return 0;
+        }
+        if get_surface_block_info(tile, column_nr, &self.blocks).water && self.water_color != 0 {
+            let depth = tile.get_height(column_nr) - tile.get_ocean_floor_height(column_nr);
+            let shade = if depth < 3 {
+                SHADE_BRIGHT
+            } else if depth < 8 {
+                SHADE_NORMAL
+            } else {
+                SHADE_DARK
+            };
+            return shade_color(self.water_color, shade);
+        }
+
+        let x = (column_nr % TILE_WIDTH) as i32;
+        let z = (column_nr / TILE_WIDTH) as i32;
+        let height = self.get_top_height(tile, column_nr);
+        let shade = match ctx.column_at(x, z - 1) {
+            Some((north_tile, north_column_nr)) if !north_tile.is_col_empty(north_column_nr) => {
+                let north_height = self.get_top_height(north_tile, north_column_nr);
+                if height > north_height {
+                    SHADE_BRIGHT
+                } else if height < north_height {
+                    SHADE_DARK
+                } else {
+                    SHADE_NORMAL
+                }
+            }
+            _ => SHADE_NORMAL,
+        };
+        shade_color(self.get_base_color(tile, column_nr), shade)
+    }
+}
+
+/// Scales the color channels, keeping the alpha.
+fn shade_color(color: u32, shade: u32) -> u32 {
+    let mut shaded = color & 0xff_00_00_00;
+    for shift in &[0, 8, 16] {
+        let component = (color >> shift & 0xff) * shade / 255;
+        shaded |= component << shift;
+    }
+    shaded
+}
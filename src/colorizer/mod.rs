@@ -0,0 +1,526 @@
+pub mod contour;
+pub mod grid;
+pub mod highlight;
+pub mod map;
+pub mod slope;
+pub mod spawn;
+pub mod waypoints;
+
+use crate::ccnatural::{get_naturality_color, Naturality, NATURALITY_VALUES};
+use crate::legend::Legend;
+use crate::mc::biomes::BIOMES;
+use crate::mc::block_registry::{BlockInfo, BlockRegistry};
+use crate::naturality::NaturalityRules;
+use crate::palette::{parse_color, read_palette, Gradient, Palette};
+use crate::tile::{column_nr_of_pos, KeysMap, NamesVec, Tile};
+use crate::{TILE_COLUMNS, TILE_HEIGHT, TILE_WIDTH};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+pub fn colorize_biome(tile: &Tile, column_nr: usize) -> u32 {
+    if tile.is_col_empty(column_nr) {
+        return 0;
+    }
+    tile.get_biome(column_nr)
+        .map_or(UNKNOWN_BIOME_COLOR, |biome| biome.color)
+}
+
+pub fn colorize_naturality(tile: &Tile, column_nr: usize, rules: &NaturalityRules) -> u32 {
+    if tile.is_col_empty(column_nr) {
+        return 0;
+    }
+    get_column_naturality(tile, column_nr, rules)
+        .map(|n| get_naturality_color(&n))
+        .unwrap_or(0)
+}
+
+/// The least natural classification of the column's blocks,
+/// `None` if the column is empty or only contains air.
+pub fn get_column_naturality(
+    tile: &Tile,
+    column_nr: usize,
+    rules: &NaturalityRules,
+) -> Option<Naturality> {
+    if tile.is_col_empty(column_nr) {
+        return None;
+    }
+    let biome = tile.get_biome(column_nr);
+
+    let mut final_naturality = None;
+
+    let steps_block_getters: Vec<fn(&Tile, usize) -> u16> = vec![
+        Tile::get_blockstate,
+        Tile::get_ocean_floor_blockstate,
+        Tile::get_transparent_blockstate,
+        Tile::get_foliage_blockstate,
+    ];
+    for get_block_nr in steps_block_getters {
+        let block_nr = get_block_nr(tile, column_nr);
+        if block_nr != 0 {
+            let block_state = tile.get_block_state(block_nr);
+            if block_state.is_unknown() || block_state.name() == "air" {
+                continue;
+            }
+            let block_naturality = rules.get_naturality(block_state, biome);
+            if final_naturality.is_none() || final_naturality.unwrap() < block_naturality {
+                final_naturality = Some(block_naturality);
+            }
+        }
+    }
+
+    final_naturality
+}
+
+/// for modded biomes and unknown ids
+pub const UNKNOWN_BIOME_COLOR: u32 = 0x88ff00ff;
+
+pub const S_WATER: u32 = 0xff_ff_c5_a6; // #a6c5ff
+pub const S_LAND: u32 = 0xff_dc_e4_e7; // #e7e4dc
+
+pub fn colorize_simple(tile: &Tile, column_nr: usize, blocks: &BlockRegistry) -> u32 {
+    if tile.is_col_empty(column_nr) {
+        return 0;
+    }
+    if is_water(tile, column_nr, blocks) {
+        return S_WATER;
+    }
+    S_LAND
+}
+
+pub fn colorize_light(tile: &Tile, column_nr: usize, palette: &Palette) -> u32 {
+    if tile.is_col_empty(column_nr) {
+        return 0;
+    }
+    let bl = tile.get_light(column_nr) & 0xf;
+    palette.light.color_at(bl as i32)
+}
+
+pub fn colorize_height_bw(tile: &Tile, column_nr: usize) -> u32 {
+    if tile.is_col_empty(column_nr) {
+        return 0;
+    }
+    // heights outside 0..=255 (1.18+) are clamped
+    let h = tile.get_height(column_nr).clamp(0, 255) as u8;
+    rgb(h, h, h)
+}
+
+pub fn colorize_height(
+    tile: &Tile,
+    column_nr: usize,
+    palette: &Palette,
+    blocks: &BlockRegistry,
+) -> u32 {
+    if tile.is_col_empty(column_nr) {
+        return 0; // unpopulated
+    }
+    if is_water(tile, column_nr, blocks) {
+        get_sea_color(palette, tile.get_ocean_floor_height(column_nr))
+    } else {
+        get_land_color(palette, tile.get_height(column_nr))
+    }
+}
+
+pub fn get_sea_color(palette: &Palette, ocean_floor_height: i32) -> u32 {
+    palette.sea.color_at(ocean_floor_height)
+}
+
+pub fn get_land_color(palette: &Palette, surface_height: i32) -> u32 {
+    palette.land.color_at(surface_height)
+}
+
+/// Height of the ground visible from above, through any water.
+pub fn get_ground_height(tile: &Tile, column_nr: usize, blocks: &BlockRegistry) -> i32 {
+    if is_water(tile, column_nr, blocks) {
+        tile.get_ocean_floor_height(column_nr)
+    } else {
+        tile.get_height(column_nr)
+    }
+}
+
+/// Also true for flowing water, waterlogged blocks, kelp and so on, see `BlockInfo::water`.
+pub fn is_water(tile: &Tile, column_nr: usize, blocks: &BlockRegistry) -> bool {
+    get_surface_block_info(tile, column_nr, blocks).water
+}
+
+pub fn get_surface_block_info(tile: &Tile, column_nr: usize, blocks: &BlockRegistry) -> BlockInfo {
+    blocks.get_block_info(tile.get_block_state(tile.get_blockstate(column_nr)))
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> u32 {
+    0xff000000 | ((b as u32) << 16) | ((g as u32) << 8) | (r as u32)
+}
+
+/// Per-user configuration for colorizers, `key=value` pairs from the command line.
+pub type ColorizerOptions = HashMap<String, String>;
+
+/// Everything a colorizer may look at while colorizing one tile.
+pub struct TileContext<'a> {
+    pub tile: &'a Tile,
+    /// the 3x3 tiles centered on `tile`, see `neighbor()`
+    pub neighbors: [Option<&'a Tile>; 9],
+    /// global block ids by name, the same across all tiles, see `render::build_global_keys_map`
+    pub global_keys: &'a KeysMap,
+    pub global_names: &'a NamesVec,
+    pub options: &'a ColorizerOptions,
+}
+
+impl<'a> TileContext<'a> {
+    pub fn new(
+        tile: &'a Tile,
+        global_keys: &'a KeysMap,
+        global_names: &'a NamesVec,
+        options: &'a ColorizerOptions,
+    ) -> Self {
+        let mut neighbors = [None; 9];
+        neighbors[4] = Some(tile);
+        Self {
+            tile,
+            neighbors,
+            global_keys,
+            global_names,
+            options,
+        }
+    }
+
+    /// `dx` and `dz` are in -1..=1, `(0, 0)` is the tile itself.
+    /// `None` if there is no such tile or it was not loaded.
+    pub fn neighbor(&self, dx: i32, dz: i32) -> Option<&'a Tile> {
+        if !(-1..=1).contains(&dx) || !(-1..=1).contains(&dz) {
+            return None;
+        }
+        self.neighbors[((dz + 1) * 3 + dx + 1) as usize]
+    }
+
+    pub fn set_neighbor(&mut self, dx: i32, dz: i32, tile: Option<&'a Tile>) {
+        self.neighbors[((dz + 1) * 3 + dx + 1) as usize] = tile;
+    }
+
+    /// Finds the column at `x,z` relative to the tile's north-west corner.
+    /// Coordinates outside the tile reach into the neighboring tiles.
+    pub fn column_at(&self, x: i32, z: i32) -> Option<(&'a Tile, usize)> {
+        let w = TILE_WIDTH as i32;
+        let h = TILE_HEIGHT as i32;
+        let tile = self.neighbor(x.div_euclid(w), z.div_euclid(h))?;
+        let column_nr = column_nr_of_pos(x.rem_euclid(w) as usize, z.rem_euclid(h) as usize);
+        Some((tile, column_nr))
+    }
+
+    pub fn get_option(&self, key: &str) -> Option<&'a str> {
+        self.options.get(key).map(|s| s.as_str())
+    }
+}
+
+pub trait Colorizer: Send + Sync {
+    /// If true, the renderer loads the 8 surrounding tiles into the context.
+    fn needs_neighbors(&self) -> bool {
+        false
+    }
+
+    /// ARGB (little endian: 0xAABBGGRR), 0 for transparent
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32;
+
+    /// What the colors mean, for `palette` to draw. `None` if not applicable.
+    fn legend(&self) -> Option<Legend> {
+        None
+    }
+
+    /// Anything noteworthy seen while colorizing, for `render` to print when done.
+    fn report(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// All columns of `ctx.tile`, this is what `render` calls.
+    /// Colorizers drawing shapes across columns, such as labels, override this instead.
+    fn colorize_tile(&self, ctx: &TileContext) -> Vec<u32> {
+        (0..TILE_COLUMNS)
+            .map(|column_nr| self.get_column_color(ctx, column_nr))
+            .collect()
+    }
+}
+
+pub struct BiomeColorizer;
+pub struct HeightColorizer {
+    pub palette: Palette,
+    pub blocks: BlockRegistry,
+}
+pub struct HeightBWColorizer;
+pub struct LightColorizer {
+    pub palette: Palette,
+}
+pub struct NaturalityColorizer {
+    pub rules: NaturalityRules,
+}
+pub struct SimpleColorizer {
+    pub blocks: BlockRegistry,
+}
+
+impl Colorizer for BiomeColorizer {
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        colorize_biome(ctx.tile, column_nr)
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let mut legend = Legend::new("Biome");
+        for biome in BIOMES.iter() {
+            let label = match biome.legacy_id {
+                Some(biome_id) => format!("{} ({})", biome.name, biome_id),
+                None => biome.name.to_string(),
+            };
+            legend = legend.with_entry(&label, biome.color);
+        }
+        Some(legend.with_entry("Unknown", UNKNOWN_BIOME_COLOR))
+    }
+}
+
+impl Colorizer for HeightColorizer {
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        colorize_height(ctx.tile, column_nr, &self.palette, &self.blocks)
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        Some(
+            Legend::new("Height")
+                .with_gradient("Land surface height", &self.palette.land)
+                .with_gradient("Sea floor height", &self.palette.sea),
+        )
+    }
+}
+
+impl Colorizer for HeightBWColorizer {
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        colorize_height_bw(ctx.tile, column_nr)
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let gradient = Gradient::new(vec![(0, rgb(0, 0, 0)), (255, rgb(255, 255, 255))])
+            .expect("gradient has stops");
+        Some(Legend::new("Height (grayscale)").with_gradient("Surface height", &gradient))
+    }
+}
+
+impl Colorizer for LightColorizer {
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        colorize_light(ctx.tile, column_nr, &self.palette)
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        Some(Legend::new("Light").with_gradient("Block light level", &self.palette.light))
+    }
+}
+
+impl Colorizer for NaturalityColorizer {
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        colorize_naturality(ctx.tile, column_nr, &self.rules)
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let mut legend = Legend::new("Naturality");
+        for naturality in NATURALITY_VALUES.iter() {
+            let label = format!("{:?}", naturality);
+            legend = legend.with_entry(&label, get_naturality_color(naturality));
+        }
+        Some(legend)
+    }
+
+    fn report(&self) -> Vec<String> {
+        self.rules
+            .unclassified_blocks()
+            .iter()
+            .map(|(block_name, count)| {
+                format!("No naturality rule for {} ({} times)", block_name, count)
+            })
+            .collect()
+    }
+}
+
+impl Colorizer for SimpleColorizer {
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        colorize_simple(ctx.tile, column_nr, &self.blocks)
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        Some(
+            Legend::new("Land and water")
+                .with_entry("Water", S_WATER)
+                .with_entry("Land", S_LAND),
+        )
+    }
+}
+
+pub type ColorizerFactory =
+    Box<dyn Fn(&ColorizerOptions) -> Result<Box<dyn Colorizer>, String> + Send + Sync>;
+
+/// Looks up colorizers by name, so `render` can offer custom ones too.
+pub struct ColorizerRegistry {
+    factories: BTreeMap<String, ColorizerFactory>,
+}
+
+impl Default for ColorizerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorizerRegistry {
+    pub fn new() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register("biome", |_| Ok(Box::new(BiomeColorizer)));
+        registry.register("height", |options| {
+            Ok(Box::new(HeightColorizer {
+                palette: get_palette_option(options)?,
+                blocks: get_block_registry_option(options)?,
+            }))
+        });
+        registry.register("contour", |options| {
+            Ok(Box::new(contour::ContourColorizer::new(options)?))
+        });
+        registry.register("grid", |options| {
+            Ok(Box::new(grid::GridColorizer::new(options)?))
+        });
+        registry.register("height-bw", |_| Ok(Box::new(HeightBWColorizer)));
+        registry.register("highlight", |options| {
+            Ok(Box::new(highlight::HighlightColorizer::new(options)?))
+        });
+        registry.register("light", |options| {
+            let palette = get_palette_option(options)?;
+            Ok(Box::new(LightColorizer { palette }))
+        });
+        registry.register("map", |options| {
+            Ok(Box::new(map::MapColorizer::new(options)?))
+        });
+        registry.register("naturality", |options| {
+            Ok(Box::new(NaturalityColorizer {
+                rules: get_naturality_rules_option(options)?,
+            }))
+        });
+        registry.register("simple", |options| {
+            Ok(Box::new(SimpleColorizer {
+                blocks: get_block_registry_option(options)?,
+            }))
+        });
+        registry.register("spawn", |options| {
+            Ok(Box::new(spawn::SpawnColorizer::new(options)?))
+        });
+        registry.register("slope", |options| {
+            Ok(Box::new(slope::SlopeColorizer::new(options)?))
+        });
+        registry.register("waypoints", |options| {
+            Ok(Box::new(waypoints::WaypointColorizer::new(options)?))
+        });
+        registry
+    }
+
+    /// Replaces any colorizer previously registered under that name.
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&ColorizerOptions) -> Result<Box<dyn Colorizer>, String> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(|k| k.as_str()).collect()
+    }
+
+    pub fn create(
+        &self,
+        name: &str,
+        options: &ColorizerOptions,
+    ) -> Result<Box<dyn Colorizer>, String> {
+        let factory = self.factories.get(name).ok_or_else(|| {
+            format!(
+                "Unknown colorizer {:?}, available: {}",
+                name,
+                self.names().join(", ")
+            )
+        })?;
+        factory(options)
+    }
+}
+
+/// Reads the palette file given as `palette=<path>`, or uses the default palette.
+pub fn get_palette_option(options: &ColorizerOptions) -> Result<Palette, String> {
+    match options.get("palette") {
+        Some(path) => read_palette(Path::new(path)),
+        None => Ok(Palette::default()),
+    }
+}
+
+/// The built-in block rules, followed by those of the blocks file given as `blocks=<path>`,
+/// see `BlockRegistry::add_rules`.
+pub fn get_block_registry_option(options: &ColorizerOptions) -> Result<BlockRegistry, String> {
+    let mut blocks = BlockRegistry::new();
+    if let Some(path) = options.get("blocks") {
+        blocks.read(Path::new(path))?;
+    }
+    Ok(blocks)
+}
+
+/// The rules file given as `naturality=<path>` (TSV or JSON) on top of the compiled tables,
+/// see `NaturalityRules`.
+pub fn get_naturality_rules_option(options: &ColorizerOptions) -> Result<NaturalityRules, String> {
+    let mut rules = NaturalityRules::new();
+    if let Some(path) = options.get("naturality") {
+        rules.read(Path::new(path))?;
+    }
+    Ok(rules)
+}
+
+pub fn get_int_option(options: &ColorizerOptions, key: &str, default: i32) -> Result<i32, String> {
+    match options.get(key) {
+        Some(value) => value
+            .parse()
+            .map_err(|e| format!("Invalid option {}={} {}", key, value, e)),
+        None => Ok(default),
+    }
+}
+
+/// `true`/`false`, `yes`/`no` or `1`/`0`, a bare `key` means `true`.
+pub fn get_bool_option(
+    options: &ColorizerOptions,
+    key: &str,
+    default: bool,
+) -> Result<bool, String> {
+    match options.get(key).map(|s| s.as_str()) {
+        Some("true") | Some("yes") | Some("1") => Ok(true),
+        Some("false") | Some("no") | Some("0") => Ok(false),
+        Some(value) => Err(format!(
+            "Invalid option {}={}, should be true or false",
+            key, value
+        )),
+        None => Ok(default),
+    }
+}
+
+/// Colors are given as `#rrggbb` or `#rrggbbaa`.
+pub fn get_color_option(
+    options: &ColorizerOptions,
+    key: &str,
+    default: u32,
+) -> Result<u32, String> {
+    match options.get(key) {
+        Some(value) => parse_color(value).map_err(|e| format!("Invalid option {}: {}", key, e)),
+        None => Ok(default),
+    }
+}
+
+/// Parses `key=value` pairs, a bare `key` is set to `true`.
+pub fn parse_colorizer_options(pairs: &[String]) -> Result<ColorizerOptions, String> {
+    let mut options = ColorizerOptions::new();
+    for pair in pairs {
+        let mut split = pair.splitn(2, "=");
+        let key = split.next().unwrap_or("");
+        if key.is_empty() {
+            return Err(format!("Invalid option {:?}, should be: key=value", pair));
+        }
+        let value = split.next().unwrap_or("true");
+        options.insert(key.to_string(), value.to_string());
+    }
+    Ok(options)
+}
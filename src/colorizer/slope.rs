@@ -0,0 +1,95 @@
+use super::{
+    get_block_registry_option, get_bool_option, get_ground_height, get_int_option,
+    get_palette_option, is_water, Colorizer, ColorizerOptions, TileContext, S_WATER,
+};
+use crate::legend::Legend;
+use crate::mc::block_registry::BlockRegistry;
+use crate::palette::Gradient;
+use crate::TILE_WIDTH;
+
+/// Steepness of the ground, for finding flat building land.
+/// Colored by the `slope` gradient of the palette, in percent (100 = 45 degrees).
+///
+/// Options:
+/// - `palette`: palette file, see `palette.rs`
+/// - `slope-mask-water`: draw water in a flat water color instead of the ocean floor slope
+/// - `slope-radius`: compare heights this many blocks away, larger values smooth out
+///   the stair steps of gentle slopes, default 2
+/// - `blocks`: blocks file, see `BlockRegistry::add_rules`
+pub struct SlopeColorizer {
+    pub gradient: Gradient,
+    pub mask_water: bool,
+    pub radius: i32,
+    pub blocks: BlockRegistry,
+}
+
+impl SlopeColorizer {
+    pub fn new(options: &ColorizerOptions) -> Result<Self, String> {
+        let radius = get_int_option(options, "slope-radius", 2)?;
+        if !(1..=16).contains(&radius) {
+            return Err("slope-radius must be between 1 and 16".to_string());
+        }
+        Ok(Self {
+            gradient: get_palette_option(options)?.slope,
+            mask_water: get_bool_option(options, "slope-mask-water", false)?,
+            radius,
+            blocks: get_block_registry_option(options)?,
+        })
+    }
+}
+
+/// Height difference per block along one axis, from the columns `dx,dz` away on either side.
+/// Uses a one-sided difference if only one of them is known.
+fn get_height_delta(
+    ctx: &TileContext,
+    blocks: &BlockRegistry,
+    x: i32,
+    z: i32,
+    dx: i32,
+    dz: i32,
+    center: i32,
+) -> f32 {
+    let distance = (dx + dz).abs() as f32;
+    let get_height = |x, z| {
+        ctx.column_at(x, z)
+            .filter(|(tile, column_nr)| !tile.is_col_empty(*column_nr))
+            .map(|(tile, column_nr)| get_ground_height(tile, column_nr, blocks))
+    };
+    match (get_height(x - dx, z - dz), get_height(x + dx, z + dz)) {
+        (Some(before), Some(after)) => (after - before) as f32 / (2.0 * distance),
+        (Some(before), None) => (center - before) as f32 / distance,
+        (None, Some(after)) => (after - center) as f32 / distance,
+        (None, None) => 0.0,
+    }
+}
+
+impl Colorizer for SlopeColorizer {
+    fn needs_neighbors(&self) -> bool {
+        true
+    }
+
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        let tile = ctx.tile;
+        if tile.is_col_empty(column_nr) {
+            return 0;
+        }
+        if self.mask_water && is_water(tile, column_nr, &self.blocks) {
+            return S_WATER;
+        }
+        let x = (column_nr % TILE_WIDTH) as i32;
+        let z = (column_nr / TILE_WIDTH) as i32;
+        let center = get_ground_height(tile, column_nr, &self.blocks);
+        let delta_x = get_height_delta(ctx, &self.blocks, x, z, self.radius, 0, center);
+        let delta_z = get_height_delta(ctx, &self.blocks, x, z, 0, self.radius, center);
+        let slope_percent = (delta_x * delta_x + delta_z * delta_z).sqrt() * 100.0;
+        self.gradient.color_at(slope_percent.round() as i32)
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let mut legend = Legend::new("Slope").with_gradient("Slope in percent", &self.gradient);
+        if self.mask_water {
+            legend = legend.with_entry("Water", S_WATER);
+        }
+        Some(legend)
+    }
+}
@@ -0,0 +1,90 @@
+use super::{
+    get_block_registry_option, get_bool_option, get_int_option, is_water, Colorizer,
+    ColorizerOptions, TileContext, S_WATER,
+};
+use crate::legend::Legend;
+use crate::mc::block_registry::BlockRegistry;
+use crate::tile::Tile;
+
+const LIT_COLOR: u32 = 0xff_8f_d1_8f; // #8fd18f light green
+const SPAWNABLE_COLOR: u32 = 0xff_30_30_e0; // #e03030 red
+const UNSPAWNABLE_COLOR: u32 = 0xff_90_90_90; // #909090 gray
+
+/// sky light is reduced by this much at night
+const NIGHT_SKY_DARKENING: u8 = 11;
+
+/// Where hostile mobs can spawn on the surface: lit, dark and spawnable, or unspawnable.
+///
+/// Options:
+/// - `spawn-max-light`: mobs spawn at this light level or darker, default 7
+/// - `spawn-skylight`: also count sky light, as it is at night
+/// - `blocks`: blocks file, see `BlockRegistry::add_rules` and `BlockInfo::spawnable`
+pub struct SpawnColorizer {
+    pub max_light: u8,
+    pub use_skylight: bool,
+    pub blocks: BlockRegistry,
+}
+
+impl SpawnColorizer {
+    pub fn new(options: &ColorizerOptions) -> Result<Self, String> {
+        let max_light = get_int_option(options, "spawn-max-light", 7)?;
+        if !(0..=15).contains(&max_light) {
+            return Err("spawn-max-light must be between 0 and 15".to_string());
+        }
+        Ok(Self {
+            max_light: max_light as u8,
+            use_skylight: get_bool_option(options, "spawn-skylight", false)?,
+            blocks: get_block_registry_option(options)?,
+        })
+    }
+
+    /// Light level where a mob would spawn, in the block above the surface.
+    fn get_spawn_light(&self, tile: &Tile, column_nr: usize) -> u8 {
+        let surface_light = tile.get_light(column_nr);
+        let foliage_light = tile.get_foliage_light(column_nr);
+        let mut light = (surface_light & 0xf).max(foliage_light & 0xf);
+        if self.use_skylight {
+            let sky_light = (surface_light >> 4).max(foliage_light >> 4);
+            light = light.max(sky_light.saturating_sub(NIGHT_SKY_DARKENING));
+        }
+        light
+    }
+}
+
+impl Colorizer for SpawnColorizer {
+    fn get_column_color(&self, ctx: &TileContext, column_nr: usize) -> u32 {
+        let tile = ctx.tile;
+        if tile.is_col_empty(column_nr) {
+            return 0;
+        }
+        if is_water(tile, column_nr, &self.blocks) {
+            return S_WATER;
+        }
+        let surface_block = tile.get_block_state(tile.get_blockstate(column_nr));
+        let foliage_nr = tile.get_foliage_blockstate(column_nr);
+        let blocked_by_foliage = foliage_nr != 0
+            && !self
+                .blocks
+                .get_block_info(tile.get_block_state(foliage_nr))
+                .spawnable;
+        if !self.blocks.get_block_info(surface_block).spawnable || blocked_by_foliage {
+            return UNSPAWNABLE_COLOR;
+        }
+        if self.get_spawn_light(tile, column_nr) > self.max_light {
+            LIT_COLOR
+        } else {
+            SPAWNABLE_COLOR
+        }
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let lit_label = format!("Lit (light above {})", self.max_light);
+        Some(
+            Legend::new("Mob spawning")
+                .with_entry(&lit_label, LIT_COLOR)
+                .with_entry("Dark, mobs can spawn", SPAWNABLE_COLOR)
+                .with_entry("Mobs can't spawn", UNSPAWNABLE_COLOR)
+                .with_entry("Water", S_WATER),
+        )
+    }
+}
@@ -0,0 +1,113 @@
+use super::{get_bool_option, Colorizer, ColorizerOptions, TileContext};
+use crate::draw::{text_width, Canvas, GLYPH_HEIGHT};
+use crate::legend::Legend;
+use crate::waypoint::{read_waypoints, Waypoint, OVERWORLD};
+use crate::{TILE_COLUMNS, TILE_HEIGHT, TILE_WIDTH};
+use std::path::Path;
+
+const MARKER_RADIUS: i32 = 2;
+const MARKER_OUTLINE_COLOR: u32 = 0xff_00_00_00;
+const LABEL_COLOR: u32 = 0xff_ff_ff_ff;
+const LABEL_OUTLINE_COLOR: u32 = 0xff_00_00_00;
+
+/// Transparent except for a marker at each waypoint, meant as an overlay.
+/// Only works per tile, as markers and labels span many columns.
+///
+/// Options:
+/// - `waypoints`: a VoxelMap `.points` file, or a JSON file as exported by `waypoints`
+/// - `waypoint-dimension`: only draw waypoints in this dimension, default overworld
+/// - `waypoint-labels`: write the name next to each marker, default true
+pub struct WaypointColorizer {
+    /// only enabled ones in the selected dimension
+    pub waypoints: Vec<Waypoint>,
+    pub labels: bool,
+}
+
+impl WaypointColorizer {
+    pub fn new(options: &ColorizerOptions) -> Result<Self, String> {
+        let path = options
+            .get("waypoints")
+            .ok_or("waypoints mode needs a waypoints file, as -o waypoints=<file>")?;
+        let dimension = options
+            .get("waypoint-dimension")
+            .map(|s| s.as_str())
+            .unwrap_or(OVERWORLD);
+        let waypoints = read_waypoints(Path::new(path))
+            .map_err(|e| format!("Reading waypoints {}: {}", path, e))?
+            .into_iter()
+            .filter(|waypoint| waypoint.enabled && waypoint.is_in_dimension(dimension))
+            .collect();
+        Ok(Self {
+            waypoints,
+            labels: get_bool_option(options, "waypoint-labels", true)?,
+        })
+    }
+
+    fn draw_waypoint(&self, canvas: &mut Canvas, x: i32, z: i32, waypoint: &Waypoint) {
+        let r = MARKER_RADIUS;
+        let size = (2 * r + 1) as usize;
+        canvas.fill_rect(
+            x - r - 1,
+            z - r - 1,
+            size + 2,
+            size + 2,
+            MARKER_OUTLINE_COLOR,
+        );
+        canvas.fill_rect(x - r, z - r, size, size, waypoint.color);
+        if self.labels {
+            let label_x = x + r + 3;
+            let label_z = z - GLYPH_HEIGHT as i32 / 2;
+            canvas.draw_text_outlined(
+                label_x,
+                label_z,
+                &waypoint.name,
+                LABEL_COLOR,
+                LABEL_OUTLINE_COLOR,
+            );
+        }
+    }
+}
+
+impl Colorizer for WaypointColorizer {
+    fn get_column_color(&self, _ctx: &TileContext, _column_nr: usize) -> u32 {
+        unreachable!("waypoints are only drawn by colorize_tile")
+    }
+
+    fn colorize_tile(&self, ctx: &TileContext) -> Vec<u32> {
+        let mut pixbuf = vec![0; TILE_COLUMNS];
+        let (tile_x, tile_z) = match ctx.tile.pos {
+            Some(pos) => pos,
+            None => return pixbuf,
+        };
+        let x0 = tile_x * TILE_WIDTH as i32;
+        let z0 = tile_z * TILE_HEIGHT as i32;
+        let mut canvas = Canvas::new(&mut pixbuf, TILE_WIDTH, TILE_HEIGHT);
+        // markers and labels near the tile border reach into this tile too
+        let margin = MARKER_RADIUS + 1 + GLYPH_HEIGHT as i32;
+        for waypoint in &self.waypoints {
+            let (x, z) = (waypoint.x - x0, waypoint.z - z0);
+            let label_width = if self.labels {
+                text_width(&waypoint.name) as i32 + 4
+            } else {
+                0
+            };
+            if x < -margin - label_width
+                || x >= TILE_WIDTH as i32 + margin
+                || z < -margin
+                || z >= TILE_HEIGHT as i32 + margin
+            {
+                continue;
+            }
+            self.draw_waypoint(&mut canvas, x, z, waypoint);
+        }
+        pixbuf
+    }
+
+    fn legend(&self) -> Option<Legend> {
+        let mut legend = Legend::new("Waypoints");
+        for waypoint in &self.waypoints {
+            legend = legend.with_entry(&waypoint.name, waypoint.color);
+        }
+        Some(legend)
+    }
+}
@@ -0,0 +1,262 @@
+pub const GLYPH_WIDTH: usize = 5;
+pub const GLYPH_HEIGHT: usize = 7;
+/// horizontal distance between the start of two consecutive characters
+pub const CHAR_ADVANCE: usize = GLYPH_WIDTH + 1;
+pub const LINE_HEIGHT: usize = GLYPH_HEIGHT + 3;
+
+/// 5x7 pixel font for ASCII 0x20-0x7e, one byte per column, lowest bit at the top
+const FONT_5X7: [[u8; GLYPH_WIDTH]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x00, 0x00, 0x5f, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7f, 0x14, 0x7f, 0x14], // #
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1c, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1c, 0x00], // )
+    [0x08, 0x2a, 0x1c, 0x2a, 0x08], // *
+    [0x08, 0x08, 0x3e, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3e, 0x51, 0x49, 0x45, 0x3e], // 0
+    [0x00, 0x42, 0x7f, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4b, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7f, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3c, 0x4a, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1e], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3e], // @
+    [0x7e, 0x11, 0x11, 0x11, 0x7e], // A
+    [0x7f, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3e, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7f, 0x41, 0x41, 0x22, 0x1c], // D
+    [0x7f, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7f, 0x09, 0x09, 0x09, 0x01], // F
+    [0x3e, 0x41, 0x49, 0x49, 0x7a], // G
+    [0x7f, 0x08, 0x08, 0x08, 0x7f], // H
+    [0x00, 0x41, 0x7f, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3f, 0x01], // J
+    [0x7f, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7f, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7f, 0x02, 0x0c, 0x02, 0x7f], // M
+    [0x7f, 0x04, 0x08, 0x10, 0x7f], // N
+    [0x3e, 0x41, 0x41, 0x41, 0x3e], // O
+    [0x7f, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3e, 0x41, 0x51, 0x21, 0x5e], // Q
+    [0x7f, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7f, 0x01, 0x01], // T
+    [0x3f, 0x40, 0x40, 0x40, 0x3f], // U
+    [0x1f, 0x20, 0x40, 0x20, 0x1f], // V
+    [0x3f, 0x40, 0x38, 0x40, 0x3f], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x07, 0x08, 0x70, 0x08, 0x07], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7f, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // backslash
+    [0x00, 0x41, 0x41, 0x7f, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7f, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7f], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7e, 0x09, 0x01, 0x02], // f
+    [0x0c, 0x52, 0x52, 0x52, 0x3e], // g
+    [0x7f, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7d, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3d, 0x00], // j
+    [0x7f, 0x10, 0x28, 0x44, 0x00], // k
+    [0x00, 0x41, 0x7f, 0x40, 0x00], // l
+    [0x7c, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7c, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7c, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7c], // q
+    [0x7c, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3f, 0x44, 0x40, 0x20], // t
+    [0x3c, 0x40, 0x40, 0x20, 0x7c], // u
+    [0x1c, 0x20, 0x40, 0x20, 0x1c], // v
+    [0x3c, 0x40, 0x30, 0x40, 0x3c], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0c, 0x50, 0x50, 0x50, 0x3c], // y
+    [0x44, 0x64, 0x54, 0x4c, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7f, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x08, 0x10, 0x08], // ~
+];
+
+/// A mutable view of a row-major pixel buffer.
+pub struct Canvas<'a> {
+    pub pixels: &'a mut [u32],
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<'a> Canvas<'a> {
+    pub fn new(pixels: &'a mut [u32], width: usize, height: usize) -> Self {
+        assert_eq!(pixels.len(), width * height);
+        Self {
+            pixels,
+            width,
+            height,
+        }
+    }
+
+    /// Pixels outside the canvas are ignored.
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: u32) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        self.pixels[x as usize + y as usize * self.width] = color;
+    }
+
+    /// Like `set_pixel`, but alpha-composites `color` onto the existing pixel.
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: u32) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        let pixel = &mut self.pixels[x as usize + y as usize * self.width];
+        *pixel = blend_over(*pixel, color);
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, w: usize, h: usize, color: u32) {
+        for dy in 0..h as i32 {
+            for dx in 0..w as i32 {
+                self.set_pixel(x + dx, y + dy, color);
+            }
+        }
+    }
+
+    pub fn outline_rect(&mut self, x: i32, y: i32, w: usize, h: usize, color: u32) {
+        let (w, h) = (w as i32, h as i32);
+        for dx in 0..w {
+            self.set_pixel(x + dx, y, color);
+            self.set_pixel(x + dx, y + h - 1, color);
+        }
+        for dy in 0..h {
+            self.set_pixel(x, y + dy, color);
+            self.set_pixel(x + w - 1, y + dy, color);
+        }
+    }
+
+    /// Draws `text` with its top left corner at `x,y`.
+    /// Characters outside of printable ASCII are drawn as `?`.
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, color: u32) {
+        for (i, c) in text.chars().enumerate() {
+            let glyph = get_glyph(c);
+            let glyph_x = x + (i * CHAR_ADVANCE) as i32;
+            for (dx, column) in glyph.iter().enumerate() {
+                for dy in 0..GLYPH_HEIGHT {
+                    if column >> dy & 1 != 0 {
+                        self.set_pixel(glyph_x + dx as i32, y + dy as i32, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws `text` with a one pixel outline, to keep it readable on any background.
+    pub fn draw_text_outlined(&mut self, x: i32, y: i32, text: &str, color: u32, outline: u32) {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx != 0 || dy != 0 {
+                    self.draw_text(x + dx, y + dy, text, outline);
+                }
+            }
+        }
+        self.draw_text(x, y, text, color);
+    }
+}
+
+/// Alpha-composites `over` onto `under`, both ABGR.
+pub fn blend_over(under: u32, over: u32) -> u32 {
+    let over_alpha = over >> 24;
+    if over_alpha == 0xff {
+        return over;
+    }
+    if over_alpha == 0 {
+        return under;
+    }
+    let under_alpha = (under >> 24) * (0xff - over_alpha) / 0xff;
+    let out_alpha = over_alpha + under_alpha;
+    let mut color = out_alpha << 24;
+    for shift in &[0, 8, 16] {
+        let over_c = over >> shift & 0xff;
+        let under_c = under >> shift & 0xff;
+        let c = (over_c * over_alpha + under_c * under_alpha) / out_alpha;
+        color |= c << shift;
+    }
+    color
+}
+
+fn get_glyph(c: char) -> &'static [u8; GLYPH_WIDTH] {
+    let index = match c {
+        ' '..='~' => c as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &FONT_5X7[index]
+}
+
+/// width in pixels of `text` when drawn, without trailing spacing
+pub fn text_width(text: &str) -> usize {
+    let len = text.chars().count();
+    if len == 0 {
+        0
+    } else {
+        len * CHAR_ADVANCE - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_text() {
+        assert_eq!(text_width(""), 0);
+        assert_eq!(text_width("a"), GLYPH_WIDTH);
+        assert_eq!(text_width("ab"), CHAR_ADVANCE + GLYPH_WIDTH);
+        // drawn as `?`
+        assert_eq!(text_width("é"), GLYPH_WIDTH);
+    }
+
+    #[test]
+    fn blends_colors() {
+        let red = 0xff_00_00_ff;
+        assert_eq!(blend_over(red, 0xff_ff_00_00), 0xff_ff_00_00);
+        assert_eq!(blend_over(red, 0x00_ff_00_00), red);
+        assert_eq!(blend_over(0xff_00_00_00, 0x80_ff_ff_ff), 0xff_80_80_80);
+        // over a transparent pixel, the color is kept as is
+        assert_eq!(blend_over(0, 0x80_ff_00_00), 0x80_ff_00_00);
+    }
+
+    #[test]
+    fn draws_text_within_canvas() {
+        let mut pixels = vec![0; 8 * 8];
+        let mut canvas = Canvas::new(&mut pixels, 8, 8);
+        canvas.draw_text(-3, 1, "|!", 1);
+        // `|` is cut off, `!` is drawn in its third column at x=5
+        assert_eq!(pixels[8], 0);
+        assert_eq!(pixels[4 + 8], 0);
+        assert_eq!(pixels[5 + 8], 1);
+        assert_eq!(pixels.iter().filter(|p| **p == 1).count(), 6);
+    }
+}
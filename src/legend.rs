@@ -0,0 +1,225 @@
+extern crate serde_json;
+
+use crate::draw::{text_width, Canvas, GLYPH_HEIGHT, LINE_HEIGHT};
+use crate::palette::{format_color, Gradient};
+use serde::Serialize;
+
+const PADDING: usize = 6;
+const SWATCH_SIZE: usize = GLYPH_HEIGHT + 1;
+const ENTRIES_PER_COLUMN: usize = 32;
+const GRADIENT_WIDTH: usize = 256;
+const GRADIENT_HEIGHT: usize = 12;
+
+const BACKGROUND: u32 = 0xff_ff_ff_ff;
+const TEXT_COLOR: u32 = 0xff_00_00_00;
+const BORDER_COLOR: u32 = 0xff_88_88_88;
+
+/// Describes what the colors of a colorizer mean.
+#[derive(Debug, Clone, Default)]
+pub struct Legend {
+    pub title: String,
+    /// distinct colors, such as one per biome
+    pub entries: Vec<(String, u32)>,
+    /// continuous color ranges, such as land height
+    pub gradients: Vec<(String, Gradient)>,
+}
+
+impl Legend {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_entry(mut self, label: &str, color: u32) -> Self {
+        self.entries.push((label.to_string(), color));
+        self
+    }
+
+    pub fn with_gradient(mut self, label: &str, gradient: &Gradient) -> Self {
+        self.gradients.push((label.to_string(), gradient.clone()));
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct LegendJson<'a> {
+    title: &'a str,
+    entries: Vec<EntryJson<'a>>,
+    gradients: Vec<GradientJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct EntryJson<'a> {
+    label: &'a str,
+    color: String,
+}
+
+#[derive(Serialize)]
+struct GradientJson<'a> {
+    label: &'a str,
+    /// same format as in palette files
+    stops: Vec<(i32, String)>,
+}
+
+pub fn legend_to_json(legend: &Legend) -> String {
+    let json = LegendJson {
+        title: &legend.title,
+        entries: legend
+            .entries
+            .iter()
+            .map(|(label, color)| EntryJson {
+                label,
+                color: format_color(*color),
+            })
+            .collect(),
+        gradients: legend
+            .gradients
+            .iter()
+            .map(|(label, gradient)| GradientJson {
+                label,
+                stops: gradient
+                    .stops()
+                    .iter()
+                    .map(|(value, color)| (*value, format_color(*color)))
+                    .collect(),
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&json).expect("serializing legend")
+}
+
+/// Returns the pixels, width and height of the legend image.
+///
+/// The title is on top, followed by the entries as color swatches
+/// in columns, then each gradient as a bar with its stops labeled.
+pub fn draw_legend(legend: &Legend) -> (Vec<u32>, usize, usize) {
+    let num_columns = (legend.entries.len() + ENTRIES_PER_COLUMN - 1) / ENTRIES_PER_COLUMN;
+    let num_rows = legend.entries.len().min(ENTRIES_PER_COLUMN);
+    let column_width = SWATCH_SIZE
+        + 4
+        + legend
+            .entries
+            .iter()
+            .map(|(label, _)| text_width(label))
+            .max()
+            .unwrap_or(0)
+        + PADDING;
+
+    let entries_height = num_rows * LINE_HEIGHT;
+    let gradients_height = legend.gradients.len() * (2 * LINE_HEIGHT + GRADIENT_HEIGHT + 2);
+
+    let mut width = text_width(&legend.title).max(num_columns * column_width);
+    if !legend.gradients.is_empty() {
+        width = width.max(GRADIENT_WIDTH + PADDING);
+    }
+    let width = width + 2 * PADDING;
+    let height = 2 * PADDING + LINE_HEIGHT + entries_height + gradients_height;
+
+    let mut pixels = vec![BACKGROUND; width * height];
+    {
+        let mut canvas = Canvas::new(&mut pixels, width, height);
+        let pad = PADDING as i32;
+        canvas.draw_text(pad, pad, &legend.title, TEXT_COLOR);
+
+        let entries_top = pad + LINE_HEIGHT as i32;
+        for (i, (label, color)) in legend.entries.iter().enumerate() {
+            let x = pad + (i / ENTRIES_PER_COLUMN * column_width) as i32;
+            let y = entries_top + (i % ENTRIES_PER_COLUMN * LINE_HEIGHT) as i32;
+            canvas.fill_rect(x, y, SWATCH_SIZE, SWATCH_SIZE, *color);
+            canvas.outline_rect(x, y, SWATCH_SIZE, SWATCH_SIZE, BORDER_COLOR);
+            canvas.draw_text(x + SWATCH_SIZE as i32 + 4, y + 1, label, TEXT_COLOR);
+        }
+
+        let mut y = entries_top + entries_height as i32;
+        for (label, gradient) in &legend.gradients {
+            canvas.draw_text(pad, y, label, TEXT_COLOR);
+            y += LINE_HEIGHT as i32;
+            draw_gradient(&mut canvas, pad, y, gradient);
+            y += GRADIENT_HEIGHT as i32 + 2 + LINE_HEIGHT as i32;
+        }
+    }
+    (pixels, width, height)
+}
+
+fn draw_gradient(canvas: &mut Canvas, x: i32, y: i32, gradient: &Gradient) {
+    let min = gradient.min_value();
+    let range = (gradient.max_value() - min).max(1);
+    let bar_end = GRADIENT_WIDTH as i32 - 1;
+    for dx in 0..GRADIENT_WIDTH as i32 {
+        let color = gradient.color_at(min + dx * range / bar_end);
+        canvas.fill_rect(x + dx, y, 1, GRADIENT_HEIGHT, color);
+    }
+    canvas.outline_rect(x, y, GRADIENT_WIDTH, GRADIENT_HEIGHT, BORDER_COLOR);
+
+    // label the stops, skipping any that would overlap the previous label
+    let label_y = y + GRADIENT_HEIGHT as i32 + 2;
+    let mut free_x = x;
+    for (value, _) in gradient.stops() {
+        let tick_x = x + (value - min) * bar_end / range;
+        let label = value.to_string();
+        let label_x = (tick_x - text_width(&label) as i32 / 2).max(x);
+        if label_x < free_x {
+            continue;
+        }
+        canvas.fill_rect(tick_x, label_y - 2, 1, 2, TEXT_COLOR);
+        canvas.draw_text(label_x, label_y, &label, TEXT_COLOR);
+        free_x = label_x + text_width(&label) as i32 + 4;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: u32 = 0xff_00_00_00;
+    const WHITE: u32 = 0xff_ff_ff_ff;
+    const GREEN: u32 = 0xff_00_ff_00;
+
+    #[test]
+    fn writes_json() {
+        let gradient = Gradient::new(vec![(0, BLACK), (255, WHITE)]).unwrap();
+        let legend = Legend::new("Biomes")
+            .with_entry("plains", GREEN)
+            .with_gradient("height", &gradient);
+        let json: serde_json::Value = serde_json::from_str(&legend_to_json(&legend)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "title": "Biomes",
+                "entries": [{"label": "plains", "color": "#00ff00"}],
+                "gradients": [{"label": "height", "stops": [[0, "#000000"], [255, "#ffffff"]]}],
+            })
+        );
+    }
+
+    #[test]
+    fn draws_entries_in_columns() {
+        let mut legend = Legend::new("Biomes");
+        for i in 0..ENTRIES_PER_COLUMN + 1 {
+            legend = legend.with_entry(&format!("biome {}", i), GREEN);
+        }
+        let (pixels, width, height) = draw_legend(&legend);
+        assert_eq!(pixels.len(), width * height);
+        assert_eq!(
+            height,
+            2 * PADDING + LINE_HEIGHT + ENTRIES_PER_COLUMN * LINE_HEIGHT
+        );
+        let column_width = SWATCH_SIZE + 4 + text_width("biome 10") + PADDING;
+        assert_eq!(width, 2 * column_width + 2 * PADDING);
+        // inside the swatches at the top of both columns, and below the last one
+        let top = PADDING + LINE_HEIGHT + 1;
+        assert_eq!(pixels[PADDING + 1 + top * width], GREEN);
+        assert_eq!(pixels[PADDING + column_width + 1 + top * width], GREEN);
+        let below = top + LINE_HEIGHT;
+        assert_eq!(
+            pixels[PADDING + column_width + 1 + below * width],
+            BACKGROUND
+        );
+
+        let (_, width, height) = draw_legend(&Legend::new("Empty"));
+        assert_eq!(width, text_width("Empty") + 2 * PADDING);
+        assert_eq!(height, 2 * PADDING + LINE_HEIGHT);
+    }
+}
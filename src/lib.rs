@@ -3,6 +3,7 @@ extern crate byteorder;
 extern crate error_chain;
 #[macro_use]
 extern crate lazy_static;
+extern crate serde;
 extern crate zip;
 
 use mc::blocks::BLOCK_STRINGS_ARR;
@@ -10,13 +11,23 @@ use std::fs;
 use std::path::PathBuf;
 use std::time::{Duration, Instant, SystemTime};
 
+pub mod anvil;
+pub mod area;
 pub mod biomes;
 pub mod buf_rw;
 pub mod ccnatural;
 pub mod colorizer;
+pub mod draw;
+pub mod legend;
 pub mod mc;
+pub mod naturality;
+pub mod nbt;
+pub mod palette;
+pub mod render;
 pub mod replay;
 pub mod tile;
+pub mod waypoint;
+pub mod xaero;
 
 pub const CHUNK_WIDTH: usize = 16;
 pub const CHUNK_HEIGHT: usize = 16;
@@ -69,6 +80,15 @@ pub fn parse_bounds(bounds_str: &str) -> Result<Vec<i32>, String> {
     }
 }
 
+/// Quotes a CSV field if it contains commas or quotes.
+pub fn csv_quote(field: &str) -> String {
+    if field.contains(",") || field.contains("\"") {
+        format!("\"{}\"", field.replace("\"", "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
 pub const PROGRESS_INTERVAL: u64 = 3;
 
 pub struct ProgressTracker {
@@ -81,7 +101,7 @@ pub struct ProgressTracker {
 impl ProgressTracker {
     pub fn new(total: usize) -> Self {
         Self {
-            total: total,
+            total,
             start_time: Instant::now(),
             done: 0,
             next_msg_elapsed: PROGRESS_INTERVAL,
@@ -110,7 +130,7 @@ impl ProgressTracker {
 
 // TODO put more weight on recent measurements
 pub fn print_progress(done: usize, total: usize, start_time: Instant, next_msg_elapsed: &mut u64) {
-    if total <= 0 || done == 0 {
+    if total == 0 || done == 0 {
         return;
     }
 
@@ -0,0 +1,858 @@
+use crate::biomes::get_biome_id_by_name;
+use std::collections::HashMap;
+
+/// Vanilla's biome categories, as in 1.16 to 1.18.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BiomeCategory {
+    None,
+    Taiga,
+    ExtremeHills,
+    Jungle,
+    Mesa,
+    Plains,
+    Savanna,
+    Icy,
+    TheEnd,
+    Beach,
+    Forest,
+    Ocean,
+    Desert,
+    River,
+    Swamp,
+    Mushroom,
+    Nether,
+    Underground,
+    Mountain,
+}
+
+impl BiomeCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BiomeCategory::None => "none",
+            BiomeCategory::Taiga => "taiga",
+            BiomeCategory::ExtremeHills => "extreme_hills",
+            BiomeCategory::Jungle => "jungle",
+            BiomeCategory::Mesa => "mesa",
+            BiomeCategory::Plains => "plains",
+            BiomeCategory::Savanna => "savanna",
+            BiomeCategory::Icy => "icy",
+            BiomeCategory::TheEnd => "the_end",
+            BiomeCategory::Beach => "beach",
+            BiomeCategory::Forest => "forest",
+            BiomeCategory::Ocean => "ocean",
+            BiomeCategory::Desert => "desert",
+            BiomeCategory::River => "river",
+            BiomeCategory::Swamp => "swamp",
+            BiomeCategory::Mushroom => "mushroom",
+            BiomeCategory::Nether => "nether",
+            BiomeCategory::Underground => "underground",
+            BiomeCategory::Mountain => "mountain",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Biome {
+    /// registry name as of 1.21, such as `minecraft:windswept_hills`
+    pub name: &'static str,
+    /// registry names this biome had in 1.13 to 1.17, such as `minecraft:mountains`
+    pub old_names: &'static [&'static str],
+    /// numeric id used until 1.17, and by VoxelMap caches
+    pub legacy_id: Option<u8>,
+    /// ABGR, from the AMIDST color map where it has one
+    pub color: u32,
+    pub temperature: f32,
+    pub category: BiomeCategory,
+}
+
+/// Vanilla biomes, including the ones removed in 1.18.
+pub const BIOMES: [Biome; 91] = [
+    Biome {
+        name: "minecraft:ocean",
+        old_names: &[],
+        legacy_id: Some(0),
+        color: 0xff700000,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:plains",
+        old_names: &[],
+        legacy_id: Some(1),
+        color: 0xff60b38d,
+        temperature: 0.8,
+        category: BiomeCategory::Plains,
+    },
+    Biome {
+        name: "minecraft:desert",
+        old_names: &[],
+        legacy_id: Some(2),
+        color: 0xff1894fa,
+        temperature: 2.0,
+        category: BiomeCategory::Desert,
+    },
+    Biome {
+        name: "minecraft:windswept_hills",
+        old_names: &["minecraft:mountains"],
+        legacy_id: Some(3),
+        color: 0xff606060,
+        temperature: 0.2,
+        category: BiomeCategory::ExtremeHills,
+    },
+    Biome {
+        name: "minecraft:forest",
+        old_names: &[],
+        legacy_id: Some(4),
+        color: 0xff216605,
+        temperature: 0.7,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:taiga",
+        old_names: &[],
+        legacy_id: Some(5),
+        color: 0xff59660b,
+        temperature: 0.25,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:swamp",
+        old_names: &[],
+        legacy_id: Some(6),
+        color: 0xffb2f907,
+        temperature: 0.8,
+        category: BiomeCategory::Swamp,
+    },
+    Biome {
+        name: "minecraft:river",
+        old_names: &[],
+        legacy_id: Some(7),
+        color: 0xffff0000,
+        temperature: 0.5,
+        category: BiomeCategory::River,
+    },
+    Biome {
+        name: "minecraft:nether_wastes",
+        old_names: &["minecraft:nether"],
+        legacy_id: Some(8),
+        color: 0xff0000ff,
+        temperature: 2.0,
+        category: BiomeCategory::Nether,
+    },
+    Biome {
+        name: "minecraft:the_end",
+        old_names: &[],
+        legacy_id: Some(9),
+        color: 0xffff8080,
+        temperature: 0.5,
+        category: BiomeCategory::TheEnd,
+    },
+    Biome {
+        name: "minecraft:frozen_ocean",
+        old_names: &[],
+        legacy_id: Some(10),
+        color: 0xffa09090,
+        temperature: 0.0,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:frozen_river",
+        old_names: &[],
+        legacy_id: Some(11),
+        color: 0xffffa0a0,
+        temperature: 0.0,
+        category: BiomeCategory::River,
+    },
+    Biome {
+        name: "minecraft:snowy_plains",
+        old_names: &["minecraft:snowy_tundra"],
+        legacy_id: Some(12),
+        color: 0xffffffff,
+        temperature: 0.0,
+        category: BiomeCategory::Icy,
+    },
+    Biome {
+        name: "minecraft:snowy_mountains",
+        old_names: &[],
+        legacy_id: Some(13),
+        color: 0xffa0a0a0,
+        temperature: 0.0,
+        category: BiomeCategory::Icy,
+    },
+    Biome {
+        name: "minecraft:mushroom_fields",
+        old_names: &[],
+        legacy_id: Some(14),
+        color: 0xffff00ff,
+        temperature: 0.9,
+        category: BiomeCategory::Mushroom,
+    },
+    Biome {
+        name: "minecraft:mushroom_field_shore",
+        old_names: &[],
+        legacy_id: Some(15),
+        color: 0xffff00a0,
+        temperature: 0.9,
+        category: BiomeCategory::Mushroom,
+    },
+    Biome {
+        name: "minecraft:beach",
+        old_names: &[],
+        legacy_id: Some(16),
+        color: 0xff55defa,
+        temperature: 0.8,
+        category: BiomeCategory::Beach,
+    },
+    Biome {
+        name: "minecraft:desert_hills",
+        old_names: &[],
+        legacy_id: Some(17),
+        color: 0xff125fd2,
+        temperature: 2.0,
+        category: BiomeCategory::Desert,
+    },
+    Biome {
+        name: "minecraft:wooded_hills",
+        old_names: &[],
+        legacy_id: Some(18),
+        color: 0xff1c5522,
+        temperature: 0.7,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:taiga_hills",
+        old_names: &[],
+        legacy_id: Some(19),
+        color: 0xff333916,
+        temperature: 0.25,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:mountain_edge",
+        old_names: &[],
+        legacy_id: Some(20),
+        color: 0xff9a7872,
+        temperature: 0.2,
+        category: BiomeCategory::ExtremeHills,
+    },
+    Biome {
+        name: "minecraft:jungle",
+        old_names: &[],
+        legacy_id: Some(21),
+        color: 0xff097b53,
+        temperature: 0.95,
+        category: BiomeCategory::Jungle,
+    },
+    Biome {
+        name: "minecraft:jungle_hills",
+        old_names: &[],
+        legacy_id: Some(22),
+        color: 0xff05422c,
+        temperature: 0.95,
+        category: BiomeCategory::Jungle,
+    },
+    Biome {
+        name: "minecraft:sparse_jungle",
+        old_names: &["minecraft:jungle_edge"],
+        legacy_id: Some(23),
+        color: 0xff178b62,
+        temperature: 0.95,
+        category: BiomeCategory::Jungle,
+    },
+    Biome {
+        name: "minecraft:deep_ocean",
+        old_names: &[],
+        legacy_id: Some(24),
+        color: 0xff300000,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:stony_shore",
+        old_names: &["minecraft:stone_shore"],
+        legacy_id: Some(25),
+        color: 0xff84a2a2,
+        temperature: 0.2,
+        category: BiomeCategory::Beach,
+    },
+    Biome {
+        name: "minecraft:snowy_beach",
+        old_names: &[],
+        legacy_id: Some(26),
+        color: 0xffc0f0fa,
+        temperature: 0.05,
+        category: BiomeCategory::Beach,
+    },
+    Biome {
+        name: "minecraft:birch_forest",
+        old_names: &[],
+        legacy_id: Some(27),
+        color: 0xff447430,
+        temperature: 0.6,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:birch_forest_hills",
+        old_names: &[],
+        legacy_id: Some(28),
+        color: 0xff325f1f,
+        temperature: 0.6,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:dark_forest",
+        old_names: &[],
+        legacy_id: Some(29),
+        color: 0xff1a5140,
+        temperature: 0.7,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:snowy_taiga",
+        old_names: &[],
+        legacy_id: Some(30),
+        color: 0xff4a5531,
+        temperature: -0.5,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:snowy_taiga_hills",
+        old_names: &[],
+        legacy_id: Some(31),
+        color: 0xff363f24,
+        temperature: -0.5,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:old_growth_pine_taiga",
+        old_names: &["minecraft:giant_tree_taiga"],
+        legacy_id: Some(32),
+        color: 0xff516659,
+        temperature: 0.3,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:giant_tree_taiga_hills",
+        old_names: &[],
+        legacy_id: Some(33),
+        color: 0xff3e4f45,
+        temperature: 0.3,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:windswept_forest",
+        old_names: &["minecraft:wooded_mountains"],
+        legacy_id: Some(34),
+        color: 0xff507050,
+        temperature: 0.2,
+        category: BiomeCategory::ExtremeHills,
+    },
+    Biome {
+        name: "minecraft:savanna",
+        old_names: &[],
+        legacy_id: Some(35),
+        color: 0xff5fb2bd,
+        temperature: 2.0,
+        category: BiomeCategory::Savanna,
+    },
+    Biome {
+        name: "minecraft:savanna_plateau",
+        old_names: &[],
+        legacy_id: Some(36),
+        color: 0xff649da7,
+        temperature: 2.0,
+        category: BiomeCategory::Savanna,
+    },
+    Biome {
+        name: "minecraft:badlands",
+        old_names: &[],
+        legacy_id: Some(37),
+        color: 0xff1545d9,
+        temperature: 2.0,
+        category: BiomeCategory::Mesa,
+    },
+    Biome {
+        name: "minecraft:wooded_badlands",
+        old_names: &["minecraft:wooded_badlands_plateau"],
+        legacy_id: Some(38),
+        color: 0xff6597b0,
+        temperature: 2.0,
+        category: BiomeCategory::Mesa,
+    },
+    Biome {
+        name: "minecraft:badlands_plateau",
+        old_names: &[],
+        legacy_id: Some(39),
+        color: 0xff658cca,
+        temperature: 2.0,
+        category: BiomeCategory::Mesa,
+    },
+    Biome {
+        name: "minecraft:small_end_islands",
+        old_names: &[],
+        legacy_id: Some(40),
+        color: 0xffff8080,
+        temperature: 0.5,
+        category: BiomeCategory::TheEnd,
+    },
+    Biome {
+        name: "minecraft:end_midlands",
+        old_names: &[],
+        legacy_id: Some(41),
+        color: 0xffff8080,
+        temperature: 0.5,
+        category: BiomeCategory::TheEnd,
+    },
+    Biome {
+        name: "minecraft:end_highlands",
+        old_names: &[],
+        legacy_id: Some(42),
+        color: 0xffff8080,
+        temperature: 0.5,
+        category: BiomeCategory::TheEnd,
+    },
+    Biome {
+        name: "minecraft:end_barrens",
+        old_names: &[],
+        legacy_id: Some(43),
+        color: 0xffff8080,
+        temperature: 0.5,
+        category: BiomeCategory::TheEnd,
+    },
+    Biome {
+        name: "minecraft:warm_ocean",
+        old_names: &[],
+        legacy_id: Some(44),
+        color: 0xffac0000,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:lukewarm_ocean",
+        old_names: &[],
+        legacy_id: Some(45),
+        color: 0xff900000,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:cold_ocean",
+        old_names: &[],
+        legacy_id: Some(46),
+        color: 0xff702020,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:deep_warm_ocean",
+        old_names: &[],
+        legacy_id: Some(47),
+        color: 0xff500000,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:deep_lukewarm_ocean",
+        old_names: &[],
+        legacy_id: Some(48),
+        color: 0xff400000,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:deep_cold_ocean",
+        old_names: &[],
+        legacy_id: Some(49),
+        color: 0xff382020,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:deep_frozen_ocean",
+        old_names: &[],
+        legacy_id: Some(50),
+        color: 0xff904040,
+        temperature: 0.5,
+        category: BiomeCategory::Ocean,
+    },
+    Biome {
+        name: "minecraft:the_void",
+        old_names: &[],
+        legacy_id: Some(127),
+        color: 0xff000000,
+        temperature: 0.5,
+        category: BiomeCategory::None,
+    },
+    Biome {
+        name: "minecraft:sunflower_plains",
+        old_names: &[],
+        legacy_id: Some(129),
+        color: 0xff60b38d,
+        temperature: 0.8,
+        category: BiomeCategory::Plains,
+    },
+    Biome {
+        name: "minecraft:desert_lakes",
+        old_names: &[],
+        legacy_id: Some(130),
+        color: 0xff1894fa,
+        temperature: 2.0,
+        category: BiomeCategory::Desert,
+    },
+    Biome {
+        name: "minecraft:windswept_gravelly_hills",
+        old_names: &["minecraft:gravelly_mountains"],
+        legacy_id: Some(131),
+        color: 0xff606060,
+        temperature: 0.2,
+        category: BiomeCategory::ExtremeHills,
+    },
+    Biome {
+        name: "minecraft:flower_forest",
+        old_names: &[],
+        legacy_id: Some(132),
+        color: 0xff25746a,
+        temperature: 0.7,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:taiga_mountains",
+        old_names: &[],
+        legacy_id: Some(133),
+        color: 0xff59660b,
+        temperature: 0.25,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:swamp_hills",
+        old_names: &[],
+        legacy_id: Some(134),
+        color: 0xffb2f907,
+        temperature: 0.8,
+        category: BiomeCategory::Swamp,
+    },
+    Biome {
+        name: "minecraft:ice_spikes",
+        old_names: &[],
+        legacy_id: Some(140),
+        color: 0xffffffd2,
+        temperature: 0.0,
+        category: BiomeCategory::Icy,
+    },
+    Biome {
+        name: "minecraft:modified_jungle",
+        old_names: &[],
+        legacy_id: Some(149),
+        color: 0xff097b53,
+        temperature: 0.95,
+        category: BiomeCategory::Jungle,
+    },
+    Biome {
+        name: "minecraft:modified_jungle_edge",
+        old_names: &[],
+        legacy_id: Some(151),
+        color: 0xff178b62,
+        temperature: 0.95,
+        category: BiomeCategory::Jungle,
+    },
+    Biome {
+        name: "minecraft:old_growth_birch_forest",
+        old_names: &["minecraft:tall_birch_forest"],
+        legacy_id: Some(155),
+        color: 0xff447430,
+        temperature: 0.6,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:tall_birch_hills",
+        old_names: &[],
+        legacy_id: Some(156),
+        color: 0xff325f1f,
+        temperature: 0.6,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:dark_forest_hills",
+        old_names: &[],
+        legacy_id: Some(157),
+        color: 0xff1a5140,
+        temperature: 0.7,
+        category: BiomeCategory::Forest,
+    },
+    Biome {
+        name: "minecraft:snowy_taiga_mountains",
+        old_names: &[],
+        legacy_id: Some(158),
+        color: 0xff727d59,
+        temperature: -0.5,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:old_growth_spruce_taiga",
+        old_names: &["minecraft:giant_spruce_taiga"],
+        legacy_id: Some(160),
+        color: 0xff516659,
+        temperature: 0.25,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:giant_spruce_taiga_hills",
+        old_names: &[],
+        legacy_id: Some(161),
+        color: 0xff75a4e6,
+        temperature: 0.25,
+        category: BiomeCategory::Taiga,
+    },
+    Biome {
+        name: "minecraft:modified_gravelly_mountains",
+        old_names: &[],
+        legacy_id: Some(162),
+        color: 0xff507050,
+        temperature: 0.2,
+        category: BiomeCategory::ExtremeHills,
+    },
+    Biome {
+        name: "minecraft:windswept_savanna",
+        old_names: &["minecraft:shattered_savanna"],
+        legacy_id: Some(163),
+        color: 0xff5fb2bd,
+        temperature: 1.1,
+        category: BiomeCategory::Savanna,
+    },
+    Biome {
+        name: "minecraft:shattered_savanna_plateau",
+        old_names: &[],
+        legacy_id: Some(164),
+        color: 0xff649da7,
+        temperature: 1.0,
+        category: BiomeCategory::Savanna,
+    },
+    Biome {
+        name: "minecraft:eroded_badlands",
+        old_names: &[],
+        legacy_id: Some(165),
+        color: 0xff1545d9,
+        temperature: 2.0,
+        category: BiomeCategory::Mesa,
+    },
+    Biome {
+        name: "minecraft:modified_wooded_badlands_plateau",
+        old_names: &[],
+        legacy_id: Some(166),
+        color: 0xff6597b0,
+        temperature: 2.0,
+        category: BiomeCategory::Mesa,
+    },
+    Biome {
+        name: "minecraft:modified_badlands_plateau",
+        old_names: &[],
+        legacy_id: Some(167),
+        color: 0xff658cca,
+        temperature: 2.0,
+        category: BiomeCategory::Mesa,
+    },
+    Biome {
+        name: "minecraft:bamboo_jungle",
+        old_names: &[],
+        legacy_id: Some(168),
+        color: 0xff148e76,
+        temperature: 0.95,
+        category: BiomeCategory::Jungle,
+    },
+    Biome {
+        name: "minecraft:bamboo_jungle_hills",
+        old_names: &[],
+        legacy_id: Some(169),
+        color: 0xff0a473b,
+        temperature: 0.95,
+        category: BiomeCategory::Jungle,
+    },
+    Biome {
+        name: "minecraft:soul_sand_valley",
+        old_names: &[],
+        legacy_id: Some(170),
+        color: 0xff30385e,
+        temperature: 2.0,
+        category: BiomeCategory::Nether,
+    },
+    Biome {
+        name: "minecraft:crimson_forest",
+        old_names: &[],
+        legacy_id: Some(171),
+        color: 0xff0808dd,
+        temperature: 2.0,
+        category: BiomeCategory::Nether,
+    },
+    Biome {
+        name: "minecraft:warped_forest",
+        old_names: &[],
+        legacy_id: Some(172),
+        color: 0xff7b9049,
+        temperature: 2.0,
+        category: BiomeCategory::Nether,
+    },
+    Biome {
+        name: "minecraft:basalt_deltas",
+        old_names: &[],
+        legacy_id: Some(173),
+        color: 0xff363640,
+        temperature: 2.0,
+        category: BiomeCategory::Nether,
+    },
+    Biome {
+        name: "minecraft:dripstone_caves",
+        old_names: &[],
+        legacy_id: Some(174),
+        color: 0xff436086,
+        temperature: 0.8,
+        category: BiomeCategory::Underground,
+    },
+    Biome {
+        name: "minecraft:lush_caves",
+        old_names: &[],
+        legacy_id: Some(175),
+        color: 0xff50c828,
+        temperature: 0.5,
+        category: BiomeCategory::Underground,
+    },
+    Biome {
+        name: "minecraft:meadow",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xff64be82,
+        temperature: 0.5,
+        category: BiomeCategory::Mountain,
+    },
+    Biome {
+        name: "minecraft:grove",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xffaabe96,
+        temperature: -0.2,
+        category: BiomeCategory::Mountain,
+    },
+    Biome {
+        name: "minecraft:snowy_slopes",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xfff5ebdc,
+        temperature: -0.3,
+        category: BiomeCategory::Mountain,
+    },
+    Biome {
+        name: "minecraft:jagged_peaks",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xfffaf0eb,
+        temperature: -0.7,
+        category: BiomeCategory::Mountain,
+    },
+    Biome {
+        name: "minecraft:frozen_peaks",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xffffe0b0,
+        temperature: -0.7,
+        category: BiomeCategory::Mountain,
+    },
+    Biome {
+        name: "minecraft:stony_peaks",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xff969696,
+        temperature: 1.0,
+        category: BiomeCategory::Mountain,
+    },
+    Biome {
+        name: "minecraft:deep_dark",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xff281e14,
+        temperature: 0.8,
+        category: BiomeCategory::Underground,
+    },
+    Biome {
+        name: "minecraft:mangrove_swamp",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xff3c6e32,
+        temperature: 0.8,
+        category: BiomeCategory::Swamp,
+    },
+    Biome {
+        name: "minecraft:cherry_grove",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xffc5b7ff,
+        temperature: 0.5,
+        category: BiomeCategory::Mountain,
+    },
+    Biome {
+        name: "minecraft:pale_garden",
+        old_names: &[],
+        legacy_id: None,
+        color: 0xffbec8c8,
+        temperature: 0.7,
+        category: BiomeCategory::Forest,
+    },
+];
+
+lazy_static! {
+    /// current and old registry names to biome
+    static ref BIOMES_BY_NAME: HashMap<&'static str, &'static Biome> = {
+        let mut biomes = HashMap::new();
+        for biome in BIOMES.iter() {
+            biomes.insert(biome.name, biome);
+            for old_name in biome.old_names {
+                biomes.insert(*old_name, biome);
+            }
+        }
+        biomes
+    };
+    static ref BIOMES_BY_LEGACY_ID: HashMap<u16, &'static Biome> = BIOMES
+        .iter()
+        .filter_map(|biome| biome.legacy_id.map(|id| (id as u16, biome)))
+        .collect();
+}
+
+/// Finds a biome by its registry name, with or without `minecraft:`,
+/// or by its name in the AMIDST table such as `Extreme Hills`.
+/// Modded biomes are not found.
+pub fn get_biome_by_name(name: &str) -> Option<&'static Biome> {
+    let name = name.to_lowercase();
+    let name = if name.contains(":") {
+        name
+    } else {
+        format!("minecraft:{}", name)
+    };
+    BIOMES_BY_NAME.get(name.as_str()).cloned().or_else(|| {
+        get_biome_id_by_name(&name).and_then(|biome_id| get_biome_by_legacy_id(biome_id as u16))
+    })
+}
+
+pub fn get_biome_by_legacy_id(biome_id: u16) -> Option<&'static Biome> {
+    BIOMES_BY_LEGACY_ID.get(&biome_id).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_biomes_by_any_name() {
+        let windswept_hills = get_biome_by_legacy_id(3).unwrap();
+        assert_eq!(windswept_hills.name, "minecraft:windswept_hills");
+        assert_eq!(windswept_hills.category, BiomeCategory::ExtremeHills);
+        assert_eq!(
+            get_biome_by_name("minecraft:mountains"),
+            Some(windswept_hills)
+        );
+        assert_eq!(get_biome_by_name("Extreme Hills"), Some(windswept_hills));
+        assert_eq!(get_biome_by_name("windswept_hills"), Some(windswept_hills));
+
+        let cherry_grove = get_biome_by_name("minecraft:cherry_grove").unwrap();
+        assert_eq!(cherry_grove.legacy_id, None);
+        assert_eq!(get_biome_by_name("modded:cherry_grove"), None);
+        assert_eq!(get_biome_by_legacy_id(300), None);
+    }
+}
@@ -0,0 +1,220 @@
+extern crate serde_json;
+
+use super::blocks::{BlockNamePattern, BlockState};
+use crate::palette::parse_color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// The built-in rules, covering vanilla blocks up to 1.20.
+const DEFAULT_BLOCKS_JSON: &str = include_str!("blocks.json");
+
+/// How a block state looks and behaves, as far as the colorizers care.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInfo {
+    /// water, and blocks that are always or currently filled with water
+    pub water: bool,
+    pub lava: bool,
+    /// light and the view from above pass through, such as glass or torches
+    pub transparent: bool,
+    /// plants and leaves
+    pub foliage: bool,
+    /// can be stood on
+    pub solid: bool,
+    /// mobs can spawn on top of it, or in it if it's not solid
+    pub spawnable: bool,
+    /// ABGR, 0 if unknown
+    pub map_color: u32,
+}
+
+impl Default for BlockInfo {
+    /// Unclassified blocks are assumed to be plain full blocks.
+    fn default() -> Self {
+        Self {
+            water: false,
+            lava: false,
+            transparent: false,
+            foliage: false,
+            solid: true,
+            spawnable: true,
+            map_color: 0,
+        }
+    }
+}
+
+/// One entry of a blocks file, see `BlockRegistry::add_rules`.
+#[derive(Debug, Deserialize)]
+struct BlockRuleFile {
+    blocks: Vec<String>,
+    water: Option<bool>,
+    lava: Option<bool>,
+    transparent: Option<bool>,
+    foliage: Option<bool>,
+    solid: Option<bool>,
+    spawnable: Option<bool>,
+    map_color: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct BlockRule {
+    blocks: Vec<BlockNamePattern>,
+    water: Option<bool>,
+    lava: Option<bool>,
+    transparent: Option<bool>,
+    foliage: Option<bool>,
+    solid: Option<bool>,
+    spawnable: Option<bool>,
+    map_color: Option<u32>,
+}
+
+impl BlockRule {
+    fn apply(&self, info: &mut BlockInfo) {
+        info.water = self.water.unwrap_or(info.water);
+        info.lava = self.lava.unwrap_or(info.lava);
+        info.transparent = self.transparent.unwrap_or(info.transparent);
+        info.foliage = self.foliage.unwrap_or(info.foliage);
+        info.solid = self.solid.unwrap_or(info.solid);
+        info.spawnable = self.spawnable.unwrap_or(info.spawnable);
+        info.map_color = self.map_color.unwrap_or(info.map_color);
+    }
+}
+
+/// Classifies block states by the rules of `blocks.json`, and optionally user supplied files.
+pub struct BlockRegistry {
+    rules: Vec<BlockRule>,
+    /// by `&'static BlockState` address, as block states are interned
+    cache: RwLock<HashMap<usize, BlockInfo>>,
+}
+
+impl Default for BlockRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockRegistry {
+    /// With the built-in rules.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .add_rules(DEFAULT_BLOCKS_JSON)
+            .expect("built-in blocks.json is valid");
+        registry
+    }
+
+    /// Without any rules, every block gets the `BlockInfo` default.
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Reads more rules, see `add_rules`.
+    pub fn read(&mut self, path: &Path) -> Result<(), String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{:?} {}", path, e))?;
+        self.add_rules(&text)
+            .map_err(|e| format!("{:?} {}", path, e))
+    }
+
+    /// Adds rules after the existing ones. Each rule sets some fields of `BlockInfo`
+    /// for the blocks matching any of its patterns (see `BlockNamePattern`),
+    /// later rules override the fields they set.
+    ///
+    /// ```json
+    /// [
+    ///   {"blocks": ["*water", "bubble_column", "*[waterlogged=true]"], "water": true},
+    ///   {"blocks": ["mymod:*_leaves"], "foliage": true, "map_color": "#007c00"}
+    /// ]
+    /// ```
+    pub fn add_rules(&mut self, json: &str) -> Result<(), String> {
+        let file: Vec<BlockRuleFile> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        for rule in file {
+            let blocks = rule
+                .blocks
+                .iter()
+                .map(|pattern| BlockNamePattern::new(pattern))
+                .collect::<Result<Vec<_>, _>>()?;
+            let map_color = match rule.map_color {
+                Some(color) => Some(parse_color(&color)?),
+                None => None,
+            };
+            self.rules.push(BlockRule {
+                blocks,
+                water: rule.water,
+                lava: rule.lava,
+                transparent: rule.transparent,
+                foliage: rule.foliage,
+                solid: rule.solid,
+                spawnable: rule.spawnable,
+                map_color,
+            });
+        }
+        self.cache.write().unwrap().clear();
+        Ok(())
+    }
+
+    pub fn get_block_info(&self, block_state: &'static BlockState) -> BlockInfo {
+        let key = block_state as *const BlockState as usize;
+        if let Some(info) = self.cache.read().unwrap().get(&key) {
+            return *info;
+        }
+        let info = self.classify(block_state);
+        self.cache.write().unwrap().insert(key, info);
+        info
+    }
+
+    fn classify(&self, block_state: &BlockState) -> BlockInfo {
+        let mut info = BlockInfo::default();
+        if block_state.is_unknown() {
+            info.solid = false;
+            info.spawnable = false;
+            return info;
+        }
+        for rule in &self.rules {
+            if rule.blocks.iter().any(|p| p.matches(block_state)) {
+                rule.apply(&mut info);
+            }
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_block_states() {
+        let blocks = BlockRegistry::new();
+        let info = |name: &str| blocks.get_block_info(BlockState::intern(name));
+
+        assert!(info("minecraft:water[level=0]").water);
+        assert!(info("minecraft:water[level=3]").water);
+        assert!(info("minecraft:bubble_column[drag=true]").water);
+        assert!(info("minecraft:oak_stairs[half=bottom,waterlogged=true]").water);
+        assert!(!info("minecraft:oak_stairs[half=bottom,waterlogged=false]").water);
+        assert!(info("minecraft:lava[level=0]").lava);
+        assert!(info("minecraft:oak_leaves[distance=1,persistent=false]").foliage);
+        assert!(info("minecraft:glass").transparent);
+        assert!(!info("minecraft:stone").transparent);
+        assert!(!info("minecraft:smooth_stone_slab[type=bottom]").spawnable);
+        assert!(info("minecraft:smooth_stone_slab[type=top]").spawnable);
+        assert_eq!(
+            info("minecraft:grass_block[snowy=false]").map_color,
+            0xff_38_b2_7f
+        );
+        assert_eq!(info("mymod:strange_block"), BlockInfo::default());
+
+        let mut custom = BlockRegistry::new();
+        custom
+            .add_rules(r##"[{"blocks": ["mymod:*"], "water": true, "map_color": "#010203"}]"##)
+            .unwrap();
+        let strange = custom.get_block_info(BlockState::intern("mymod:strange_block"));
+        assert!(strange.water);
+        assert_eq!(strange.map_color, 0xff_03_02_01);
+        assert!(custom.add_rules(r#"[{"blocks": ["a[b]"]}]"#).is_err());
+    }
+}
@@ -1,13 +1,225 @@
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-pub struct Block {
-    name: &'static str,
+extern crate glob;
+
+use self::glob::Pattern;
+use super::flattening::flatten_block_name;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Mutex;
+
+pub const UNKNOWN_BLOCK_NAME: &str = "?UNKNOWN_BLOCK?";
+
+/// A block name such as `minecraft:water[level=0]`, split into namespace, name and properties.
+/// Tiles keep one for each of their keys, see `Tile::get_block_state`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BlockState {
+    full_name: String,
+    prefixed_name: String,
+    namespace: String,
+    name: String,
+    properties: BTreeMap<String, String>,
 }
 
-pub const AIR_STR: &'static str = "minecraft:air";
-pub const AIR: Block = Block { name: AIR_STR };
+lazy_static! {
+    /// never freed, there are only a few thousand different block names
+    static ref INTERNED_BLOCK_STATES: Mutex<HashMap<String, &'static BlockState>> =
+        Mutex::new(HashMap::new());
+    static ref UNKNOWN_BLOCK_STATE: &'static BlockState = BlockState::intern(UNKNOWN_BLOCK_NAME);
+}
+
+impl BlockState {
+    /// Names without namespace are in `minecraft`.
+    pub fn parse(block_name: &str) -> Self {
+        let mut parts = block_name.splitn(2, "[");
+        let prefixed_name = parts.next().unwrap();
+        let mut properties = BTreeMap::new();
+        if let Some(props) = parts.next() {
+            for prop in props.trim_end_matches("]").split(",") {
+                if prop.is_empty() {
+                    continue;
+                }
+                let mut kv = prop.splitn(2, "=");
+                let key = kv.next().unwrap().to_string();
+                properties.insert(key, kv.next().unwrap_or("").to_string());
+            }
+        }
+        let (namespace, name) = match prefixed_name.find(":") {
+            Some(colon) => (&prefixed_name[..colon], &prefixed_name[colon + 1..]),
+            None => ("minecraft", prefixed_name),
+        };
+        Self {
+            full_name: block_name.to_string(),
+            prefixed_name: prefixed_name.to_string(),
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            properties,
+        }
+    }
+
+    /// Parses each block name only once, equal names get the same `&BlockState`.
+    pub fn intern(block_name: &str) -> &'static BlockState {
+        let mut interned = INTERNED_BLOCK_STATES.lock().unwrap();
+        if let Some(state) = interned.get(block_name) {
+            return state;
+        }
+        let state: &'static BlockState = Box::leak(Box::new(BlockState::parse(block_name)));
+        interned.insert(block_name.to_string(), state);
+        state
+    }
+
+    /// For blocks whose name is not known, without locking like `intern` does.
+    pub fn unknown() -> &'static BlockState {
+        *UNKNOWN_BLOCK_STATE
+    }
+
+    /// The name as parsed, with properties.
+    pub fn as_str(&self) -> &str {
+        &self.full_name
+    }
+    /// Such as `minecraft:water`, without properties.
+    pub fn prefixed_name(&self) -> &str {
+        &self.prefixed_name
+    }
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+    /// Such as `water`, without namespace and properties.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn properties(&self) -> &BTreeMap<String, String> {
+        &self.properties
+    }
+    pub fn get_property(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(|value| value.as_str())
+    }
+    pub fn has_property(&self, key: &str, value: &str) -> bool {
+        self.get_property(key) == Some(value)
+    }
+    /// Also true for `?` and empty names, which tiles use for ids missing from their key file.
+    pub fn is_unknown(&self) -> bool {
+        self.full_name.is_empty() || self.full_name == "?" || self.full_name == UNKNOWN_BLOCK_NAME
+    }
+}
+
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.full_name)
+    }
+}
+
+/// Matches block names, and optionally some of their properties,
+/// such as `water[level=0]` or `*[waterlogged=true]`.
+/// `*` matches anything, and patterns without namespace match any namespace.
+#[derive(Debug, Clone)]
+pub struct BlockNamePattern {
+    pattern: Pattern,
+    with_namespace: bool,
+    properties: Vec<(String, String)>,
+    pattern_str: String,
+}
 
-const UNKNOWN_BLOCK_NAME: &'static str = "?UNKNOWN_BLOCK?";
-pub const MISSING: Block = Block { name: UNKNOWN_BLOCK_NAME };
+impl BlockNamePattern {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        if pattern.is_empty() {
+            return Err("Empty block pattern".to_string());
+        }
+        let mut parts = pattern.splitn(2, "[");
+        let name_pattern = parts.next().unwrap();
+        let mut properties = Vec::new();
+        if let Some(props) = parts.next() {
+            if !props.ends_with("]") {
+                return Err(format!("{:?} has no closing ]", pattern));
+            }
+            for prop in props.trim_end_matches("]").split(",") {
+                let mut kv = prop.splitn(2, "=");
+                match (kv.next(), kv.next()) {
+                    (Some(key), Some(value)) if !key.is_empty() => {
+                        properties.push((key.to_string(), value.to_string()))
+                    }
+                    _ => {
+                        return Err(format!(
+                            "{:?} property {:?} is not key=value",
+                            pattern, prop
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(Self {
+            pattern: Pattern::new(name_pattern).map_err(|e| format!("{:?} {}", pattern, e))?,
+            with_namespace: name_pattern.contains(":"),
+            properties,
+            pattern_str: pattern.to_string(),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern_str
+    }
+
+    /// Properties not in the pattern are ignored.
+    pub fn matches(&self, block_state: &BlockState) -> bool {
+        let name_matches = if self.with_namespace {
+            self.pattern.matches(block_state.prefixed_name())
+        } else {
+            self.pattern.matches(block_state.name())
+        };
+        name_matches
+            && self
+                .properties
+                .iter()
+                .all(|(key, value)| block_state.has_property(key, value))
+    }
+}
+
+lazy_static! {
+    /// full name with properties, and also just the name, to `meta << 12 | id`
+    static ref LEGACY_BLOCK_STATES: HashMap<&'static str, u16> = {
+        let mut states = HashMap::new();
+        for (index, name) in BLOCK_STRINGS_ARR.iter().enumerate().rev() {
+            if *name == UNKNOWN_BLOCK_NAME {
+                continue;
+            }
+            let state = (index >> 4 | (index & 0xf) << 12) as u16;
+            states.insert(*name, state);
+            states.insert(name.split("[").next().unwrap(), state);
+        }
+        states
+    };
+
+    /// like `LEGACY_BLOCK_STATES`, by the flattened names
+    static ref FLATTENED_BLOCK_STATES: HashMap<String, u16> = {
+        let mut states = HashMap::new();
+        for (index, name) in BLOCK_STRINGS_ARR.iter().enumerate().rev() {
+            if *name == UNKNOWN_BLOCK_NAME {
+                continue;
+            }
+            let state = (index >> 4 | (index & 0xf) << 12) as u16;
+            let flattened = flatten_block_name(name).unwrap_or_else(|| name.to_string());
+            states.insert(flattened.split("[").next().unwrap().to_string(), state);
+            states.insert(flattened, state);
+        }
+        states
+    };
+}
+
+/// The legacy (pre-1.13) block state `meta << 12 | id` of a block name such as
+/// `minecraft:stone[variant=granite]`, as used in VoxelMap's keys.
+/// Modern names such as `minecraft:granite` are converted back.
+/// Unknown properties fall back to the block's first state.
+pub fn get_legacy_block_state(block_name: &str) -> Option<u16> {
+    LEGACY_BLOCK_STATES
+        .get(block_name)
+        .or_else(|| {
+            let flattened = flatten_block_name(block_name);
+            let flattened = flattened.as_ref().map_or(block_name, |name| name.as_str());
+            FLATTENED_BLOCK_STATES
+                .get(flattened)
+                .or_else(|| FLATTENED_BLOCK_STATES.get(flattened.split("[").next().unwrap()))
+        })
+        .or_else(|| LEGACY_BLOCK_STATES.get(block_name.split("[").next().unwrap()))
+        .cloned()
+}
 
 /*
 indices are id << 4 | meta
@@ -20,7 +232,7 @@ with open('test.rs','w') as f:
         print('"%s",' % (UNKNOWN_BLOCK_NAME if i not in d else "minecraft:"+d[i]), file=f)
 ```
 */
-pub const BLOCK_STRINGS_ARR: [&'static str; 4096] = [
+pub static BLOCK_STRINGS_ARR: [&str; 4096] = [
 "minecraft:air",
 "minecraft:air",
 "minecraft:air",
@@ -4118,3 +4330,29 @@ UNKNOWN_BLOCK_NAME,
 UNKNOWN_BLOCK_NAME,
 UNKNOWN_BLOCK_NAME,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_block_states_by_properties() {
+        let water = BlockState::intern("minecraft:water[level=0]");
+        assert!(std::ptr::eq(
+            water,
+            BlockState::intern("minecraft:water[level=0]")
+        ));
+        assert_eq!(water.namespace(), "minecraft");
+        assert_eq!(water.name(), "water");
+        assert_eq!(water.get_property("level"), Some("0"));
+
+        let stairs = BlockState::parse("minecraft:oak_stairs[facing=east,waterlogged=true]");
+        let pattern = |p: &str| BlockNamePattern::new(p).unwrap();
+        assert!(pattern("water[level=0]").matches(water));
+        assert!(!pattern("water[level=1]").matches(water));
+        assert!(pattern("*[waterlogged=true]").matches(&stairs));
+        assert!(!pattern("*[waterlogged=true]").matches(water));
+        assert!(pattern("minecraft:*_stairs[facing=east,waterlogged=true]").matches(&stairs));
+        assert!(BlockNamePattern::new("water[level]").is_err());
+    }
+}
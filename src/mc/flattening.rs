@@ -0,0 +1,532 @@
+//! Conversion of legacy (pre-1.13) block names such as `minecraft:stone[variant=granite]`
+//! to the modern ("flattened") ones such as `minecraft:granite`,
+//! similar to what Minecraft does when upgrading a world to 1.13.
+//! Names reused by later versions are also converted to their current name.
+
+use super::blocks::BlockState;
+use std::collections::{BTreeMap, HashMap};
+
+/// `(legacy name, conditions, modern name)`, the first rule with matching conditions applies.
+///
+/// Conditions are separated by commas:
+/// `prop=value` must match and is removed, `prop=*` must exist and is removed,
+/// `prop` must exist and is kept, `!prop` must not exist, `-prop` is removed if present.
+/// Properties of the modern name are added.
+/// Remaining properties are kept, see `convert_properties` for the ones that changed everywhere.
+const FLATTENING_RULES: &[(&str, &str, &str)] = &[
+    ("stone", "variant=stone", "stone"),
+    ("stone", "variant=granite", "granite"),
+    ("stone", "variant=smooth_granite", "polished_granite"),
+    ("stone", "variant=diorite", "diorite"),
+    ("stone", "variant=smooth_diorite", "polished_diorite"),
+    ("stone", "variant=andesite", "andesite"),
+    ("stone", "variant=smooth_andesite", "polished_andesite"),
+    // grass blocks until 1.13, then the plant until 1.20.3
+    ("grass", "snowy", "grass_block"),
+    ("grass", "!snowy", "short_grass"),
+    ("dirt", "variant=dirt,-snowy", "dirt"),
+    ("dirt", "variant=coarse_dirt,-snowy", "coarse_dirt"),
+    ("dirt", "variant=podzol", "podzol"),
+    ("sand", "variant=sand", "sand"),
+    ("sand", "variant=red_sand", "red_sand"),
+    ("sponge", "wet=false", "sponge"),
+    ("sponge", "wet=true", "wet_sponge"),
+    ("sandstone", "type=sandstone", "sandstone"),
+    ("sandstone", "type=chiseled_sandstone", "chiseled_sandstone"),
+    ("sandstone", "type=smooth_sandstone", "cut_sandstone"),
+    ("red_sandstone", "type=red_sandstone", "red_sandstone"),
+    (
+        "red_sandstone",
+        "type=chiseled_red_sandstone",
+        "chiseled_red_sandstone",
+    ),
+    (
+        "red_sandstone",
+        "type=smooth_red_sandstone",
+        "cut_red_sandstone",
+    ),
+    ("stonebrick", "variant=stonebrick", "stone_bricks"),
+    (
+        "stonebrick",
+        "variant=mossy_stonebrick",
+        "mossy_stone_bricks",
+    ),
+    (
+        "stonebrick",
+        "variant=cracked_stonebrick",
+        "cracked_stone_bricks",
+    ),
+    (
+        "stonebrick",
+        "variant=chiseled_stonebrick",
+        "chiseled_stone_bricks",
+    ),
+    ("monster_egg", "variant=stone", "infested_stone"),
+    ("monster_egg", "variant=cobblestone", "infested_cobblestone"),
+    (
+        "monster_egg",
+        "variant=stone_brick",
+        "infested_stone_bricks",
+    ),
+    (
+        "monster_egg",
+        "variant=mossy_brick",
+        "infested_mossy_stone_bricks",
+    ),
+    (
+        "monster_egg",
+        "variant=cracked_brick",
+        "infested_cracked_stone_bricks",
+    ),
+    (
+        "monster_egg",
+        "variant=chiseled_brick",
+        "infested_chiseled_stone_bricks",
+    ),
+    ("prismarine", "variant=prismarine", "prismarine"),
+    (
+        "prismarine",
+        "variant=prismarine_bricks",
+        "prismarine_bricks",
+    ),
+    ("prismarine", "variant=dark_prismarine", "dark_prismarine"),
+    ("quartz_block", "variant=default", "quartz_block"),
+    ("quartz_block", "variant=chiseled", "chiseled_quartz_block"),
+    ("quartz_block", "variant=lines", "quartz_pillar[axis=y]"),
+    ("stone_slab", "variant=stone", "smooth_stone_slab"),
+    ("stone_slab", "variant=sandstone", "sandstone_slab"),
+    ("stone_slab", "variant=wood_old", "petrified_oak_slab"),
+    ("stone_slab", "variant=cobblestone", "cobblestone_slab"),
+    ("stone_slab", "variant=brick", "brick_slab"),
+    ("stone_slab", "variant=stone_brick", "stone_brick_slab"),
+    ("stone_slab", "variant=nether_brick", "nether_brick_slab"),
+    ("stone_slab", "variant=quartz", "quartz_slab"),
+    (
+        "double_stone_slab",
+        "variant=stone,seamless=true",
+        "smooth_stone",
+    ),
+    (
+        "double_stone_slab",
+        "variant=sandstone,seamless=true",
+        "smooth_sandstone",
+    ),
+    (
+        "double_stone_slab",
+        "variant=quartz,seamless=true",
+        "smooth_quartz",
+    ),
+    (
+        "double_stone_slab",
+        "variant=stone,-seamless",
+        "smooth_stone_slab[type=double]",
+    ),
+    (
+        "double_stone_slab",
+        "variant=sandstone,-seamless",
+        "sandstone_slab[type=double]",
+    ),
+    (
+        "double_stone_slab",
+        "variant=wood_old,-seamless",
+        "petrified_oak_slab[type=double]",
+    ),
+    (
+        "double_stone_slab",
+        "variant=cobblestone,-seamless",
+        "cobblestone_slab[type=double]",
+    ),
+    (
+        "double_stone_slab",
+        "variant=brick,-seamless",
+        "brick_slab[type=double]",
+    ),
+    (
+        "double_stone_slab",
+        "variant=stone_brick,-seamless",
+        "stone_brick_slab[type=double]",
+    ),
+    (
+        "double_stone_slab",
+        "variant=nether_brick,-seamless",
+        "nether_brick_slab[type=double]",
+    ),
+    (
+        "double_stone_slab",
+        "variant=quartz,-seamless",
+        "quartz_slab[type=double]",
+    ),
+    ("stone_slab2", "variant=red_sandstone", "red_sandstone_slab"),
+    (
+        "double_stone_slab2",
+        "variant=red_sandstone,seamless=true",
+        "smooth_red_sandstone",
+    ),
+    (
+        "double_stone_slab2",
+        "variant=red_sandstone,-seamless",
+        "red_sandstone_slab[type=double]",
+    ),
+    ("purpur_slab", "variant=*", "purpur_slab"),
+    (
+        "purpur_double_slab",
+        "variant=*",
+        "purpur_slab[type=double]",
+    ),
+    (
+        "cobblestone_wall",
+        "variant=cobblestone",
+        "cobblestone_wall",
+    ),
+    (
+        "cobblestone_wall",
+        "variant=mossy_cobblestone",
+        "mossy_cobblestone_wall",
+    ),
+    ("anvil", "damage=0", "anvil"),
+    ("anvil", "damage=1", "chipped_anvil"),
+    ("anvil", "damage=2", "damaged_anvil"),
+    ("brown_mushroom_block", "variant=all_stem", "mushroom_stem"),
+    ("brown_mushroom_block", "variant=stem", "mushroom_stem"),
+    ("brown_mushroom_block", "variant=*", "brown_mushroom_block"),
+    ("red_mushroom_block", "variant=all_stem", "mushroom_stem"),
+    ("red_mushroom_block", "variant=stem", "mushroom_stem"),
+    ("red_mushroom_block", "variant=*", "red_mushroom_block"),
+    ("tallgrass", "type=tall_grass", "short_grass"),
+    ("tallgrass", "type=fern", "fern"),
+    ("tallgrass", "type=dead_bush", "dead_bush"),
+    ("deadbush", "", "dead_bush"),
+    ("yellow_flower", "type=dandelion", "dandelion"),
+    ("red_flower", "type=poppy", "poppy"),
+    ("red_flower", "type=blue_orchid", "blue_orchid"),
+    ("red_flower", "type=allium", "allium"),
+    ("red_flower", "type=houstonia", "azure_bluet"),
+    ("red_flower", "type=red_tulip", "red_tulip"),
+    ("red_flower", "type=orange_tulip", "orange_tulip"),
+    ("red_flower", "type=white_tulip", "white_tulip"),
+    ("red_flower", "type=pink_tulip", "pink_tulip"),
+    ("red_flower", "type=oxeye_daisy", "oxeye_daisy"),
+    ("double_plant", "variant=sunflower,-facing", "sunflower"),
+    ("double_plant", "variant=syringa,-facing", "lilac"),
+    ("double_plant", "variant=double_grass,-facing", "tall_grass"),
+    ("double_plant", "variant=double_fern,-facing", "large_fern"),
+    ("double_plant", "variant=double_rose,-facing", "rose_bush"),
+    ("double_plant", "variant=paeonia,-facing", "peony"),
+    ("torch", "facing=up", "torch"),
+    ("torch", "facing", "wall_torch"),
+    ("redstone_torch", "facing=up", "redstone_torch[lit=true]"),
+    ("redstone_torch", "facing", "redstone_wall_torch[lit=true]"),
+    (
+        "unlit_redstone_torch",
+        "facing=up",
+        "redstone_torch[lit=false]",
+    ),
+    (
+        "unlit_redstone_torch",
+        "facing",
+        "redstone_wall_torch[lit=false]",
+    ),
+    ("skull", "facing=up", "skeleton_skull"),
+    ("skull", "facing", "skeleton_wall_skull"),
+    ("melon_stem", "facing=up", "melon_stem"),
+    ("melon_stem", "facing,-age", "attached_melon_stem"),
+    ("pumpkin_stem", "facing=up", "pumpkin_stem"),
+    ("pumpkin_stem", "facing,-age", "attached_pumpkin_stem"),
+    // pumpkins got their face in 1.13
+    ("pumpkin", "facing", "carved_pumpkin"),
+    ("lit_pumpkin", "", "jack_o_lantern"),
+    ("furnace", "!lit", "furnace[lit=false]"),
+    ("lit_furnace", "", "furnace[lit=true]"),
+    ("redstone_ore", "!lit", "redstone_ore[lit=false]"),
+    ("lit_redstone_ore", "", "redstone_ore[lit=true]"),
+    ("redstone_lamp", "!lit", "redstone_lamp[lit=false]"),
+    ("lit_redstone_lamp", "", "redstone_lamp[lit=true]"),
+    ("unpowered_repeater", "", "repeater[powered=false]"),
+    ("powered_repeater", "", "repeater[powered=true]"),
+    ("unpowered_comparator", "", "comparator"),
+    ("powered_comparator", "", "comparator"),
+    (
+        "daylight_detector",
+        "!inverted",
+        "daylight_detector[inverted=false]",
+    ),
+    (
+        "daylight_detector_inverted",
+        "",
+        "daylight_detector[inverted=true]",
+    ),
+    ("flowing_water", "", "water"),
+    ("flowing_lava", "", "lava"),
+    // snow layers were just "snow" in 1.13
+    ("snow", "!layers", "snow_block"),
+    ("snow_layer", "", "snow"),
+    // 1.14 added stone stairs, which have waterlogged
+    ("stone_stairs", "!waterlogged", "cobblestone_stairs"),
+    ("standing_sign", "", "oak_sign"),
+    ("sign", "", "oak_sign"),
+    ("wall_sign", "", "oak_wall_sign"),
+    ("wooden_door", "", "oak_door"),
+    ("fence", "", "oak_fence"),
+    ("fence_gate", "", "oak_fence_gate"),
+    ("trapdoor", "", "oak_trapdoor"),
+    ("wooden_button", "", "oak_button"),
+    ("wooden_pressure_plate", "", "oak_pressure_plate"),
+    ("bed", "", "red_bed"),
+    ("standing_banner", "", "white_banner"),
+    ("wall_banner", "", "white_wall_banner"),
+    ("piston_extension", "", "moving_piston"),
+    ("golden_rail", "", "powered_rail"),
+    ("noteblock", "", "note_block"),
+    ("web", "", "cobweb"),
+    ("reeds", "", "sugar_cane"),
+    ("waterlily", "", "lily_pad"),
+    ("mob_spawner", "", "spawner"),
+    ("melon_block", "", "melon"),
+    ("portal", "", "nether_portal"),
+    ("brick_block", "", "bricks"),
+    ("nether_brick", "", "nether_bricks"),
+    ("red_nether_brick", "", "red_nether_bricks"),
+    ("end_bricks", "", "end_stone_bricks"),
+    ("quartz_ore", "", "nether_quartz_ore"),
+    ("hardened_clay", "", "terracotta"),
+    ("magma", "", "magma_block"),
+    ("slime", "", "slime_block"),
+    (
+        "silver_glazed_terracotta",
+        "",
+        "light_gray_glazed_terracotta",
+    ),
+    ("silver_shulker_box", "", "light_gray_shulker_box"),
+    // renamed in 1.17
+    ("grass_path", "", "dirt_path"),
+];
+
+/// legacy `color` property values to modern name prefixes
+const COLORS: [(&str, &str); 16] = [
+    ("white", "white"),
+    ("orange", "orange"),
+    ("magenta", "magenta"),
+    ("lightBlue", "light_blue"),
+    ("yellow", "yellow"),
+    ("lime", "lime"),
+    ("pink", "pink"),
+    ("gray", "gray"),
+    ("silver", "light_gray"),
+    ("cyan", "cyan"),
+    ("purple", "purple"),
+    ("blue", "blue"),
+    ("brown", "brown"),
+    ("green", "green"),
+    ("red", "red"),
+    ("black", "black"),
+];
+
+/// legacy blocks with a `color` property, and the modern name without the color
+const COLORED_BLOCKS: [(&str, &str); 7] = [
+    ("wool", "wool"),
+    ("carpet", "carpet"),
+    ("stained_glass", "stained_glass"),
+    ("stained_glass_pane", "stained_glass_pane"),
+    ("stained_hardened_clay", "terracotta"),
+    ("concrete", "concrete"),
+    ("concrete_powder", "concrete_powder"),
+];
+
+const WOOD_TYPES: [&str; 6] = ["oak", "spruce", "birch", "jungle", "acacia", "dark_oak"];
+
+/// legacy blocks with the wood type in a property, and the modern name without the wood type
+const WOODEN_BLOCKS: [(&str, &str, &str); 8] = [
+    ("planks", "variant", "planks"),
+    ("sapling", "type", "sapling"),
+    ("leaves", "variant", "leaves"),
+    ("leaves2", "variant", "leaves"),
+    ("log", "variant", "log"),
+    ("log2", "variant", "log"),
+    ("wooden_slab", "variant", "slab"),
+    ("double_wooden_slab", "variant", "slab[type=double]"),
+];
+
+lazy_static! {
+    /// legacy name without namespace to `(conditions, modern name)`
+    static ref RULES_BY_NAME: HashMap<&'static str, Vec<(String, String)>> = {
+        let mut rules: HashMap<&'static str, Vec<(String, String)>> = HashMap::new();
+        for (legacy, conditions, modern) in FLATTENING_RULES.iter() {
+            rules
+                .entry(legacy)
+                .or_default()
+                .push((conditions.to_string(), modern.to_string()));
+        }
+        for (legacy, modern) in COLORED_BLOCKS.iter() {
+            for (color, prefix) in COLORS.iter() {
+                rules
+                    .entry(legacy)
+                    .or_default()
+                    .push((format!("color={}", color), format!("{}_{}", prefix, modern)));
+            }
+        }
+        for (legacy, property, modern) in WOODEN_BLOCKS.iter() {
+            let legacy_rules = rules.entry(legacy).or_default();
+            for wood in WOOD_TYPES.iter() {
+                if legacy.starts_with("log") {
+                    // bark on all sides
+                    legacy_rules.push((
+                        format!("{}={},axis=none", property, wood),
+                        format!("{}_wood[axis=y]", wood),
+                    ));
+                }
+                legacy_rules.push((
+                    format!("{}={}", property, wood),
+                    format!("{}_{}", wood, modern),
+                ));
+            }
+        }
+        rules
+    };
+}
+
+type Properties = BTreeMap<String, String>;
+
+/// The properties after applying the conditions, `None` if they don't match.
+fn apply_conditions(conditions: &str, properties: &Properties) -> Option<Properties> {
+    let mut properties = properties.clone();
+    for condition in conditions.split(",").filter(|c| !c.is_empty()) {
+        if let Some(key) = condition.strip_prefix("!") {
+            if properties.contains_key(key) {
+                return None;
+            }
+        } else if let Some(key) = condition.strip_prefix("-") {
+            properties.remove(key);
+        } else if let Some(eq) = condition.find("=") {
+            let (key, value) = (&condition[..eq], &condition[eq + 1..]);
+            match properties.remove(key) {
+                Some(ref v) if value == "*" || v == value => {}
+                _ => return None,
+            }
+        } else if !properties.contains_key(condition) {
+            return None;
+        }
+    }
+    Some(properties)
+}
+
+/// Properties that changed the same way for all blocks.
+fn convert_properties(name: &str, properties: Properties) -> Properties {
+    let mut converted = Properties::new();
+    for (key, value) in properties {
+        match key.as_str() {
+            "check_decay" | "nodrop" | "legacy_data" | "contents" => {}
+            "decayable" => {
+                let persistent = if value == "true" { "false" } else { "true" };
+                converted.insert("persistent".to_string(), persistent.to_string());
+            }
+            "explode" => {
+                converted.insert("unstable".to_string(), value);
+            }
+            "half" if name.ends_with("_slab") => {
+                converted.insert("type".to_string(), value);
+            }
+            _ => {
+                converted.insert(key, value);
+            }
+        }
+    }
+    converted
+}
+
+/// The modern name of a legacy block name, for example
+/// `minecraft:log[axis=y,variant=birch]` becomes `minecraft:birch_log[axis=y]`.
+/// Properties are sorted by name, like Minecraft does.
+/// `None` if the name is already modern, or is not from the `minecraft` namespace.
+pub fn flatten_block_name(block_name: &str) -> Option<String> {
+    if !block_name.starts_with("minecraft:") {
+        return None;
+    }
+    let legacy = BlockState::parse(block_name);
+
+    let rules = RULES_BY_NAME
+        .get(legacy.name())
+        .map_or(&[][..], |rules| &rules[..]);
+    let matching_rule = rules.iter().find_map(|(conditions, modern)| {
+        apply_conditions(conditions, legacy.properties())
+            .map(|remaining| (BlockState::parse(modern), remaining))
+    });
+    let (modern_name, modern_properties) = match matching_rule {
+        Some((ref modern, ref remaining)) => {
+            let mut properties = remaining.clone();
+            for (key, value) in modern.properties() {
+                properties.insert(key.clone(), value.clone());
+            }
+            (modern.name().to_string(), properties)
+        }
+        None => (legacy.name().to_string(), legacy.properties().clone()),
+    };
+    let modern_properties = convert_properties(&modern_name, modern_properties);
+
+    let mut flattened = format!("minecraft:{}", modern_name);
+    if !modern_properties.is_empty() {
+        let properties: Vec<String> = modern_properties
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        flattened = format!("{}[{}]", flattened, properties.join(","));
+    }
+    if flattened == block_name {
+        None
+    } else {
+        Some(flattened)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flattens_legacy_names() {
+        let flat = |name: &str| flatten_block_name(name).unwrap_or_else(|| name.to_string());
+        assert_eq!(
+            flat("minecraft:stone[variant=smooth_granite]"),
+            "minecraft:polished_granite"
+        );
+        assert_eq!(
+            flat("minecraft:grass[snowy=false]"),
+            "minecraft:grass_block[snowy=false]"
+        );
+        assert_eq!(flat("minecraft:grass"), "minecraft:short_grass");
+        assert_eq!(
+            flat("minecraft:wool[color=lightBlue]"),
+            "minecraft:light_blue_wool"
+        );
+        assert_eq!(
+            flat("minecraft:log2[axis=none,variant=dark_oak]"),
+            "minecraft:dark_oak_wood[axis=y]"
+        );
+        assert_eq!(
+            flat("minecraft:leaves[check_decay=true,decayable=true,variant=birch]"),
+            "minecraft:birch_leaves[persistent=false]"
+        );
+        assert_eq!(
+            flat("minecraft:stone_slab[half=top,variant=stone_brick]"),
+            "minecraft:stone_brick_slab[type=top]"
+        );
+        assert_eq!(
+            flat("minecraft:double_wooden_slab[variant=acacia]"),
+            "minecraft:acacia_slab[type=double]"
+        );
+        assert_eq!(
+            flat("minecraft:torch[facing=east]"),
+            "minecraft:wall_torch[facing=east]"
+        );
+        assert_eq!(flat("minecraft:snow"), "minecraft:snow_block");
+        assert_eq!(
+            flat("minecraft:snow_layer[layers=2]"),
+            "minecraft:snow[layers=2]"
+        );
+
+        // modern and modded names stay
+        assert_eq!(flatten_block_name("minecraft:snow[layers=2]"), None);
+        assert_eq!(flatten_block_name("minecraft:stone"), None);
+        assert_eq!(flatten_block_name("minecraft:water[level=0]"), None);
+        assert_eq!(flatten_block_name("mymod:wool[color=red]"), None);
+    }
+}
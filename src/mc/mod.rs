@@ -1,2 +1,5 @@
+pub mod biomes;
+pub mod block_registry;
 pub mod blocks;
+pub mod flattening;
 pub mod packet;
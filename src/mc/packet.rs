@@ -58,8 +58,8 @@ pub enum McPacket {
 }
 
 impl McPacket {
-    pub fn decode(data: &Vec<u8>) -> Result<McPacket, BufErr> {
-        let mut data = BufReader::new(data.clone()); // TODO operate on data ref directly, no clone
+    pub fn decode(data: &[u8]) -> Result<McPacket, BufErr> {
+        let mut data = BufReader::new(data.to_vec()); // TODO operate on data ref directly, no clone
         match data.read_u8()? {
             0x05 => {
                 Ok(McPacket::SpawnPlayer {
@@ -0,0 +1,296 @@
+extern crate serde_json;
+
+use crate::ccnatural::{Naturality, CCNATURAL_COLORS_BLOCK_BIOME, CCNATURAL_COLORS_BLOCK_DEFAULT};
+use crate::mc::biomes::{get_biome_by_legacy_id, get_biome_by_name, Biome, BiomeCategory, BIOMES};
+use crate::mc::blocks::{BlockNamePattern, BlockState};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, RwLock};
+
+/// `natural`, `pond`, `exploited`, `planted`, `built` or `unknown`, any case.
+/// `x` is `unknown`, as in the categories TSV.
+pub fn parse_naturality(name: &str) -> Result<Naturality, String> {
+    match name.trim().to_lowercase().as_str() {
+        "natural" => Ok(Naturality::Natural),
+        "pond" => Ok(Naturality::Pond),
+        "exploited" => Ok(Naturality::Exploited),
+        "planted" => Ok(Naturality::Planted),
+        "built" => Ok(Naturality::Built),
+        "unknown" | "x" => Ok(Naturality::Unknown),
+        _ => Err(format!("Unknown naturality {:?}", name)),
+    }
+}
+
+/// Which biomes a naturality rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BiomeSelector {
+    /// `*` or empty
+    Any,
+    /// such as `#forest`, see `BiomeCategory::name`
+    Category(BiomeCategory),
+    /// legacy id such as `4`, or name such as `forest` or `minecraft:forest`
+    Biome(&'static Biome),
+}
+
+impl BiomeSelector {
+    pub fn parse(selector: &str) -> Result<Self, String> {
+        let selector = selector.trim();
+        if selector.is_empty() || selector == "*" {
+            return Ok(BiomeSelector::Any);
+        }
+        if let Some(category_name) = selector.strip_prefix("#") {
+            return BIOMES
+                .iter()
+                .map(|biome| biome.category)
+                .find(|category| category.name() == category_name)
+                .map(BiomeSelector::Category)
+                .ok_or_else(|| format!("Unknown biome category {:?}", selector));
+        }
+        let biome = match selector.parse::<u16>() {
+            Ok(biome_id) => get_biome_by_legacy_id(biome_id),
+            Err(_) => get_biome_by_name(selector),
+        };
+        biome
+            .map(BiomeSelector::Biome)
+            .ok_or_else(|| format!("Unknown biome {:?}", selector))
+    }
+
+    /// Unknown biomes are only matched by `Any`.
+    pub fn matches(&self, biome: Option<&'static Biome>) -> bool {
+        match (self, biome) {
+            (BiomeSelector::Any, _) => true,
+            (BiomeSelector::Category(category), Some(biome)) => biome.category == *category,
+            (BiomeSelector::Biome(selected), Some(biome)) => selected.name == biome.name,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NaturalityRule {
+    block: BlockNamePattern,
+    biome: BiomeSelector,
+    naturality: Naturality,
+}
+
+/// One entry of a JSON rules file, see `NaturalityRules::add_json_rules`.
+#[derive(Debug, Deserialize)]
+struct NaturalityRuleFile {
+    blocks: Vec<String>,
+    biomes: Option<Vec<String>>,
+    naturality: String,
+}
+
+/// `&'static BlockState` address and biome name
+type CacheKey = (usize, Option<&'static str>);
+
+/// Classifies blocks as natural, built and so on, depending on the biome they are in.
+/// Loaded rules come first, blocks matched by none of them are looked up in the
+/// tables of `ccnatural.rs`.
+/// Blocks not found anywhere are `Unknown`, and remembered for `unclassified_blocks`.
+pub struct NaturalityRules {
+    /// later rules take precedence
+    rules: Vec<NaturalityRule>,
+    cache: RwLock<HashMap<CacheKey, Option<Naturality>>>,
+    /// block name to number of lookups
+    unclassified: Mutex<BTreeMap<String, usize>>,
+}
+
+impl Default for NaturalityRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NaturalityRules {
+    /// Only the compiled tables.
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            cache: RwLock::new(HashMap::new()),
+            unclassified: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// `.json` files are read with `add_json_rules`, anything else with `add_tsv_rules`.
+    pub fn read(&mut self, path: &Path) -> Result<(), String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{:?} {}", path, e))?;
+        let is_json = path.extension().map_or(false, |ext| ext == "json");
+        let result = if is_json {
+            self.add_json_rules(&text)
+        } else {
+            self.add_tsv_rules(&text)
+        };
+        result.map_err(|e| format!("{:?} {}", path, e))
+    }
+
+    /// Tab separated, with a header line, in the format of `blockcounts-categories.tsv`:
+    /// biome (see `BiomeSelector`), biome name (ignored), block pattern
+    /// (see `BlockNamePattern`) and naturality, further columns are ignored.
+    /// For example, with tabs between the columns:
+    ///
+    /// ```text
+    /// biomeid  biome   block      category
+    /// *                *_planks   built
+    /// #ocean           sand       natural
+    /// 2        Desert  sandstone  natural
+    /// ```
+    pub fn add_tsv_rules(&mut self, tsv: &str) -> Result<(), String> {
+        for (line_nr, line) in tsv.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let columns: Vec<&str> = line.split('\t').collect();
+            let rule =
+                parse_tsv_rule(&columns).map_err(|e| format!("line {}: {}", line_nr + 1, e))?;
+            self.add_rule(rule);
+        }
+        Ok(())
+    }
+
+    /// A list of rules, `biomes` may be left out to match any biome.
+    ///
+    /// ```json
+    /// [
+    ///   {"blocks": ["*_planks", "*_door"], "naturality": "built"},
+    ///   {"blocks": ["sand"], "biomes": ["#ocean", "#beach"], "naturality": "natural"}
+    /// ]
+    /// ```
+    pub fn add_json_rules(&mut self, json: &str) -> Result<(), String> {
+        let file: Vec<NaturalityRuleFile> =
+            serde_json::from_str(json).map_err(|e| e.to_string())?;
+        for rule in file {
+            let naturality = parse_naturality(&rule.naturality)?;
+            let biomes = rule.biomes.unwrap_or_else(|| vec!["*".to_string()]);
+            for block in &rule.blocks {
+                for biome in &biomes {
+                    self.add_rule(NaturalityRule {
+                        block: BlockNamePattern::new(block)?,
+                        biome: BiomeSelector::parse(biome)?,
+                        naturality,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_rule(&mut self, rule: NaturalityRule) {
+        self.rules.push(rule);
+        self.cache.write().unwrap().clear();
+    }
+
+    pub fn get_naturality(
+        &self,
+        block_state: &'static BlockState,
+        biome: Option<&'static Biome>,
+    ) -> Naturality {
+        let key = (
+            block_state as *const BlockState as usize,
+            biome.map(|biome| biome.name),
+        );
+        let cached = self.cache.read().unwrap().get(&key).cloned();
+        let naturality = cached.unwrap_or_else(|| {
+            let naturality = self.classify(block_state, biome);
+            self.cache.write().unwrap().insert(key, naturality);
+            naturality
+        });
+        naturality.unwrap_or_else(|| {
+            let mut unclassified = self.unclassified.lock().unwrap();
+            *unclassified
+                .entry(block_state.prefixed_name().to_string())
+                .or_insert(0) += 1;
+            Naturality::Unknown
+        })
+    }
+
+    fn classify(
+        &self,
+        block_state: &'static BlockState,
+        biome: Option<&'static Biome>,
+    ) -> Option<Naturality> {
+        let rule = self
+            .rules
+            .iter()
+            .rev()
+            .find(|rule| rule.biome.matches(biome) && rule.block.matches(block_state));
+        if let Some(rule) = rule {
+            return Some(rule.naturality);
+        }
+        // the per-biome tables are by legacy id, biomes added since 1.18 only get the defaults
+        let block_name = block_state.name();
+        biome
+            .and_then(|biome| biome.legacy_id)
+            .and_then(|biome_id| CCNATURAL_COLORS_BLOCK_BIOME.get(&(block_name, biome_id)))
+            .or_else(|| CCNATURAL_COLORS_BLOCK_DEFAULT.get(&block_name))
+            .cloned()
+    }
+
+    /// Blocks no rule applied to so far, with how often they were looked up, most frequent first.
+    pub fn unclassified_blocks(&self) -> Vec<(String, usize)> {
+        let mut blocks: Vec<(String, usize)> = self
+            .unclassified
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, count)| (name.clone(), *count))
+            .collect();
+        blocks.sort_by(|(_, a), (_, b)| b.cmp(a));
+        blocks
+    }
+}
+
+fn parse_tsv_rule(columns: &[&str]) -> Result<NaturalityRule, String> {
+    if columns.len() < 4 {
+        return Err("expected biome, biome name, block and naturality".to_string());
+    }
+    Ok(NaturalityRule {
+        block: BlockNamePattern::new(columns[2].trim())?,
+        biome: BiomeSelector::parse(columns[0])?,
+        naturality: parse_naturality(columns[3])?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loaded_rules_override_tables() {
+        let mut rules = NaturalityRules::new();
+        let forest = get_biome_by_name("forest");
+        let ocean = get_biome_by_name("ocean");
+        let planks = BlockState::intern("minecraft:oak_planks");
+        let sand = BlockState::intern("minecraft:sand");
+        let strange = BlockState::intern("mymod:strange_block");
+        assert_eq!(rules.get_naturality(planks, forest), Naturality::Built);
+
+        rules
+            .add_tsv_rules(
+                "biomeid\tbiome\tblock\tcategory\n*\t\t*_planks\tx\n#ocean\t\tsand\tbuilt\n",
+            )
+            .unwrap();
+        rules
+            .add_json_rules(
+                r#"[{"blocks": ["mymod:*"], "biomes": ["4"], "naturality": "planted"}]"#,
+            )
+            .unwrap();
+        assert_eq!(rules.get_naturality(planks, forest), Naturality::Unknown);
+        assert_eq!(rules.get_naturality(sand, ocean), Naturality::Built);
+        assert_ne!(rules.get_naturality(sand, forest), Naturality::Built);
+        assert_eq!(rules.get_naturality(strange, forest), Naturality::Planted);
+        assert!(rules.unclassified_blocks().is_empty());
+
+        assert_eq!(rules.get_naturality(strange, ocean), Naturality::Unknown);
+        assert_eq!(
+            rules.unclassified_blocks(),
+            vec![("mymod:strange_block".to_string(), 1)]
+        );
+        assert!(rules.add_tsv_rules("header\n*\t\tsand\n").is_err());
+        assert!(rules
+            .add_tsv_rules("header\n#nowhere\t\tsand\tnatural\n")
+            .is_err());
+    }
+}
@@ -0,0 +1,291 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::collections::HashMap;
+use std::io::{self, Read};
+
+/// A tag of Minecraft's Named Binary Tag format, as in `level.dat` and the chunks of region files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    End,
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<u8>),
+    String(String),
+    List(Vec<Tag>),
+    Compound(HashMap<String, Tag>),
+    IntArray(Vec<i32>),
+    /// since 1.13, for block states and height maps
+    LongArray(Vec<i64>),
+}
+
+pub fn get_key<'a>(compound: &'a Tag, key: &str) -> Result<&'a Tag, String> {
+    match compound {
+        Tag::Compound(map) => map.get(key).ok_or(format!("missing {}", key)),
+        _ => Err(format!("not a compound, looking for {}", key)),
+    }
+}
+
+pub fn get_byte(compound: &Tag, key: &str) -> Result<i8, String> {
+    match get_key(compound, key)? {
+        Tag::Byte(value) => Ok(*value),
+        _ => Err(format!("{} is not a byte", key)),
+    }
+}
+
+pub fn get_int(compound: &Tag, key: &str) -> Result<i32, String> {
+    match get_key(compound, key)? {
+        Tag::Int(value) => Ok(*value),
+        _ => Err(format!("{} is not an int", key)),
+    }
+}
+
+pub fn get_string<'a>(compound: &'a Tag, key: &str) -> Result<&'a str, String> {
+    match get_key(compound, key)? {
+        Tag::String(value) => Ok(value),
+        _ => Err(format!("{} is not a string", key)),
+    }
+}
+
+pub fn get_byte_array(compound: &Tag, key: &str) -> Result<Vec<u8>, String> {
+    match get_key(compound, key)? {
+        Tag::ByteArray(values) => Ok(values.clone()),
+        _ => Err(format!("{} is not a byte array", key)),
+    }
+}
+
+pub fn get_int_array(compound: &Tag, key: &str) -> Result<Vec<i32>, String> {
+    match get_key(compound, key)? {
+        Tag::IntArray(values) => Ok(values.clone()),
+        _ => Err(format!("{} is not an int array", key)),
+    }
+}
+
+pub fn get_long_array<'a>(compound: &'a Tag, key: &str) -> Result<&'a Vec<i64>, String> {
+    match get_key(compound, key)? {
+        Tag::LongArray(values) => Ok(values),
+        _ => Err(format!("{} is not a long array", key)),
+    }
+}
+
+pub fn get_list<'a>(compound: &'a Tag, key: &str) -> Result<&'a Vec<Tag>, String> {
+    match get_key(compound, key)? {
+        Tag::List(values) => Ok(values),
+        _ => Err(format!("{} is not a list", key)),
+    }
+}
+
+fn get_type_id(tag: &Tag) -> u8 {
+    match tag {
+        Tag::End => 0,
+        Tag::Byte(_) => 1,
+        Tag::Short(_) => 2,
+        Tag::Int(_) => 3,
+        Tag::Long(_) => 4,
+        Tag::Float(_) => 5,
+        Tag::Double(_) => 6,
+        Tag::ByteArray(_) => 7,
+        Tag::String(_) => 8,
+        Tag::List(_) => 9,
+        Tag::Compound(_) => 10,
+        Tag::IntArray(_) => 11,
+        Tag::LongArray(_) => 12,
+    }
+}
+
+/// Reads a named tag, as the root of an NBT file, which must be uncompressed.
+pub fn read_named_tag<R: Read>(reader: &mut R) -> Result<(String, Tag), String> {
+    read_named_tag_io(reader).map_err(|e| format!("Invalid NBT: {}", e))
+}
+
+fn read_named_tag_io<R: Read>(reader: &mut R) -> io::Result<(String, Tag)> {
+    let type_id = reader.read_u8()?;
+    if type_id == 0 {
+        return Ok((String::new(), Tag::End));
+    }
+    let name = read_string(reader)?;
+    Ok((name, read_tag_payload(reader, type_id)?))
+}
+
+/// Java's modified UTF-8 is read as UTF-8, which only differs for NUL and supplementary characters.
+fn read_string<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = reader.read_u16::<BigEndian>()? as usize;
+    let bytes = read_bytes(reader, len)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Without allocating the length up front, which may be garbage in corrupt files.
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+/// Negative lengths are read as empty.
+fn read_len<R: Read>(reader: &mut R) -> io::Result<usize> {
+    Ok(reader.read_i32::<BigEndian>()?.max(0) as usize)
+}
+
+fn read_tag_payload<R: Read>(reader: &mut R, type_id: u8) -> io::Result<Tag> {
+    Ok(match type_id {
+        0 => Tag::End,
+        1 => Tag::Byte(reader.read_i8()?),
+        2 => Tag::Short(reader.read_i16::<BigEndian>()?),
+        3 => Tag::Int(reader.read_i32::<BigEndian>()?),
+        4 => Tag::Long(reader.read_i64::<BigEndian>()?),
+        5 => Tag::Float(reader.read_f32::<BigEndian>()?),
+        6 => Tag::Double(reader.read_f64::<BigEndian>()?),
+        7 => {
+            let len = read_len(reader)?;
+            Tag::ByteArray(read_bytes(reader, len)?)
+        }
+        8 => Tag::String(read_string(reader)?),
+        9 => {
+            let element_type_id = reader.read_u8()?;
+            let len = read_len(reader)?;
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(read_tag_payload(reader, element_type_id)?);
+            }
+            Tag::List(values)
+        }
+        10 => {
+            let mut map = HashMap::new();
+            loop {
+                let (name, value) = read_named_tag_io(reader)?;
+                if value == Tag::End {
+                    break;
+                }
+                map.insert(name, value);
+            }
+            Tag::Compound(map)
+        }
+        11 => {
+            let len = read_len(reader)?;
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(reader.read_i32::<BigEndian>()?);
+            }
+            Tag::IntArray(values)
+        }
+        12 => {
+            let len = read_len(reader)?;
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(reader.read_i64::<BigEndian>()?);
+            }
+            Tag::LongArray(values)
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unknown tag type {}", type_id),
+            ))
+        }
+    })
+}
+
+/// Serializes a named tag, as the root of an NBT file.
+pub fn write_named_tag(data: &mut Vec<u8>, name: &str, tag: &Tag) {
+    data.push(get_type_id(tag));
+    write_string(data, name);
+    write_tag_payload(data, tag);
+}
+
+fn write_string(data: &mut Vec<u8>, value: &str) {
+    data.write_u16::<BigEndian>(value.len() as u16).unwrap();
+    data.extend_from_slice(value.as_bytes());
+}
+
+fn write_tag_payload(data: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::End => {}
+        Tag::Byte(value) => data.push(*value as u8),
+        Tag::Short(value) => data.write_i16::<BigEndian>(*value).unwrap(),
+        Tag::Int(value) => data.write_i32::<BigEndian>(*value).unwrap(),
+        Tag::Long(value) => data.write_i64::<BigEndian>(*value).unwrap(),
+        Tag::Float(value) => data.write_f32::<BigEndian>(*value).unwrap(),
+        Tag::Double(value) => data.write_f64::<BigEndian>(*value).unwrap(),
+        Tag::ByteArray(values) => {
+            data.write_i32::<BigEndian>(values.len() as i32).unwrap();
+            data.extend_from_slice(values);
+        }
+        Tag::String(value) => write_string(data, value),
+        Tag::List(values) => {
+            // empty lists have the element type of End
+            data.push(values.first().map_or(0, get_type_id));
+            data.write_i32::<BigEndian>(values.len() as i32).unwrap();
+            for value in values {
+                write_tag_payload(data, value);
+            }
+        }
+        Tag::Compound(map) => {
+            for (name, value) in map {
+                write_named_tag(data, name, value);
+            }
+            data.push(0);
+        }
+        Tag::IntArray(values) => {
+            data.write_i32::<BigEndian>(values.len() as i32).unwrap();
+            for value in values {
+                data.write_i32::<BigEndian>(*value).unwrap();
+            }
+        }
+        Tag::LongArray(values) => {
+            data.write_i32::<BigEndian>(values.len() as i32).unwrap();
+            for value in values {
+                data.write_i64::<BigEndian>(*value).unwrap();
+            }
+        }
+    }
+}
+
+/// A compound tag, for building NBT to write.
+pub fn compound(entries: Vec<(&str, Tag)>) -> Tag {
+    Tag::Compound(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+pub fn byte_array(values: &[u8]) -> Tag {
+    Tag::ByteArray(values.to_vec())
+}
+
+pub fn int_array(values: &[i32]) -> Tag {
+    Tag::IntArray(values.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_what_it_writes() {
+        let tag = compound(vec![
+            ("byte", Tag::Byte(-3)),
+            ("name", Tag::String("minecraft:stone".to_string())),
+            ("light", byte_array(&[0, 0xf0, 0xff])),
+            ("heights", int_array(&[-64, 320])),
+            ("states", Tag::LongArray(vec![i64::MIN, 1])),
+            ("list", Tag::List(vec![Tag::Short(1), Tag::Short(2)])),
+            ("empty", Tag::List(Vec::new())),
+            ("nested", compound(vec![("double", Tag::Double(0.5))])),
+        ]);
+        let mut data = Vec::new();
+        write_named_tag(&mut data, "root", &tag);
+        assert_eq!(
+            read_named_tag(&mut &data[..]),
+            Ok(("root".to_string(), tag))
+        );
+        assert!(read_named_tag(&mut &data[..data.len() - 1]).is_err());
+        assert!(read_named_tag(&mut &[13, 0, 0][..]).is_err());
+    }
+}
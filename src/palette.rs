@@ -0,0 +1,248 @@
+extern crate serde_json;
+
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+// colors are ABGR, as stored in the little endian pixel buffers
+const BLACK: u32 = 0xff_00_00_00;
+const WHITE: u32 = 0xff_ff_ff_ff;
+const SKY_COLOR: u32 = 0xff_88_00_88; // #880088 pink
+const MTN_COLOR: u32 = 0xff_32_6e_9f; // #9f6e32 brown
+const MID_COLOR: u32 = 0xff_00_ff_ff; // #ffff00 yellow
+const COAST_COLOR: u32 = 0xff_00_b6_00; // #00b600 dark green
+const SEA_COLOR: u32 = 0xff_ff_d9_00; // #00d9ff light blue
+
+const FLAT_COLOR: u32 = 0xff_60_c0_40; // #40c060 green
+const GENTLE_COLOR: u32 = 0xff_00_e0_f0; // #f0e000 yellow
+const STEEP_COLOR: u32 = 0xff_00_80_f0; // #f08000 orange
+const CLIFF_COLOR: u32 = 0xff_20_20_c0; // #c02020 red
+const WALL_COLOR: u32 = 0xff_30_10_50; // #501030 dark purple
+
+const HIGH_LEVEL: i32 = 240;
+const MTN_LEVEL: i32 = 150;
+const MID_LEVEL: i32 = 100;
+const SEA_LEVEL: i32 = 64;
+
+/// Maps a value (height, light level, ...) to a color,
+/// linearly interpolating between the two closest stops.
+/// Values outside the stops get the color of the closest stop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    /// sorted by value
+    stops: Vec<(i32, u32)>,
+}
+
+impl Gradient {
+    pub fn new(mut stops: Vec<(i32, u32)>) -> Result<Self, String> {
+        if stops.is_empty() {
+            return Err("gradient needs at least one stop".to_string());
+        }
+        stops.sort_by_key(|(value, _)| *value);
+        Ok(Self { stops })
+    }
+
+    pub fn stops(&self) -> &[(i32, u32)] {
+        &self.stops
+    }
+
+    pub fn min_value(&self) -> i32 {
+        self.stops[0].0
+    }
+
+    pub fn max_value(&self) -> i32 {
+        self.stops[self.stops.len() - 1].0
+    }
+
+    pub fn color_at(&self, value: i32) -> u32 {
+        let (first_value, first_color) = self.stops[0];
+        if value <= first_value {
+            return first_color;
+        }
+        for window in self.stops.windows(2) {
+            let (val_start, col_start) = window[0];
+            let (val_stop, col_stop) = window[1];
+            if value < val_stop {
+                return interpolate(col_start, col_stop, val_start, val_stop, value);
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+/// The gradients used by the height and light colorizers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    /// by surface height
+    pub land: Gradient,
+    /// by ocean floor height
+    pub sea: Gradient,
+    /// by block light level 0-15
+    pub light: Gradient,
+    /// by slope in percent, 100 = one block up per block
+    pub slope: Gradient,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            land: Gradient {
+                stops: vec![
+                    (0, BLACK),
+                    (SEA_LEVEL, COAST_COLOR),
+                    (MID_LEVEL, MID_COLOR),
+                    (MTN_LEVEL, MTN_COLOR),
+                    (HIGH_LEVEL, WHITE),
+                    (255, SKY_COLOR),
+                ],
+            },
+            sea: Gradient {
+                stops: vec![(0, BLACK), (SEA_LEVEL, SEA_COLOR)],
+            },
+            light: Gradient {
+                stops: vec![(0, BLACK), (15, WHITE)],
+            },
+            slope: Gradient {
+                stops: vec![
+                    (0, FLAT_COLOR),
+                    (20, GENTLE_COLOR),
+                    (50, STEEP_COLOR),
+                    (100, CLIFF_COLOR),
+                    (300, WALL_COLOR),
+                ],
+            },
+        }
+    }
+}
+
+/// Palette file contents, any missing gradient uses the default.
+///
+/// ```json
+/// {
+///   "land": [[0, "#000000"], [40, "#00b600"], [250, "#ffffff"]],
+///   "sea": [[0, "#000000"], [40, "#00d9ff"]],
+///   "light": [[0, "#000000"], [15, "#ffff88"]],
+///   "slope": [[0, "#40c060"], [50, "#f08000"], [100, "#c02020"]]
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+struct PaletteFile {
+    land: Option<Vec<(i32, String)>>,
+    sea: Option<Vec<(i32, String)>>,
+    light: Option<Vec<(i32, String)>>,
+    slope: Option<Vec<(i32, String)>>,
+}
+
+pub fn read_palette(path: &Path) -> Result<Palette, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{:?} {}", path, e))?;
+    parse_palette(&text).map_err(|e| format!("{:?} {}", path, e))
+}
+
+pub fn parse_palette(text: &str) -> Result<Palette, String> {
+    let file: PaletteFile = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let mut palette = Palette::default();
+    if let Some(stops) = file.land {
+        palette.land = parse_gradient(&stops).map_err(|e| format!("land: {}", e))?;
+    }
+    if let Some(stops) = file.sea {
+        palette.sea = parse_gradient(&stops).map_err(|e| format!("sea: {}", e))?;
+    }
+    if let Some(stops) = file.light {
+        palette.light = parse_gradient(&stops).map_err(|e| format!("light: {}", e))?;
+    }
+    if let Some(stops) = file.slope {
+        palette.slope = parse_gradient(&stops).map_err(|e| format!("slope: {}", e))?;
+    }
+    Ok(palette)
+}
+
+fn parse_gradient(stops: &[(i32, String)]) -> Result<Gradient, String> {
+    let stops = stops
+        .iter()
+        .map(|(value, color)| parse_color(color).map(|c| (*value, c)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Gradient::new(stops)
+}
+
+/// `#rrggbb` or `#rrggbbaa` to ABGR
+pub fn parse_color(color_str: &str) -> Result<u32, String> {
+    let hex = color_str.trim_start_matches('#');
+    let invalid = || format!("invalid color {:?}, should be: #rrggbb", color_str);
+    if hex.len() != 6 && hex.len() != 8 {
+        return Err(invalid());
+    }
+    let rgba = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+    let rgba = if hex.len() == 6 {
+        rgba << 8 | 0xff
+    } else {
+        rgba
+    };
+    Ok(rgba.swap_bytes())
+}
+
+/// ABGR to `#rrggbb`, or `#rrggbbaa` if not opaque
+pub fn format_color(color: u32) -> String {
+    let rgba = color.swap_bytes();
+    if rgba & 0xff == 0xff {
+        format!("#{:06x}", rgba >> 8)
+    } else {
+        format!("#{:08x}", rgba)
+    }
+}
+
+pub fn interpolate(col_start: u32, col_stop: u32, val_start: i32, val_stop: i32, val: i32) -> u32 {
+    let mut color = 0;
+    for shift in &[0, 8, 16, 24] {
+        let component = interpolate_color_component(
+            col_start >> shift & 0xff,
+            col_stop >> shift & 0xff,
+            val_start,
+            val_stop,
+            val,
+        );
+        color |= component << shift;
+    }
+    color
+}
+
+fn interpolate_color_component(
+    col_start: u32,
+    col_stop: u32,
+    val_start: i32,
+    val_stop: i32,
+    val: i32,
+) -> u32 {
+    let diff_start = (val - val_start) as u32;
+    let diff_stop = (val_stop - val) as u32;
+    let val_diff = (val_stop - val_start) as u32;
+    (col_start * diff_stop + col_stop * diff_start) / val_diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format_colors() {
+        assert_eq!(Ok(COAST_COLOR), parse_color("#00b600"));
+        assert_eq!(Ok(0x80_ff_d9_00), parse_color("#00d9ff80"));
+        assert_eq!("#9f6e32", format_color(MTN_COLOR));
+        assert!(parse_color("#12345").is_err());
+    }
+
+    #[test]
+    fn gradient_clamps_and_interpolates() {
+        let gradient = Gradient::new(vec![(40, WHITE), (0, BLACK)]).unwrap();
+        assert_eq!(BLACK, gradient.color_at(-5));
+        assert_eq!(0xff_7f_7f_7f, gradient.color_at(20));
+        assert_eq!(WHITE, gradient.color_at(300));
+    }
+
+    #[test]
+    fn palette_file_overrides_only_given_gradients() {
+        let palette = parse_palette(r##"{"land": [[40, "#00b600"], [250, "#ffffff"]]}"##);
+        let palette = palette.unwrap();
+        assert_eq!(COAST_COLOR, palette.land.color_at(40));
+        assert_eq!(Palette::default().sea, palette.sea);
+    }
+}
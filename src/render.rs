@@ -0,0 +1,304 @@
+use crate::colorizer::{Colorizer, ColorizerOptions, TileContext};
+use crate::draw::blend_over;
+use crate::mc::blocks::BLOCK_STRINGS_ARR;
+use crate::mc::flattening::flatten_block_name;
+use crate::tile::{
+    get_xz_from_tile_path, read_tile, KeysMap, Layer, NamesVec, Tile, TilePos, LAYERS,
+};
+use crate::TILE_COLUMNS;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Tiles are rendered in strips this many tiles wide, row by row, see `get_render_order`.
+pub const RENDER_STRIP_WIDTH: usize = 16;
+
+/// Which layer the colorizers see as the surface layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerSelector {
+    Layer(Layer),
+    /// the lowest non-empty layer of each column, to see under roofs and canopies
+    XRay,
+}
+
+impl LayerSelector {
+    /// A layer name, or `x-ray`.
+    pub fn from_name(name: &str) -> Option<LayerSelector> {
+        match name {
+            "x-ray" => Some(LayerSelector::XRay),
+            _ => Layer::from_name(name).map(LayerSelector::Layer),
+        }
+    }
+}
+
+pub struct RenderConfig {
+    pub colorizer: Box<dyn Colorizer>,
+    /// drawn over the colorizer's output, in order
+    pub overlays: Vec<Box<dyn Colorizer>>,
+    pub layer: LayerSelector,
+    pub options: ColorizerOptions,
+    pub global_keys: KeysMap,
+    pub global_names: NamesVec,
+    /// all tiles in the cache, to find neighbors of the rendered tile
+    pub tile_paths: HashMap<TilePos, PathBuf>,
+    /// tiles read for rendering, shared between threads
+    pub tile_cache: TileCache,
+}
+
+impl RenderConfig {
+    pub fn new(colorizer: Box<dyn Colorizer>, options: ColorizerOptions) -> Self {
+        Self {
+            colorizer,
+            overlays: Vec::new(),
+            layer: LayerSelector::Layer(Layer::Surface),
+            options,
+            global_keys: build_global_keys_map(),
+            global_names: build_global_names(),
+            tile_paths: HashMap::new(),
+            tile_cache: TileCache::for_threads(4),
+        }
+    }
+}
+
+/// Decoded tiles with the selected layer, shared by the threads rendering,
+/// so tiles aren't read again for each of their neighbors.
+/// Holds up to `capacity` tiles, dropping the least recently used one first.
+pub struct TileCache {
+    capacity: usize,
+    entries: Mutex<TileCacheEntries>,
+}
+
+#[derive(Default)]
+struct TileCacheEntries {
+    /// with the `clock` of their last use
+    tiles: HashMap<TilePos, (Arc<Tile>, u64)>,
+    clock: u64,
+}
+
+impl TileCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(TileCacheEntries::default()),
+        }
+    }
+
+    /// Enough for three rows of a strip and the tiles being rendered, see `get_render_order`.
+    pub fn for_threads(num_threads: usize) -> Self {
+        Self::new(3 * (RENDER_STRIP_WIDTH + 2) + 2 * num_threads)
+    }
+
+    /// The cached tile at `pos`, or else the result of `read`, which is cached if it succeeded.
+    /// Reading doesn't block other threads, which may read the same tile at the same time.
+    pub fn get_or_read<F>(&self, pos: TilePos, read: F) -> Result<Arc<Tile>, String>
+    where
+        F: FnOnce() -> Result<Box<Tile>, String>,
+    {
+        {
+            let mut entries = self.entries.lock().unwrap();
+            entries.clock += 1;
+            let clock = entries.clock;
+            if let Some((tile, last_used)) = entries.tiles.get_mut(&pos) {
+                *last_used = clock;
+                return Ok(tile.clone());
+            }
+        }
+        let tile: Arc<Tile> = Arc::from(read()?);
+        let mut entries = self.entries.lock().unwrap();
+        if entries.tiles.len() >= self.capacity {
+            let least_recently_used = entries
+                .tiles
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(pos, _)| *pos);
+            if let Some(least_recently_used) = least_recently_used {
+                entries.tiles.remove(&least_recently_used);
+            }
+        }
+        entries.clock += 1;
+        let clock = entries.clock;
+        entries.tiles.insert(pos, (tile.clone(), clock));
+        Ok(tile)
+    }
+}
+
+/// Sort key to render tiles in strips `RENDER_STRIP_WIDTH` tiles wide, row by row,
+/// so most neighbors of a tile are still in the `TileCache` from the tiles before it.
+pub fn get_render_order((x, z): TilePos) -> (i32, i32, i32) {
+    (x.div_euclid(RENDER_STRIP_WIDTH as i32), z, x)
+}
+
+pub fn render_tile(tile_path: &PathBuf, config: &RenderConfig) -> Result<Vec<u32>, String> {
+    let needs_neighbors = config.colorizer.needs_neighbors()
+        || config
+            .overlays
+            .iter()
+            .any(|overlay| overlay.needs_neighbors());
+    // tiles are only cached to be neighbors
+    let pos = get_xz_from_tile_path(tile_path)
+        .ok()
+        .filter(|_| needs_neighbors);
+    let tile = match pos {
+        Some(pos) => config
+            .tile_cache
+            .get_or_read(pos, || read_tile_layer(tile_path, config.layer))?,
+        None => Arc::from(read_tile_layer(tile_path, config.layer)?),
+    };
+    let neighbors = match pos {
+        Some(pos) => read_neighbors(pos, config),
+        None => Vec::new(),
+    };
+
+    let mut ctx = TileContext::new(
+        &tile,
+        &config.global_keys,
+        &config.global_names,
+        &config.options,
+    );
+    for ((dx, dz), neighbor) in &neighbors {
+        ctx.set_neighbor(*dx, *dz, Some(neighbor));
+    }
+
+    let mut pixbuf = config.colorizer.colorize_tile(&ctx);
+    for overlay in &config.overlays {
+        let overlay_pixbuf = overlay.colorize_tile(&ctx);
+        for (pixel, over) in pixbuf.iter_mut().zip(overlay_pixbuf) {
+            *pixel = blend_over(*pixel, over);
+        }
+    }
+
+    Ok(pixbuf)
+}
+
+fn read_tile_layer(tile_path: &PathBuf, selector: LayerSelector) -> Result<Box<Tile>, String> {
+    Ok(select_layer(read_tile(tile_path)?, selector))
+}
+
+/// Copies the selected layer into the surface layer.
+/// With a single layer selected, columns where that layer is empty become empty.
+fn select_layer(mut tile: Box<Tile>, selector: LayerSelector) -> Box<Tile> {
+    if selector == LayerSelector::Layer(Layer::Surface) {
+        return tile;
+    }
+    for column_nr in 0..TILE_COLUMNS {
+        if tile.is_col_empty(column_nr) {
+            continue;
+        }
+        let layer = match selector {
+            LayerSelector::Layer(layer) => layer,
+            LayerSelector::XRay => get_lowest_layer(&tile, column_nr),
+        };
+        let block_nr = tile.get_layer_blockstate(layer, column_nr);
+        if block_nr == 0 {
+            tile.set_height(column_nr, 0);
+            tile.set_blockstate(column_nr, 0);
+            tile.set_biome_id(column_nr, 0);
+            continue;
+        }
+        let height = tile.get_layer_height(layer, column_nr);
+        let light = tile.get_layer_light(layer, column_nr);
+        tile.set_height(column_nr, height);
+        tile.set_blockstate(column_nr, block_nr);
+        tile.set_light(column_nr, light);
+    }
+    tile
+}
+
+/// The lowest layer containing a block, or the surface layer if none do.
+fn get_lowest_layer(tile: &Tile, column_nr: usize) -> Layer {
+    LAYERS
+        .iter()
+        .cloned()
+        .filter(|layer| tile.get_layer_blockstate(*layer, column_nr) != 0)
+        .min_by_key(|layer| tile.get_layer_height(*layer, column_nr))
+        .unwrap_or(Layer::Surface)
+}
+
+/// Missing or unreadable neighbors are left out.
+fn read_neighbors((x, z): TilePos, config: &RenderConfig) -> Vec<((i32, i32), Arc<Tile>)> {
+    let mut neighbors = Vec::new();
+    for dz in -1..=1 {
+        for dx in -1..=1 {
+            if dx == 0 && dz == 0 {
+                continue;
+            }
+            let pos = (x + dx, z + dz);
+            let neighbor = config.tile_paths.get(&pos).and_then(|path| {
+                config
+                    .tile_cache
+                    .get_or_read(pos, || read_tile_layer(path, config.layer))
+                    .ok()
+            });
+            if let Some(neighbor) = neighbor {
+                neighbors.push(((dx, dz), neighbor));
+            }
+        }
+    }
+    neighbors
+}
+
+/// The names of all legacy block states by `id << 4 | meta`, flattened to modern names
+/// like tiles' key files are when read, see `Tile::flatten_block_names`.
+pub fn build_global_names() -> NamesVec {
+    BLOCK_STRINGS_ARR
+        .iter()
+        .map(|name| flatten_block_name(name).unwrap_or_else(|| name.to_string()))
+        .collect()
+}
+
+/// The global id of each name of `build_global_names`.
+/// Legacy states that became the same modern state get the lowest of their ids.
+pub fn build_global_keys_map() -> KeysMap {
+    KeysMap::from_iter(
+        build_global_names()
+            .into_iter()
+            .enumerate()
+            .rev()
+            .map(|(i, s)| (s, i as u16)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn builds_global_keys_from_flattened_names() {
+        let names = build_global_names();
+        let keys = build_global_keys_map();
+        assert_eq!(names[1 << 4 | 1], "minecraft:granite");
+        assert_eq!(keys["minecraft:granite"], 1 << 4 | 1);
+        assert_eq!(keys["minecraft:stone"], 1 << 4);
+    }
+
+    #[test]
+    fn caches_recently_used_tiles() {
+        let cache = TileCache::new(2);
+        let reads = Cell::new(0);
+        let read = |pos| {
+            let reads = &reads;
+            move || {
+                reads.set(reads.get() + 1);
+                Ok(Box::new(Tile::new(Some(pos))))
+            }
+        };
+        for pos in [(0, 0), (1, 0), (0, 0), (2, 0), (0, 0), (1, 0)].iter() {
+            let tile = cache.get_or_read(*pos, read(*pos)).unwrap();
+            assert_eq!(tile.pos, Some(*pos));
+        }
+        // 1,0 made room for 2,0, as 0,0 was used after it
+        assert_eq!(reads.get(), 4);
+        assert!(cache
+            .get_or_read((3, 0), || Err("unreadable".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn renders_in_strips() {
+        let mut positions = vec![(16, 0), (0, 1), (15, 0), (-1, 0), (0, 0)];
+        positions.sort_by_key(|pos| get_render_order(*pos));
+        assert_eq!(positions, vec![(-1, 0), (0, 0), (15, 0), (0, 1), (16, 0)]);
+    }
+}
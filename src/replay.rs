@@ -9,7 +9,7 @@ use std::io::Read;
 use std::path::Path;
 use zip::ZipArchive;
 
-error_chain!{
+error_chain! {
     types {
         ReplayErr, ReplayErrorKind, ReplayResult;
     }
@@ -34,7 +34,7 @@ impl ReplayPacket {
         &self.decoded
     }
     pub fn parse_packet(&mut self) -> Result<(), BufErr> {
-        if let None = self.decoded {
+        if self.decoded.is_none() {
             self.decoded = Some(McPacket::decode(&self.data)?);
         }
         Ok(())
@@ -72,9 +72,9 @@ impl Iterator for Replay {
         let date = self.info.date + time_offset;
 
         Some(ReplayPacket {
-            date: date,
-            size: size,
-            id: id,
+            date,
+            size,
+            id,
             data: packet_data,
             decoded: None,
         })
@@ -85,7 +85,7 @@ pub fn read_info<P>(path: &P) -> Result<ReplayInfo, ReplayErr>
 where
     P: AsRef<Path>,
 {
-    let zip_file = File::open(&path)?;
+    let zip_file = File::open(path)?;
     let mut zip_archive = ZipArchive::new(zip_file)?;
     read_info_from_zip(&mut zip_archive)
 }
@@ -94,7 +94,7 @@ pub fn read_replay<P>(path: &P) -> Result<Replay, ReplayErr>
 where
     P: AsRef<Path>,
 {
-    let zip_file = File::open(&path)?;
+    let zip_file = File::open(path)?;
     let mut zip_archive = ZipArchive::new(zip_file)?;
 
     let info = read_info_from_zip(&mut zip_archive)?;
@@ -104,12 +104,12 @@ where
             .by_name("recording.tmcpr")
             .chain_err(|| "No recording in mcpr")?;
         let mut data = vec![0; data_file.size() as usize];
-        data_file.read_exact(&mut *data)?;
+        data_file.read_exact(&mut data)?;
         data
     };
 
     Ok(Replay {
-        info: info,
+        info,
         data: BufReader::new(data),
     })
 }
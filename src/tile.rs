@@ -1,13 +1,27 @@
+extern crate filetime;
 extern crate zip;
 
+use self::filetime::{set_file_times, FileTime};
+use crate::mc::biomes::{get_biome_by_legacy_id, get_biome_by_name, Biome};
+use crate::mc::blocks::BlockState;
+use crate::mc::flattening::flatten_block_name;
 use std::collections::{HashMap, LinkedList};
 use std::fmt;
 use std::fs;
 use std::num::ParseIntError;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 pub const COLUMN_BYTES_OLD: usize = 17;
 pub const COLUMN_BYTES_MODERN: usize = 18;
+/// Modern data plus the high byte of each layer's height, as `Tile` keeps them in memory.
+/// Caches only get the modern data, see `write_tile`.
+pub const COLUMN_BYTES_EXTENDED: usize = 22;
+
+/// Range of heights a tile can hold, the full `i16` range, so 1.18+ worlds (-63..=320) fit.
+/// Heights are one above the block's y, as stored by VoxelMap.
+pub const MIN_HEIGHT: i32 = i16::MIN as i32;
+pub const MAX_HEIGHT: i32 = i16::MAX as i32;
 
 pub const TILE_WIDTH: usize = 256;
 pub const TILE_HEIGHT: usize = 256;
@@ -32,6 +46,45 @@ const FOLIAGEHEIGHTPOS: usize = 12;
 const FOLIAGEBLOCKSTATEPOS: usize = 13;
 const FOLIAGELIGHTPOS: usize = 15;
 const BIOMEIDPOS: usize = 16;
+const HEIGHTHIGHPOS: usize = 18;
+const OCEANFLOORHEIGHTHIGHPOS: usize = 19;
+const TRANSPARENTHEIGHTHIGHPOS: usize = 20;
+const FOLIAGEHEIGHTHIGHPOS: usize = 21;
+
+/// The four block layers VoxelMap stores per column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+    /// highest light-blocking block
+    Surface,
+    /// highest block below water
+    OceanFloor,
+    /// highest rain-blocking block, such as glass
+    Transparent,
+    /// vegetation, such as tall grass and flowers
+    Foliage,
+}
+
+pub const LAYERS: [Layer; 4] = [
+    Layer::Surface,
+    Layer::OceanFloor,
+    Layer::Transparent,
+    Layer::Foliage,
+];
+
+impl Layer {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layer::Surface => "surface",
+            Layer::OceanFloor => "ocean-floor",
+            Layer::Transparent => "transparent",
+            Layer::Foliage => "foliage",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Layer> {
+        LAYERS.iter().cloned().find(|layer| layer.name() == name)
+    }
+}
 
 pub type TilePos = (i32, i32);
 pub type KeysMap = HashMap<String, u16>;
@@ -39,15 +92,34 @@ pub type NamesVec = Vec<String>;
 
 pub struct Tile {
     // TODO make private
-    /// in v2 format (layer-then-coords)
+    /// in v2 format (layer-then-coords), followed by the high bytes of the heights
     pub data: Vec<u8>,
     pub keys: KeysMap,
     pub names: NamesVec,
+    /// parsed `names`, by id
+    pub block_states: Vec<&'static BlockState>,
+    /// biome names by id, empty if the biome ids are legacy numeric ids
+    pub biome_keys: KeysMap,
+    pub biome_names: NamesVec,
     pub pos: Option<TilePos>,
     pub source: Option<PathBuf>,
 }
 
 impl Tile {
+    /// All columns empty, without any keys.
+    pub fn new(pos: Option<TilePos>) -> Self {
+        Self {
+            data: vec![0; TILE_COLUMNS * COLUMN_BYTES_EXTENDED],
+            keys: HashMap::new(),
+            names: Vec::new(),
+            block_states: Vec::new(),
+            biome_keys: HashMap::new(),
+            biome_names: Vec::new(),
+            pos,
+            source: None,
+        }
+    }
+
     pub fn is_chunk_empty(&self, chunk_nr: usize) -> bool {
         let column_nr = first_column_nr_of_chunk_nr(chunk_nr);
         self.is_col_empty(column_nr)
@@ -58,8 +130,8 @@ impl Tile {
             && self.get_blockstate(column_nr) == 0
     }
 
-    pub fn get_height(&self, column_nr: usize) -> u8 {
-        self.get_u8(column_nr, HEIGHTPOS)
+    pub fn get_height(&self, column_nr: usize) -> i32 {
+        self.get_split_i16(column_nr, HEIGHTPOS, HEIGHTHIGHPOS)
     }
     pub fn get_blockstate(&self, column_nr: usize) -> u16 {
         self.get_u16(column_nr, BLOCKSTATEPOS)
@@ -67,8 +139,8 @@ impl Tile {
     pub fn get_light(&self, column_nr: usize) -> u8 {
         self.get_u8(column_nr, LIGHTPOS)
     }
-    pub fn get_ocean_floor_height(&self, column_nr: usize) -> u8 {
-        self.get_u8(column_nr, OCEANFLOORHEIGHTPOS)
+    pub fn get_ocean_floor_height(&self, column_nr: usize) -> i32 {
+        self.get_split_i16(column_nr, OCEANFLOORHEIGHTPOS, OCEANFLOORHEIGHTHIGHPOS)
     }
     pub fn get_ocean_floor_blockstate(&self, column_nr: usize) -> u16 {
         self.get_u16(column_nr, OCEANFLOORBLOCKSTATEPOS)
@@ -76,8 +148,8 @@ impl Tile {
     pub fn get_ocean_floor_light(&self, column_nr: usize) -> u8 {
         self.get_u8(column_nr, OCEANFLOORLIGHTPOS)
     }
-    pub fn get_transparent_height(&self, column_nr: usize) -> u8 {
-        self.get_u8(column_nr, TRANSPARENTHEIGHTPOS)
+    pub fn get_transparent_height(&self, column_nr: usize) -> i32 {
+        self.get_split_i16(column_nr, TRANSPARENTHEIGHTPOS, TRANSPARENTHEIGHTHIGHPOS)
     }
     pub fn get_transparent_blockstate(&self, column_nr: usize) -> u16 {
         self.get_u16(column_nr, TRANSPARENTBLOCKSTATEPOS)
@@ -85,8 +157,8 @@ impl Tile {
     pub fn get_transparent_light(&self, column_nr: usize) -> u8 {
         self.get_u8(column_nr, TRANSPARENTLIGHTPOS)
     }
-    pub fn get_foliage_height(&self, column_nr: usize) -> u8 {
-        self.get_u8(column_nr, FOLIAGEHEIGHTPOS)
+    pub fn get_foliage_height(&self, column_nr: usize) -> i32 {
+        self.get_split_i16(column_nr, FOLIAGEHEIGHTPOS, FOLIAGEHEIGHTHIGHPOS)
     }
     pub fn get_foliage_blockstate(&self, column_nr: usize) -> u16 {
         self.get_u16(column_nr, FOLIAGEBLOCKSTATEPOS)
@@ -98,8 +170,171 @@ impl Tile {
         self.get_u16(column_nr, BIOMEIDPOS)
     }
 
-    pub fn set_height(&mut self, column_nr: usize, value: u8) {
-        self.set_u8(column_nr, HEIGHTPOS, value);
+    /// One above the block's y, so 256 for a block at the 1.17 height limit,
+    /// and negative below y=-1 in 1.18+.
+    pub fn get_layer_height(&self, layer: Layer, column_nr: usize) -> i32 {
+        match layer {
+            Layer::Surface => self.get_height(column_nr),
+            Layer::OceanFloor => self.get_ocean_floor_height(column_nr),
+            Layer::Transparent => self.get_transparent_height(column_nr),
+            Layer::Foliage => self.get_foliage_height(column_nr),
+        }
+    }
+    pub fn set_layer_height(&mut self, layer: Layer, column_nr: usize, value: i32) {
+        match layer {
+            Layer::Surface => self.set_height(column_nr, value),
+            Layer::OceanFloor => self.set_ocean_floor_height(column_nr, value),
+            Layer::Transparent => self.set_transparent_height(column_nr, value),
+            Layer::Foliage => self.set_foliage_height(column_nr, value),
+        }
+    }
+    pub fn get_layer_blockstate(&self, layer: Layer, column_nr: usize) -> u16 {
+        match layer {
+            Layer::Surface => self.get_blockstate(column_nr),
+            Layer::OceanFloor => self.get_ocean_floor_blockstate(column_nr),
+            Layer::Transparent => self.get_transparent_blockstate(column_nr),
+            Layer::Foliage => self.get_foliage_blockstate(column_nr),
+        }
+    }
+    pub fn get_layer_light(&self, layer: Layer, column_nr: usize) -> u8 {
+        match layer {
+            Layer::Surface => self.get_light(column_nr),
+            Layer::OceanFloor => self.get_ocean_floor_light(column_nr),
+            Layer::Transparent => self.get_transparent_light(column_nr),
+            Layer::Foliage => self.get_foliage_light(column_nr),
+        }
+    }
+    /// `?` for ids missing from the key file
+    pub fn get_block_name(&self, block_nr: u16) -> &str {
+        self.names
+            .get(block_nr as usize)
+            .map(|name| name.as_str())
+            .unwrap_or("?")
+    }
+    /// Ids missing from the key file are unknown, see `BlockState::is_unknown`.
+    pub fn get_block_state(&self, block_nr: u16) -> &'static BlockState {
+        match self.block_states.get(block_nr as usize) {
+            Some(state) => state,
+            None => BlockState::unknown(),
+        }
+    }
+    /// Ids start at 1, like in VoxelMap, so 0 stays empty.
+    pub fn get_or_add_key(&mut self, block_name: &str) -> u16 {
+        let id = get_or_add_key(&mut self.keys, &mut self.names, block_name);
+        let is_new = self
+            .block_states
+            .get(id as usize)
+            .map_or(true, |state| state.as_str() != block_name);
+        if is_new {
+            self.update_block_states();
+        }
+        id
+    }
+    /// Parses the names that changed since the last call.
+    fn update_block_states(&mut self) {
+        let names = &self.names;
+        self.block_states.truncate(names.len());
+        for (id, state) in self.block_states.iter_mut().enumerate() {
+            if state.as_str() != names[id] {
+                *state = BlockState::intern(&names[id]);
+            }
+        }
+        let len = self.block_states.len();
+        self.block_states
+            .extend(names[len..].iter().map(|name| BlockState::intern(name)));
+    }
+    /// Converts legacy block names to modern ones, see `mc::flattening`.
+    /// Legacy states that became the same modern state share the lowest of their ids.
+    pub fn flatten_block_names(&mut self) {
+        let mut converter: Vec<u16> = (0..self.names.len() as u16).collect();
+        let mut needs_conversion = false;
+        let mut flattened_keys = KeysMap::new();
+        let mut ids: Vec<u16> = self.keys.values().cloned().collect();
+        ids.sort();
+        for id in ids {
+            let name = &self.names[id as usize];
+            let flattened = flatten_block_name(name).unwrap_or_else(|| name.clone());
+            let first_id = *flattened_keys.entry(flattened.clone()).or_insert(id);
+            if first_id != id {
+                converter[id as usize] = first_id;
+                needs_conversion = true;
+                self.names[id as usize] = "?".to_string();
+            } else {
+                self.names[id as usize] = flattened;
+            }
+        }
+        self.keys = flattened_keys;
+        self.update_block_states();
+
+        if !needs_conversion {
+            return;
+        }
+        for column_nr in 0..TILE_COLUMNS {
+            for layer_nr in [
+                BLOCKSTATEPOS,
+                OCEANFLOORBLOCKSTATEPOS,
+                TRANSPARENTBLOCKSTATEPOS,
+                FOLIAGEBLOCKSTATEPOS,
+            ]
+            .iter()
+            {
+                let id = self.get_u16(column_nr, *layer_nr);
+                if let Some(converted) = converter.get(id as usize) {
+                    self.set_u16(column_nr, *layer_nr, *converted);
+                }
+            }
+        }
+    }
+
+    /// `None` for modded and unknown biomes.
+    pub fn get_biome(&self, column_nr: usize) -> Option<&'static Biome> {
+        let biome_id = self.get_biome_id(column_nr);
+        if self.biome_keys.is_empty() {
+            get_biome_by_legacy_id(biome_id)
+        } else {
+            get_biome_by_name(self.get_biome_name(column_nr))
+        }
+    }
+    /// Registry name such as `minecraft:plains`, also for modded biomes,
+    /// `?` for unknown ids.
+    pub fn get_biome_name(&self, column_nr: usize) -> &str {
+        let biome_id = self.get_biome_id(column_nr);
+        if self.biome_keys.is_empty() {
+            get_biome_by_legacy_id(biome_id).map_or("?", |biome| biome.name)
+        } else {
+            self.biome_names
+                .get(biome_id as usize)
+                .map(|name| name.as_str())
+                .unwrap_or("?")
+        }
+    }
+    /// Like `get_or_add_key`, switches the tile to biome keys first if needed.
+    pub fn get_or_add_biome_key(&mut self, biome_name: &str) -> u16 {
+        self.use_biome_keys();
+        get_or_add_key(&mut self.biome_keys, &mut self.biome_names, biome_name)
+    }
+    /// Converts legacy numeric biome ids to biome keys, see `voxelmap-cache-format.md`.
+    /// Unknown legacy ids become 0, which is no biome.
+    pub fn use_biome_keys(&mut self) {
+        if !self.biome_keys.is_empty() {
+            return;
+        }
+        for column_nr in 0..TILE_COLUMNS {
+            if self.is_col_empty(column_nr) {
+                continue;
+            }
+            let biome_id = match get_biome_by_legacy_id(self.get_biome_id(column_nr)) {
+                Some(biome) => {
+                    get_or_add_key(&mut self.biome_keys, &mut self.biome_names, biome.name)
+                }
+                None => 0,
+            };
+            self.set_biome_id(column_nr, biome_id);
+        }
+    }
+
+    pub fn set_height(&mut self, column_nr: usize, value: i32) {
+        self.set_split_i16(column_nr, HEIGHTPOS, HEIGHTHIGHPOS, value);
     }
     pub fn set_blockstate(&mut self, column_nr: usize, id: u16) {
         self.set_u16(column_nr, BLOCKSTATEPOS, id);
@@ -107,8 +342,13 @@ impl Tile {
     pub fn set_light(&mut self, column_nr: usize, value: u8) {
         self.set_u8(column_nr, LIGHTPOS, value);
     }
-    pub fn set_ocean_floor_height(&mut self, column_nr: usize, value: u8) {
-        self.set_u8(column_nr, OCEANFLOORHEIGHTPOS, value);
+    pub fn set_ocean_floor_height(&mut self, column_nr: usize, value: i32) {
+        self.set_split_i16(
+            column_nr,
+            OCEANFLOORHEIGHTPOS,
+            OCEANFLOORHEIGHTHIGHPOS,
+            value,
+        );
     }
     pub fn set_ocean_floor_blockstate(&mut self, column_nr: usize, id: u16) {
         self.set_u16(column_nr, OCEANFLOORBLOCKSTATEPOS, id);
@@ -116,8 +356,13 @@ impl Tile {
     pub fn set_ocean_floor_light(&mut self, column_nr: usize, value: u8) {
         self.set_u8(column_nr, OCEANFLOORLIGHTPOS, value);
     }
-    pub fn set_transparent_height(&mut self, column_nr: usize, value: u8) {
-        self.set_u8(column_nr, TRANSPARENTHEIGHTPOS, value);
+    pub fn set_transparent_height(&mut self, column_nr: usize, value: i32) {
+        self.set_split_i16(
+            column_nr,
+            TRANSPARENTHEIGHTPOS,
+            TRANSPARENTHEIGHTHIGHPOS,
+            value,
+        );
     }
     pub fn set_transparent_blockstate(&mut self, column_nr: usize, id: u16) {
         self.set_u16(column_nr, TRANSPARENTBLOCKSTATEPOS, id);
@@ -125,8 +370,8 @@ impl Tile {
     pub fn set_transparent_light(&mut self, column_nr: usize, value: u8) {
         self.set_u8(column_nr, TRANSPARENTLIGHTPOS, value);
     }
-    pub fn set_foliage_height(&mut self, column_nr: usize, value: u8) {
-        self.set_u8(column_nr, FOLIAGEHEIGHTPOS, value);
+    pub fn set_foliage_height(&mut self, column_nr: usize, value: i32) {
+        self.set_split_i16(column_nr, FOLIAGEHEIGHTPOS, FOLIAGEHEIGHTHIGHPOS, value);
     }
     pub fn set_foliage_blockstate(&mut self, column_nr: usize, id: u16) {
         self.set_u16(column_nr, FOLIAGEBLOCKSTATEPOS, id);
@@ -147,6 +392,13 @@ impl Tile {
         (self.data[index] as u16) << 8 | (self.data[index + TILE_COLUMNS] as u16)
     }
 
+    /// Heights keep their low byte where version 2 caches have it.
+    fn get_split_i16(&self, column_nr: usize, low_layer_nr: usize, high_layer_nr: usize) -> i32 {
+        let high = self.get_u8(column_nr, high_layer_nr) as u16;
+        let low = self.get_u8(column_nr, low_layer_nr) as u16;
+        (high << 8 | low) as i16 as i32
+    }
+
     fn set_u8(&mut self, column_nr: usize, layer_offset: usize, value: u8) {
         let index = column_nr + TILE_COLUMNS * layer_offset;
         self.data[index] = value;
@@ -156,6 +408,27 @@ impl Tile {
         self.data[index] = (value >> 8) as u8;
         self.data[index + TILE_COLUMNS] = value as u8;
     }
+    fn set_split_i16(
+        &mut self,
+        column_nr: usize,
+        low_layer_offset: usize,
+        high_layer_offset: usize,
+        value: i32,
+    ) {
+        let value = value.clamp(MIN_HEIGHT, MAX_HEIGHT) as i16 as u16;
+        self.set_u8(column_nr, high_layer_offset, (value >> 8) as u8);
+        self.set_u8(column_nr, low_layer_offset, value as u8);
+    }
+
+    /// Whether all heights are within 0..=256, so version 2 data can hold them.
+    pub fn fits_legacy_heights(&self) -> bool {
+        (0..TILE_COLUMNS).all(|column_nr| {
+            LAYERS.iter().all(|layer| {
+                let height = self.get_layer_height(*layer, column_nr);
+                (0..=256).contains(&height)
+            })
+        })
+    }
 }
 
 impl fmt::Debug for Tile {
@@ -176,6 +449,18 @@ impl fmt::Debug for Tile {
     }
 }
 
+fn get_or_add_key(keys: &mut KeysMap, names: &mut NamesVec, name: &str) -> u16 {
+    if let Some(id) = keys.get(name) {
+        return *id;
+    }
+    // after the highest id, keys read from a file may have gaps
+    let id = names.len().max(1) as u16;
+    keys.insert(name.to_string(), id);
+    names.resize(id as usize + 1, "?".to_string());
+    names[id as usize] = name.to_string();
+    id
+}
+
 pub fn column_nr_of_pos(x: usize, z: usize) -> usize {
     x + z * TILE_WIDTH
 }
@@ -185,93 +470,223 @@ pub fn first_column_nr_of_chunk_nr(chunk_nr: usize) -> usize {
         + (chunk_nr * CHUNK_WIDTH / TILE_WIDTH) * TILE_WIDTH * CHUNK_HEIGHT
 }
 
+/// This crate's own entry with the full heights of tiles that don't fit version 2,
+/// see `voxelmap-cache-format.md`.
+const HEIGHTS_ENTRY: &str = "voxelmap_cache_heights";
+/// This crate's own entries with the biome keys of tiles that use them, and the key of each column.
+const BIOME_KEYS_ENTRY: &str = "voxelmap_cache_biomes";
+const BIOME_IDS_ENTRY: &str = "voxelmap_cache_biome_ids";
+
+/// Legacy block names are converted to modern ones, see `Tile::flatten_block_names`.
 pub fn read_tile(tile_path: &PathBuf) -> Result<Box<Tile>, String> {
-    use std::io::{BufRead, BufReader, Read};
+    use std::io::Read;
 
-    let zip_file = fs::File::open(&tile_path).map_err(|e| e.to_string())?;
+    let zip_file = fs::File::open(tile_path).map_err(|e| e.to_string())?;
     let mut zip_archive = zip::ZipArchive::new(zip_file).map_err(|e| e.to_string())?;
 
-    let mut max_key = 0;
-    let keys = zip_archive
-        .by_name("key")
-        .ok()
-        .map(|key_file| {
-            let mut keys = Box::new(HashMap::new());
-            for line in BufReader::new(key_file).lines() {
-                let line = line.unwrap();
-                if line.is_empty() {
-                    continue;
-                }
-                let mut split = line.split(" ");
-                let block_id = split
-                    .next()
-                    .expect("getting block num from key line split")
-                    .parse::<u16>()
-                    .expect("converting block num to int");
-                let block_name = split
-                    .next()
-                    .expect("getting block name from key line split")
-                    .to_string();
-                if max_key < block_id {
-                    max_key = block_id;
-                }
-                keys.insert(block_name, block_id);
-            }
-            *keys
-        })
-        .expect("XXX support old keyless format");
-
-    let unknown_name = "?".to_string();
-    let mut names: NamesVec = vec![unknown_name; 1 + max_key as usize];
-    for (name, nr) in keys.iter() {
-        names[*nr as usize] = name.clone();
+    let (keys, names) = match zip_archive.by_name("key") {
+        Ok(key_file) => read_key_file(key_file)?,
+        Err(_) => panic!("XXX support old keyless format"),
+    };
+    // caches without control file are version 2 as well
+    let version = match zip_archive.by_name("control") {
+        Ok(control_file) => read_control_version(control_file)?,
+        Err(_) => 2,
+    };
+    if version != 2 {
+        return Err(format!("Unsupported tile version {}", version));
     }
 
-    let mut data = vec![0; TILE_COLUMNS * COLUMN_BYTES_MODERN];
+    let mut data = vec![0; TILE_COLUMNS * COLUMN_BYTES_EXTENDED];
     {
         let mut data_file = zip_archive
             .by_name("data")
             .map_err(|_e| "No data file in tile zip")?;
         data_file
-            .read_exact(&mut *data)
+            .read_exact(&mut data[..TILE_COLUMNS * COLUMN_BYTES_MODERN])
             .map_err(|e| e.to_string())?;
     }
 
-    let tile = Box::new(Tile {
+    let mut tile = Box::new(Tile {
         source: Some(tile_path.clone()),
         pos: get_xz_from_tile_path(tile_path).ok(),
-        data: data,
-        keys: keys,
-        names: names,
+        data,
+        keys,
+        names,
+        block_states: Vec::new(),
+        biome_keys: HashMap::new(),
+        biome_names: Vec::new(),
     });
 
+    unwrap_legacy_heights(&mut tile);
+    if let Ok(mut heights_file) = zip_archive.by_name(HEIGHTS_ENTRY) {
+        let mut heights = vec![0; TILE_COLUMNS * 2 * LAYERS.len()];
+        heights_file
+            .read_exact(&mut heights)
+            .map_err(|e| format!("Invalid {}: {}", HEIGHTS_ENTRY, e))?;
+        for (layer_nr, layer) in LAYERS.iter().enumerate() {
+            for column_nr in 0..TILE_COLUMNS {
+                let index = 2 * (column_nr + TILE_COLUMNS * layer_nr);
+                let height = (heights[index] as u16) << 8 | heights[index + 1] as u16;
+                tile.set_layer_height(*layer, column_nr, height as i16 as i32);
+            }
+        }
+    }
+    let has_biome_keys = zip_archive.by_name(BIOME_IDS_ENTRY).is_ok();
+    if has_biome_keys {
+        let (biome_keys, biome_names) = match zip_archive.by_name(BIOME_KEYS_ENTRY) {
+            Ok(biomes_file) => read_key_file(biomes_file)?,
+            Err(_) => return Err(format!("{} without {}", BIOME_IDS_ENTRY, BIOME_KEYS_ENTRY)),
+        };
+        let mut biome_ids = vec![0; TILE_COLUMNS * 2];
+        zip_archive
+            .by_name(BIOME_IDS_ENTRY)
+            .and_then(|mut biome_ids_file| Ok(biome_ids_file.read_exact(&mut biome_ids)?))
+            .map_err(|e| format!("Invalid {}: {}", BIOME_IDS_ENTRY, e))?;
+        tile.biome_keys = biome_keys;
+        tile.biome_names = biome_names;
+        for column_nr in 0..TILE_COLUMNS {
+            let biome_id =
+                (biome_ids[2 * column_nr] as u16) << 8 | biome_ids[2 * column_nr + 1] as u16;
+            tile.set_biome_id(column_nr, biome_id);
+        }
+    }
+    tile.flatten_block_names();
+
     Ok(tile)
 }
 
+/// Lines of `<id> <name>`, as in the `key` and `biomes` files.
+fn read_key_file<R: std::io::Read>(key_file: R) -> Result<(KeysMap, NamesVec), String> {
+    use std::io::{BufRead, BufReader};
+
+    let mut keys = HashMap::new();
+    let mut max_key = 0;
+    for line in BufReader::new(key_file).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.is_empty() {
+            continue;
+        }
+        let mut split = line.splitn(2, " ");
+        let id = split
+            .next()
+            .unwrap()
+            .parse::<u16>()
+            .map_err(|e| format!("Invalid key line {:?}: {}", line, e))?;
+        let name = split
+            .next()
+            .ok_or(format!("No name in key line {:?}", line))?
+            .to_string();
+        if max_key < id {
+            max_key = id;
+        }
+        keys.insert(name, id);
+    }
+
+    let mut names: NamesVec = vec!["?".to_string(); 1 + max_key as usize];
+    for (name, id) in keys.iter() {
+        names[*id as usize] = name.clone();
+    }
+    Ok((keys, names))
+}
+
+fn read_control_version<R: std::io::Read>(control_file: R) -> Result<u32, String> {
+    use std::io::{BufRead, BufReader};
+
+    for line in BufReader::new(control_file).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if let Some(version) = line.strip_prefix("version:") {
+            return version
+                .trim()
+                .parse()
+                .map_err(|e: ParseIntError| format!("Invalid tile version: {}", e));
+        }
+    }
+    Ok(2)
+}
+
+/// Version 2 caches wrap the height of blocks at the 1.17 height limit from 256 to 0.
+fn unwrap_legacy_heights(tile: &mut Tile) {
+    for column_nr in 0..TILE_COLUMNS {
+        for layer in LAYERS.iter() {
+            if tile.get_layer_height(*layer, column_nr) == 0
+                && tile.get_layer_blockstate(*layer, column_nr) != 0
+            {
+                tile.set_layer_height(*layer, column_nr, 256);
+            }
+        }
+    }
+}
+
 pub fn write_tile(tile_path: &PathBuf, tile: &Tile) -> Result<(), String> {
     use std::io::Write;
 
-    let zip_file = fs::File::create(&tile_path).map_err(|e| e.to_string())?;
+    let zip_file = fs::File::create(tile_path).map_err(|e| e.to_string())?;
     let mut zip_archive = zip::ZipWriter::new(zip_file);
 
     let options =
         zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
 
-    // we can only write version 2
-
     zip_archive
         .start_file("control", options)
         .map_err(|e| e.to_string())?;
     zip_archive
-        .write_all("version:2\r\n".as_bytes())
+        .write_fmt(format_args!("version:2\r\n"))
         .map_err(|e| e.to_string())?;
 
+    // in version 2, the height 256 wraps to 0 by leaving out the high byte,
+    // heights outside 0..=256 are clamped to the 1.17 world, the full ones go to HEIGHTS_ENTRY
+    let mut data = tile.data[..TILE_COLUMNS * COLUMN_BYTES_MODERN].to_vec();
+    // VoxelMap only knows the legacy biome ids, the keys go to BIOME_KEYS_ENTRY;
+    // biomes added since 1.13 become plains, like in export_anvil
+    if !tile.biome_keys.is_empty() {
+        for column_nr in 0..TILE_COLUMNS {
+            if tile.is_col_empty(column_nr) {
+                continue;
+            }
+            let legacy_id = tile
+                .get_biome(column_nr)
+                .and_then(|biome| biome.legacy_id)
+                .unwrap_or(1);
+            let index = column_nr + TILE_COLUMNS * BIOMEIDPOS;
+            data[index] = 0;
+            data[index + TILE_COLUMNS] = legacy_id;
+        }
+    }
+    let fits_legacy_heights = tile.fits_legacy_heights();
+    if !fits_legacy_heights {
+        for column_nr in 0..TILE_COLUMNS {
+            for (layer, height_pos) in LAYERS.iter().zip(&[
+                HEIGHTPOS,
+                OCEANFLOORHEIGHTPOS,
+                TRANSPARENTHEIGHTPOS,
+                FOLIAGEHEIGHTPOS,
+            ]) {
+                let height = tile.get_layer_height(*layer, column_nr);
+                if !(0..=256).contains(&height) {
+                    data[column_nr + TILE_COLUMNS * height_pos] = height.clamp(1, 255) as u8;
+                }
+            }
+        }
+    }
     zip_archive
         .start_file("data", options)
         .map_err(|e| e.to_string())?;
-    zip_archive
-        .write_all(&tile.data)
-        .map_err(|e| e.to_string())?;
+    zip_archive.write_all(&data).map_err(|e| e.to_string())?;
+
+    if !fits_legacy_heights {
+        let mut heights = Vec::with_capacity(TILE_COLUMNS * 2 * LAYERS.len());
+        for layer in LAYERS.iter() {
+            for column_nr in 0..TILE_COLUMNS {
+                let height = tile.get_layer_height(*layer, column_nr) as i16;
+                heights.extend_from_slice(&height.to_be_bytes());
+            }
+        }
+        zip_archive
+            .start_file(HEIGHTS_ENTRY, options)
+            .map_err(|e| e.to_string())?;
+        zip_archive.write_all(&heights).map_err(|e| e.to_string())?;
+    }
 
     zip_archive
         .start_file("key", options)
@@ -283,13 +698,53 @@ pub fn write_tile(tile_path: &PathBuf, tile: &Tile) -> Result<(), String> {
             .map_err(|e| e.to_string())?;
     }
 
+    if !tile.biome_keys.is_empty() {
+        zip_archive
+            .start_file(BIOME_KEYS_ENTRY, options)
+            .map_err(|e| e.to_string())?;
+        for (name, nr) in &tile.biome_keys {
+            zip_archive
+                .write_fmt(format_args!("{} {}\r\n", nr, name))
+                .map_err(|e| e.to_string())?;
+        }
+        let biome_ids: Vec<u8> = (0..TILE_COLUMNS)
+            .flat_map(|column_nr| tile.get_biome_id(column_nr).to_be_bytes().to_vec())
+            .collect();
+        zip_archive
+            .start_file(BIOME_IDS_ENTRY, options)
+            .map_err(|e| e.to_string())?;
+        zip_archive
+            .write_all(&biome_ids)
+            .map_err(|e| e.to_string())?;
+    }
+
     // Optionally finish the zip. (this is also done on drop)
     zip_archive.finish().map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
-pub fn get_xz_from_tile_path(tile_path: &PathBuf) -> Result<TilePos, String> {
+/// Writes converted tiles as `<x>,<z>.zip`, or `<x>,<z>,<contrib>.zip` so they can be merged
+/// with other contributions. They get the modification time of their source,
+/// so merge_caches can prefer more recent contributions.
+pub fn write_contrib_tiles(
+    tiles: &[Tile],
+    output_path: &str,
+    contrib: Option<&str>,
+    mtime: SystemTime,
+) -> Result<(), String> {
+    let contrib_suffix = contrib.map_or(String::new(), |contrib| format!(",{}", contrib));
+    for tile in tiles {
+        let (x, z) = tile.pos.ok_or("Converted tile has no position")?;
+        let tile_path = PathBuf::from(format!("{}/{},{}{}.zip", output_path, x, z, contrib_suffix));
+        write_tile(&tile_path, tile)?;
+        set_file_times(&tile_path, FileTime::from(mtime), FileTime::from(mtime))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+pub fn get_xz_from_tile_path(tile_path: &Path) -> Result<TilePos, String> {
     let fname = tile_path.file_name().unwrap().to_str().unwrap();
     if fname.len() <= 4 {
         return Err("file name too short".to_owned());
@@ -309,7 +764,7 @@ pub fn get_xz_from_tile_path(tile_path: &PathBuf) -> Result<TilePos, String> {
     Ok((x, z))
 }
 
-pub fn get_contrib_from_tile_path(tile_path: &PathBuf) -> Result<String, String> {
+pub fn get_contrib_from_tile_path(tile_path: &Path) -> Result<String, String> {
     let fname = tile_path.file_name().unwrap().to_str().unwrap();
     if fname.len() <= 4 {
         return Err("no contrib in filename".to_owned());
@@ -317,8 +772,7 @@ pub fn get_contrib_from_tile_path(tile_path: &PathBuf) -> Result<String, String>
     let (coords_part, _) = fname.split_at(fname.len() - 4);
     Ok(coords_part
         .splitn(3, ',')
-        .skip(2)
-        .next()
+        .nth(2)
         .ok_or("No contrib in tile name")?
         .to_string())
 }
@@ -354,7 +808,7 @@ pub fn get_tile_paths_in_dirs(
     Ok(tile_paths)
 }
 
-pub fn is_tile_pos_in_bounds((tile_x, tile_z): (i32, i32), bounds: &Vec<i32>) -> bool {
+pub fn is_tile_pos_in_bounds((tile_x, tile_z): (i32, i32), bounds: &[i32]) -> bool {
     let tw = TILE_WIDTH as i32;
     let th = TILE_HEIGHT as i32;
     let x = tile_x * tw;
@@ -363,3 +817,151 @@ pub fn is_tile_pos_in_bounds((tile_x, tile_z): (i32, i32), bounds: &Vec<i32>) ->
 
     x + tw > w && x < e && z + th > n && z < s
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_and_read(tile: &Tile, file_name: &str) -> Box<Tile> {
+        let tile_path = std::env::temp_dir().join(file_name);
+        write_tile(&tile_path, tile).unwrap();
+        let read = read_tile(&tile_path).unwrap();
+        fs::remove_file(&tile_path).unwrap();
+        read
+    }
+
+    /// The `data` entry as VoxelMap sees it, which is always version 2.
+    fn write_voxelmap_data(tile: &Tile, file_name: &str) -> Vec<u8> {
+        use std::io::Read;
+
+        let tile_path = std::env::temp_dir().join(file_name);
+        write_tile(&tile_path, tile).unwrap();
+        let mut zip_archive = zip::ZipArchive::new(fs::File::open(&tile_path).unwrap()).unwrap();
+        let mut control = String::new();
+        zip_archive
+            .by_name("control")
+            .unwrap()
+            .read_to_string(&mut control)
+            .unwrap();
+        assert_eq!(control, "version:2\r\n");
+        let mut data = Vec::new();
+        zip_archive
+            .by_name("data")
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        fs::remove_file(&tile_path).unwrap();
+        assert_eq!(data.len(), TILE_COLUMNS * COLUMN_BYTES_MODERN);
+        data
+    }
+
+    #[test]
+    fn keeps_extended_heights() {
+        let mut tile = Tile::new(None);
+        let stone = tile.get_or_add_key("minecraft:stone");
+        tile.set_blockstate(0, stone);
+        tile.set_height(0, 256);
+        tile.set_blockstate(1, stone);
+        tile.set_height(1, 70);
+
+        // fits version 2, where 256 wraps to 0
+        let read = write_and_read(&tile, "0,0,extended-heights-v2.zip");
+        assert_eq!(read.get_height(0), 256);
+        assert_eq!(read.get_height(1), 70);
+        assert!(read.is_col_empty(2));
+
+        tile.set_ocean_floor_blockstate(1, stone);
+        tile.set_ocean_floor_height(1, -50);
+        tile.set_foliage_blockstate(1, stone);
+        tile.set_foliage_height(1, 320);
+        // clamped to the 1.17 world for VoxelMap
+        let data = write_voxelmap_data(&tile, "0,0,extended-heights-data.zip");
+        assert_eq!(data[1 + TILE_COLUMNS * OCEANFLOORHEIGHTPOS], 1);
+        assert_eq!(data[1 + TILE_COLUMNS * FOLIAGEHEIGHTPOS], 255);
+        let read = write_and_read(&tile, "0,0,extended-heights.zip");
+        assert_eq!(read.get_height(0), 256);
+        assert_eq!(read.get_height(1), 70);
+        assert_eq!(read.get_ocean_floor_height(1), -50);
+        assert_eq!(read.get_foliage_height(1), 320);
+        assert_eq!(read.get_layer_height(Layer::Foliage, 1), 320);
+    }
+
+    #[test]
+    fn converts_legacy_biomes_to_keys() {
+        let mut tile = Tile::new(None);
+        let stone = tile.get_or_add_key("minecraft:stone");
+        tile.set_blockstate(0, stone);
+        tile.set_biome_id(0, 3);
+        assert_eq!(tile.get_biome_name(0), "minecraft:windswept_hills");
+
+        let cherry_grove = tile.get_or_add_biome_key("minecraft:cherry_grove");
+        tile.set_blockstate(1, stone);
+        tile.set_biome_id(1, cherry_grove);
+        let modded = tile.get_or_add_biome_key("modded:crystal_fields");
+        tile.set_blockstate(2, stone);
+        tile.set_biome_id(2, modded);
+
+        // legacy ids for VoxelMap, newer biomes become plains
+        let data = write_voxelmap_data(&tile, "0,0,biome-keys-data.zip");
+        let legacy_biome_id = |column_nr: usize| data[column_nr + TILE_COLUMNS * (BIOMEIDPOS + 1)];
+        assert_eq!(legacy_biome_id(0), 3);
+        assert_eq!(legacy_biome_id(1), 1);
+        assert_eq!(legacy_biome_id(2), 1);
+        assert_eq!(legacy_biome_id(3), 0);
+
+        let read = write_and_read(&tile, "0,0,biome-keys.zip");
+        assert_eq!(read.get_biome_name(0), "minecraft:windswept_hills");
+        assert_eq!(read.get_biome(0).and_then(|biome| biome.legacy_id), Some(3));
+        assert_eq!(read.get_biome_name(1), "minecraft:cherry_grove");
+        assert_eq!(read.get_biome_name(2), "modded:crystal_fields");
+        assert_eq!(read.get_biome(2), None);
+        assert!(read.is_col_empty(3));
+    }
+
+    #[test]
+    fn flattens_legacy_block_names() {
+        let mut tile = Tile::new(None);
+        let leaves =
+            tile.get_or_add_key("minecraft:leaves[check_decay=false,decayable=true,variant=oak]");
+        let checked =
+            tile.get_or_add_key("minecraft:leaves[check_decay=true,decayable=true,variant=oak]");
+        let grass = tile.get_or_add_key("minecraft:grass[snowy=false]");
+        tile.set_blockstate(0, grass);
+        tile.set_foliage_blockstate(0, checked);
+        tile.set_blockstate(1, leaves);
+
+        let read = write_and_read(&tile, "0,0,flattened.zip");
+        assert_eq!(
+            read.get_block_name(read.get_blockstate(0)),
+            "minecraft:grass_block[snowy=false]"
+        );
+        assert_eq!(read.get_foliage_blockstate(0), read.get_blockstate(1));
+        assert_eq!(
+            read.get_block_name(read.get_blockstate(1)),
+            "minecraft:oak_leaves[persistent=false]"
+        );
+        assert_eq!(read.keys.len(), 2);
+    }
+
+    #[test]
+    fn reads_key_gaps_as_unknown_blocks() {
+        let mut tile = Tile::new(None);
+        let stone = tile.get_or_add_key("minecraft:stone");
+        let removed = tile.get_or_add_key("minecraft:dirt");
+        let sand = tile.get_or_add_key("minecraft:sand");
+        tile.keys.remove("minecraft:dirt");
+        tile.set_blockstate(0, stone);
+        tile.set_blockstate(1, removed);
+        tile.set_blockstate(2, sand);
+
+        let read = write_and_read(&tile, "0,0,key-gap.zip");
+        assert_eq!(read.get_block_name(removed), "?");
+        assert!(read.get_block_state(removed).is_unknown());
+        assert!(!read.get_block_state(stone).is_unknown());
+        assert_eq!(read.get_block_state(sand).name(), "sand");
+        assert!(std::ptr::eq(
+            read.get_block_state(1000),
+            BlockState::unknown()
+        ));
+    }
+}
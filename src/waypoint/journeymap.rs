@@ -0,0 +1,155 @@
+extern crate serde_json;
+
+use super::voxelmap::DEATH_ICON;
+use super::{normalize_dimension, Waypoint, END, NETHER, OVERWORLD};
+use serde::{Deserialize, Serialize};
+
+const JM_NORMAL_ICON: &str = "waypoint-normal.png";
+const JM_DEATH_ICON: &str = "waypoint-death.png";
+const JM_TYPE_NORMAL: &str = "Normal";
+const JM_TYPE_DEATH: &str = "Death";
+
+/// JourneyMap stores each waypoint in its own file, named after its id.
+#[derive(Debug, Serialize, Deserialize)]
+struct JourneyMapWaypoint {
+    id: String,
+    name: String,
+    #[serde(default)]
+    icon: String,
+    x: i32,
+    y: i32,
+    z: i32,
+    r: u8,
+    g: u8,
+    b: u8,
+    #[serde(default = "default_true")]
+    enable: bool,
+    #[serde(rename = "type", default)]
+    type_: String,
+    #[serde(default)]
+    origin: String,
+    #[serde(default)]
+    dimensions: Vec<JourneyMapDimension>,
+    #[serde(default = "default_true")]
+    persistent: bool,
+}
+
+/// Before 1.16, JourneyMap used numeric dimension IDs.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum JourneyMapDimension {
+    Id(i32),
+    Name(String),
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Parses the contents of one JourneyMap waypoint file.
+pub fn parse_journeymap(text: &str) -> Result<Waypoint, String> {
+    let json: JourneyMapWaypoint = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let mut waypoint = Waypoint::new(&json.name, json.x, json.y, json.z);
+    waypoint.color = 0xff_00_00_00 | (json.b as u32) << 16 | (json.g as u32) << 8 | json.r as u32;
+    waypoint.enabled = json.enable;
+    waypoint.death = json.type_ == JM_TYPE_DEATH;
+    if json.icon != JM_NORMAL_ICON && json.icon != JM_DEATH_ICON {
+        waypoint.icon = json.icon;
+    }
+    waypoint.dimensions = json
+        .dimensions
+        .iter()
+        .map(|dimension| match dimension {
+            JourneyMapDimension::Id(id) => normalize_dimension(&id.to_string()),
+            JourneyMapDimension::Name(name) => normalize_dimension(name),
+        })
+        .collect();
+    Ok(waypoint)
+}
+
+/// The contents of a JourneyMap waypoint file, using namespaced dimension names as of 1.16.
+pub fn write_journeymap(waypoint: &Waypoint) -> String {
+    let icon = if waypoint.death {
+        JM_DEATH_ICON
+    } else {
+        JM_NORMAL_ICON
+    };
+    let json = JourneyMapWaypoint {
+        id: get_journeymap_id(waypoint),
+        name: waypoint.name.clone(),
+        icon: icon.to_string(),
+        x: waypoint.x,
+        y: waypoint.y,
+        z: waypoint.z,
+        r: (waypoint.color & 0xff) as u8,
+        g: (waypoint.color >> 8 & 0xff) as u8,
+        b: (waypoint.color >> 16 & 0xff) as u8,
+        enable: waypoint.enabled,
+        type_: if waypoint.death {
+            JM_TYPE_DEATH
+        } else {
+            JM_TYPE_NORMAL
+        }
+        .to_string(),
+        origin: "journeymap".to_string(),
+        dimensions: waypoint
+            .dimensions
+            .iter()
+            .map(|dimension| {
+                JourneyMapDimension::Name(match dimension.as_str() {
+                    OVERWORLD | NETHER | END => format!("minecraft:{}", dimension),
+                    _ => dimension.clone(),
+                })
+            })
+            .collect(),
+        persistent: true,
+    };
+    serde_json::to_string_pretty(&json).expect("serializing waypoint")
+}
+
+/// Also the file name, without `.json`.
+pub fn get_journeymap_id(waypoint: &Waypoint) -> String {
+    let name: String = waypoint
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}_{},{},{}", name, waypoint.x, waypoint.y, waypoint.z)
+}
+
+/// What gets lost when writing this waypoint as a JourneyMap waypoint.
+pub fn get_journeymap_losses(waypoint: &Waypoint) -> Vec<String> {
+    let mut losses = Vec::new();
+    if !(waypoint.icon.is_empty() || waypoint.death && waypoint.icon == DEATH_ICON) {
+        losses.push(format!("icon {:?}", waypoint.icon));
+    }
+    if !waypoint.world.is_empty() {
+        losses.push(format!("world {:?}", waypoint.world));
+    }
+    losses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_old_and_new_dimensions() {
+        let text = r#"{"id": "Home_1,2,3", "name": "Home", "icon": "waypoint-normal.png",
+            "x": 1, "y": 2, "z": 3, "r": 255, "g": 128, "b": 0, "enable": true,
+            "type": "Normal", "origin": "journeymap", "dimensions": [-1, "minecraft:the_end"],
+            "persistent": true}"#;
+        let waypoint = parse_journeymap(text).unwrap();
+        assert_eq!(waypoint.color, 0xff_00_80_ff);
+        assert_eq!(waypoint.icon, "");
+        assert_eq!(waypoint.dimensions, vec![NETHER, END]);
+        let written = parse_journeymap(&write_journeymap(&waypoint)).unwrap();
+        assert_eq!(written, waypoint);
+    }
+}
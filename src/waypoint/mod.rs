@@ -0,0 +1,360 @@
+extern crate serde_json;
+
+pub mod journeymap;
+pub mod voxelmap;
+pub mod xaero;
+
+use self::journeymap::{get_journeymap_losses, parse_journeymap};
+use self::voxelmap::parse_points;
+use self::xaero::{get_dimension_of_xaero_dir, get_xaero_losses, parse_xaero};
+use crate::palette::{format_color, parse_color};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// VoxelMap leaves out `y` in old `.points` files.
+pub const DEFAULT_Y: i32 = 64;
+
+pub const OVERWORLD: &str = "overworld";
+pub const NETHER: &str = "the_nether";
+pub const END: &str = "the_end";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Waypoint {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    /// ABGR, like the rendered tiles
+    pub color: u32,
+    pub enabled: bool,
+    /// created when the player died there
+    pub death: bool,
+    /// VoxelMap's `suffix`, the name of the marker icon
+    pub icon: String,
+    /// VoxelMap's subworld name, empty if the server has only one world
+    pub world: String,
+    /// normalized with `normalize_dimension`
+    pub dimensions: Vec<String>,
+    /// contributors that have this waypoint
+    pub sources: Vec<String>,
+}
+
+impl Waypoint {
+    pub fn new(name: &str, x: i32, y: i32, z: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            x,
+            y,
+            z,
+            color: 0xff_ff_ff_ff,
+            enabled: true,
+            death: false,
+            icon: String::new(),
+            world: String::new(),
+            dimensions: vec![OVERWORLD.to_string()],
+            sources: Vec::new(),
+        }
+    }
+
+    /// Waypoints without any dimension are treated as being in the overworld.
+    pub fn is_in_dimension(&self, dimension: &str) -> bool {
+        let dimension = normalize_dimension(dimension);
+        if self.dimensions.is_empty() {
+            return dimension == OVERWORLD;
+        }
+        self.dimensions.contains(&dimension)
+    }
+
+    /// bounds as returned by `parse_bounds`: w,n,e,s
+    pub fn is_in_bounds(&self, bounds: &[i32]) -> bool {
+        let (w, n, e, s) = (bounds[0], bounds[1], bounds[2], bounds[3]);
+        w <= self.x && self.x < e && n <= self.z && self.z < s
+    }
+}
+
+/// Maps the numeric dimension IDs of old VoxelMap versions and
+/// namespaced dimension names to VoxelMap's current names.
+pub fn normalize_dimension(dimension: &str) -> String {
+    let dimension = dimension.trim();
+    let dimension = dimension.trim_start_matches("minecraft:");
+    match dimension {
+        "0" => OVERWORLD.to_string(),
+        "-1" => NETHER.to_string(),
+        "1" => END.to_string(),
+        _ => dimension.to_lowercase(),
+    }
+}
+
+/// Combines waypoints of the same name and dimensions
+/// that are at most `max_distance` blocks apart horizontally,
+/// keeping the position of the first one and the sources of all of them.
+/// Names are compared ignoring case and surrounding whitespace,
+/// dimensions ignoring their order.
+pub fn dedup_waypoints(waypoints: Vec<Waypoint>, max_distance: i32) -> Vec<Waypoint> {
+    let mut unique: Vec<Waypoint> = Vec::new();
+    for waypoint in waypoints {
+        let key = waypoint.name.trim().to_lowercase();
+        let dimensions: BTreeSet<&String> = waypoint.dimensions.iter().collect();
+        let duplicate = unique.iter_mut().find(|other| {
+            other.name.trim().to_lowercase() == key
+                && other.dimensions.iter().collect::<BTreeSet<_>>() == dimensions
+                && (other.x - waypoint.x).abs() <= max_distance
+                && (other.z - waypoint.z).abs() <= max_distance
+        });
+        match duplicate {
+            Some(other) => {
+                other.enabled |= waypoint.enabled;
+                for source in waypoint.sources {
+                    if !other.sources.contains(&source) {
+                        other.sources.push(source);
+                    }
+                }
+            }
+            None => unique.push(waypoint),
+        }
+    }
+    unique.sort_by(|a, b| (&a.name, a.x, a.z).cmp(&(&b.name, b.x, b.z)));
+    unique
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WaypointJson {
+    name: String,
+    x: i32,
+    y: i32,
+    z: i32,
+    color: String,
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default)]
+    death: bool,
+    #[serde(default)]
+    icon: String,
+    #[serde(default)]
+    world: String,
+    #[serde(default)]
+    dimensions: Vec<String>,
+    #[serde(default)]
+    sources: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl WaypointJson {
+    fn from_waypoint(waypoint: &Waypoint) -> Self {
+        Self {
+            name: waypoint.name.clone(),
+            x: waypoint.x,
+            y: waypoint.y,
+            z: waypoint.z,
+            color: format_color(waypoint.color),
+            enabled: waypoint.enabled,
+            death: waypoint.death,
+            icon: waypoint.icon.clone(),
+            world: waypoint.world.clone(),
+            dimensions: waypoint.dimensions.clone(),
+            sources: waypoint.sources.clone(),
+        }
+    }
+
+    fn into_waypoint(self) -> Result<Waypoint, String> {
+        Ok(Waypoint {
+            color: parse_color(&self.color)?,
+            name: self.name,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            enabled: self.enabled,
+            death: self.death,
+            icon: self.icon,
+            world: self.world,
+            dimensions: self
+                .dimensions
+                .iter()
+                .map(|d| normalize_dimension(d))
+                .collect(),
+            sources: self.sources,
+        })
+    }
+}
+
+pub fn waypoints_to_json(waypoints: &[Waypoint]) -> String {
+    let json: Vec<_> = waypoints.iter().map(WaypointJson::from_waypoint).collect();
+    serde_json::to_string_pretty(&json).expect("serializing waypoints")
+}
+
+pub fn parse_waypoints_json(text: &str) -> Result<Vec<Waypoint>, String> {
+    let json: Vec<WaypointJson> = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    json.into_iter().map(WaypointJson::into_waypoint).collect()
+}
+
+#[derive(Debug, Serialize)]
+struct GeoJsonFeatureCollection {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    features: Vec<GeoJsonFeature>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeoJsonFeature {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    geometry: GeoJsonPoint,
+    properties: WaypointJson,
+}
+
+#[derive(Debug, Serialize)]
+struct GeoJsonPoint {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    coordinates: [i32; 2],
+}
+
+/// A FeatureCollection of Points at `[x, z]`, as used by CivMap,
+/// with all other waypoint fields in the properties.
+pub fn waypoints_to_geojson(waypoints: &[Waypoint]) -> String {
+    let json = GeoJsonFeatureCollection {
+        type_: "FeatureCollection",
+        features: waypoints
+            .iter()
+            .map(|waypoint| GeoJsonFeature {
+                type_: "Feature",
+                geometry: GeoJsonPoint {
+                    type_: "Point",
+                    coordinates: [waypoint.x, waypoint.z],
+                },
+                properties: WaypointJson::from_waypoint(waypoint),
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&json).expect("serializing waypoints")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WaypointFormat {
+    /// `.points` files
+    VoxelMap,
+    /// `.txt` files, one per dimension, in `dim%...` directories
+    Xaero,
+    /// `.json` files, one per waypoint, in a `waypoints` directory
+    JourneyMap,
+    /// as written by `waypoints_to_json`
+    Json,
+}
+
+impl WaypointFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "voxelmap" => Some(WaypointFormat::VoxelMap),
+            "xaero" => Some(WaypointFormat::Xaero),
+            "journeymap" => Some(WaypointFormat::JourneyMap),
+            "json" => Some(WaypointFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format from the file name and the directory it is in,
+    /// None if it doesn't look like a waypoints file.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let parent_name = path
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("points") => Some(WaypointFormat::VoxelMap),
+            Some("txt") if parent_name.starts_with("dim%") => Some(WaypointFormat::Xaero),
+            Some("json") if parent_name == "waypoints" => Some(WaypointFormat::JourneyMap),
+            Some("json") => Some(WaypointFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// What gets lost when writing this waypoint in that format,
+/// such as colors that have to be approximated.
+pub fn get_format_losses(waypoint: &Waypoint, format: WaypointFormat) -> Vec<String> {
+    match format {
+        WaypointFormat::Xaero => get_xaero_losses(waypoint),
+        WaypointFormat::JourneyMap => get_journeymap_losses(waypoint),
+        WaypointFormat::VoxelMap | WaypointFormat::Json => Vec::new(),
+    }
+}
+
+/// Reads any waypoints file, see `WaypointFormat::from_path`.
+/// Xaero waypoints get the dimension of their directory.
+pub fn read_waypoints(path: &Path) -> Result<Vec<Waypoint>, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    match WaypointFormat::from_path(path) {
+        Some(WaypointFormat::VoxelMap) => parse_points(&text),
+        Some(WaypointFormat::Xaero) => {
+            let dir_name = path
+                .parent()
+                .and_then(|parent| parent.file_name())
+                .and_then(|name| name.to_str())
+                .unwrap_or("");
+            let dimension = get_dimension_of_xaero_dir(dir_name).unwrap_or(OVERWORLD.to_string());
+            parse_xaero(&text, &dimension)
+        }
+        Some(WaypointFormat::JourneyMap) => Ok(vec![parse_journeymap(&text)?]),
+        Some(WaypointFormat::Json) => parse_waypoints_json(&text),
+        None => Err("unknown waypoints file format".to_string()),
+    }
+}
+
+/// Finds all waypoints files in `path` and its subdirectories,
+/// or just `path` itself if it is a file.
+pub fn get_waypoint_paths(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
+        let entry_path = entry.map_err(|e| e.to_string())?.path();
+        if entry_path.is_dir() {
+            paths.extend(get_waypoint_paths(&entry_path)?);
+            continue;
+        }
+        // other mods' config files are JSON too
+        match WaypointFormat::from_path(&entry_path) {
+            Some(WaypointFormat::Json) | None => {}
+            Some(_) => paths.push(entry_path),
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedups_across_contributors() {
+        let mut a = Waypoint::new("Spawn", 0, 64, 0);
+        a.sources = vec!["alice".to_string()];
+        let mut b = Waypoint::new("spawn ", 3, 64, -2);
+        b.sources = vec!["bob".to_string()];
+        let mut c = Waypoint::new("Spawn", 500, 64, 0);
+        c.sources = vec!["bob".to_string()];
+        let waypoints = dedup_waypoints(vec![a, b, c], 8);
+        assert_eq!(waypoints.len(), 2);
+        assert_eq!(waypoints[0].sources, vec!["alice", "bob"]);
+        assert_eq!(waypoints[1].x, 500);
+    }
+
+    #[test]
+    fn dedups_dimensions_in_any_order() {
+        let mut a = Waypoint::new("Portal", 0, 64, 0);
+        a.dimensions = vec![OVERWORLD.to_string(), END.to_string()];
+        let mut b = Waypoint::new("Portal", 0, 64, 0);
+        b.dimensions = vec![END.to_string(), OVERWORLD.to_string()];
+        let mut c = Waypoint::new("Portal", 0, 64, 0);
+        c.dimensions = vec![NETHER.to_string()];
+        assert_eq!(dedup_waypoints(vec![a, b, c], 8).len(), 2);
+    }
+}
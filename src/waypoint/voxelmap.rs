@@ -0,0 +1,154 @@
+use super::{normalize_dimension, Waypoint, DEFAULT_Y};
+
+/// VoxelMap's icon for the waypoints it creates on death.
+pub const DEATH_ICON: &str = "skull";
+
+/// Parses the contents of a VoxelMap `.points` file.
+/// Each waypoint is one line of comma separated `key:value` pairs, for example
+/// `name:Spawn,x:0,z:0,y:64,enabled:true,red:1.0,green:0.5,blue:0.0,suffix:,world:,dimensions:overworld#`
+pub fn parse_points(text: &str) -> Result<Vec<Waypoint>, String> {
+    let mut waypoints = Vec::new();
+    for (line_nr, line) in text.lines().enumerate() {
+        let line = line.trim();
+        // other lines list subworlds, seeds etc.
+        if !line.starts_with("name:") {
+            continue;
+        }
+        let waypoint =
+            parse_points_line(line).map_err(|e| format!("line {}: {}", line_nr + 1, e))?;
+        waypoints.push(waypoint);
+    }
+    Ok(waypoints)
+}
+
+fn parse_points_line(line: &str) -> Result<Waypoint, String> {
+    let mut waypoint = Waypoint::new("", 0, DEFAULT_Y, 0);
+    let (mut has_x, mut has_z) = (false, false);
+    let mut rgb = [1.0f32; 3];
+    for pair in line.split(',') {
+        let mut it = pair.splitn(2, ':');
+        let key = it.next().unwrap_or("");
+        let value = it.next().ok_or(format!("no value for {:?}", key))?;
+        let parse_int = || {
+            value
+                .parse::<i32>()
+                .map_err(|e| format!("invalid {} {:?}: {}", key, value, e))
+        };
+        let parse_float = || {
+            value
+                .parse::<f32>()
+                .map_err(|e| format!("invalid {} {:?}: {}", key, value, e))
+        };
+        match key {
+            "name" => waypoint.name = unescape_points_value(value),
+            "x" => {
+                waypoint.x = parse_int()?;
+                has_x = true;
+            }
+            "y" => waypoint.y = parse_int()?,
+            "z" => {
+                waypoint.z = parse_int()?;
+                has_z = true;
+            }
+            "enabled" => waypoint.enabled = value == "true",
+            "red" => rgb[0] = parse_float()?,
+            "green" => rgb[1] = parse_float()?,
+            "blue" => rgb[2] = parse_float()?,
+            "suffix" => waypoint.icon = unescape_points_value(value),
+            "world" => waypoint.world = unescape_points_value(value),
+            "dimensions" => {
+                waypoint.dimensions = value
+                    .split('#')
+                    .filter(|d| !d.is_empty())
+                    .map(normalize_dimension)
+                    .collect()
+            }
+            _ => {} // unknown keys from newer VoxelMap versions
+        }
+    }
+    if !has_x || !has_z {
+        return Err("missing x or z".to_string());
+    }
+    let channel = |f: f32| (f.clamp(0.0, 1.0) * 255.0).round() as u32;
+    waypoint.color = 0xff_00_00_00 | channel(rgb[2]) << 16 | channel(rgb[1]) << 8 | channel(rgb[0]);
+    waypoint.death = waypoint.icon == DEATH_ICON;
+    Ok(waypoint)
+}
+
+/// The contents of a `.points` file containing these waypoints,
+/// without any subworlds or seeds.
+pub fn write_points(waypoints: &[Waypoint]) -> String {
+    let mut text = String::from("subworlds:\noldNorthWorlds:\nseeds:\n");
+    for waypoint in waypoints {
+        let channel = |shift: u32| (waypoint.color >> shift & 0xff) as f32 / 255.0;
+        let icon = if waypoint.death && waypoint.icon.is_empty() {
+            DEATH_ICON
+        } else {
+            &waypoint.icon
+        };
+        let dimensions: String = waypoint
+            .dimensions
+            .iter()
+            .map(|d| format!("{}#", escape_points_value(d)))
+            .collect();
+        text += &format!(
+            "name:{},x:{},z:{},y:{},enabled:{},red:{},green:{},blue:{},suffix:{},world:{},dimensions:{}\n",
+            escape_points_value(&waypoint.name),
+            waypoint.x,
+            waypoint.z,
+            waypoint.y,
+            waypoint.enabled,
+            channel(0),
+            channel(8),
+            channel(16),
+            escape_points_value(icon),
+            escape_points_value(&waypoint.world),
+            dimensions,
+        );
+    }
+    text
+}
+
+/// VoxelMap replaces the separators in names with placeholders.
+fn unescape_points_value(value: &str) -> String {
+    value.replace("~comma~", ",").replace("~colon~", ":")
+}
+
+fn escape_points_value(value: &str) -> String {
+    value.replace(",", "~comma~").replace(":", "~colon~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_points_file() {
+        let text = "subworlds:\n\
+            oldNorthWorlds:\n\
+            name:Town~comma~ Hall,x:-120,z:45,y:70,enabled:true,red:1.0,green:0.0,blue:0.5,suffix:house,world:,dimensions:overworld#\n\
+            name:Old,x:1,z:2,enabled:false,red:0.0,green:1.0,blue:0.0,suffix:,world:,dimensions:-1#\n";
+        let waypoints = parse_points(text).unwrap();
+        assert_eq!(waypoints.len(), 2);
+        assert_eq!(waypoints[0].name, "Town, Hall");
+        assert_eq!(
+            (waypoints[0].x, waypoints[0].y, waypoints[0].z),
+            (-120, 70, 45)
+        );
+        assert_eq!(waypoints[0].color, 0xff_80_00_ff);
+        assert_eq!(waypoints[0].icon, "house");
+        assert_eq!(waypoints[1].y, DEFAULT_Y);
+        assert!(!waypoints[1].enabled);
+        assert!(waypoints[1].is_in_dimension("the_nether"));
+        assert!(parse_points("name:Broken,x:1").is_err());
+    }
+
+    #[test]
+    fn writes_what_it_reads() {
+        let mut waypoint = Waypoint::new("a:b, c", -1, 2, 3);
+        waypoint.color = 0xff_33_66_99;
+        waypoint.dimensions = vec!["overworld".to_string(), "the_end".to_string()];
+        let waypoints = vec![waypoint];
+        assert_eq!(parse_points(&write_points(&waypoints)).unwrap(), waypoints);
+    }
+}
@@ -0,0 +1,237 @@
+use super::voxelmap::DEATH_ICON;
+use super::{normalize_dimension, Waypoint, DEFAULT_Y, END, NETHER, OVERWORLD};
+use crate::palette::format_color;
+
+/// Xaero's waypoints can only have one of the 16 chat colors, ABGR.
+pub const XAERO_COLORS: [u32; 16] = [
+    0xff_00_00_00, // #000000 black
+    0xff_aa_00_00, // #0000aa dark blue
+    0xff_00_aa_00, // #00aa00 dark green
+    0xff_aa_aa_00, // #00aaaa dark aqua
+    0xff_00_00_aa, // #aa0000 dark red
+    0xff_aa_00_aa, // #aa00aa dark purple
+    0xff_00_aa_ff, // #ffaa00 gold
+    0xff_aa_aa_aa, // #aaaaaa gray
+    0xff_55_55_55, // #555555 dark gray
+    0xff_ff_55_55, // #5555ff blue
+    0xff_55_ff_55, // #55ff55 green
+    0xff_ff_ff_55, // #55ffff aqua
+    0xff_55_55_ff, // #ff5555 red
+    0xff_ff_55_ff, // #ff55ff light purple
+    0xff_55_ff_ff, // #ffff55 yellow
+    0xff_ff_ff_ff, // #ffffff white
+];
+
+pub const XAERO_DEFAULT_SET: &str = "gui.xaero_default";
+
+/// Xaero's waypoint type for death points, 0 is a normal waypoint.
+const XAERO_TYPE_DEATH: &str = "1";
+
+/// Xaero keeps one file per dimension, in a directory named
+/// `dim%0`, `dim%-1`, `dim%1`, or `dim%<namespace>$<path>` for modded dimensions.
+pub fn get_xaero_dimension_dir(dimension: &str) -> String {
+    let dimension = normalize_dimension(dimension);
+    match dimension.as_str() {
+        OVERWORLD => "dim%0".to_string(),
+        NETHER => "dim%-1".to_string(),
+        END => "dim%1".to_string(),
+        _ if dimension.contains(':') => format!("dim%{}", dimension.replace(":", "$")),
+        _ => format!("dim%minecraft${}", dimension),
+    }
+}
+
+/// Xaero's World Map keeps its regions in a directory per dimension, named differently from
+/// the waypoints': `null` for the overworld, `DIM-1` and `DIM1`.
+/// `None` for modded dimensions.
+pub fn get_xaero_world_map_dir(dimension: &str) -> Option<&'static str> {
+    match normalize_dimension(dimension).as_str() {
+        OVERWORLD => Some("null"),
+        NETHER => Some("DIM-1"),
+        END => Some("DIM1"),
+        _ => None,
+    }
+}
+
+/// The inverse of `get_xaero_dimension_dir`, None if `dir_name` isn't a dimension directory.
+pub fn get_dimension_of_xaero_dir(dir_name: &str) -> Option<String> {
+    if !dir_name.starts_with("dim%") {
+        return None;
+    }
+    Some(normalize_dimension(&dir_name[4..].replace("$", ":")))
+}
+
+/// Parses the contents of a Xaero waypoints file, such as `mw$default_1.txt`.
+/// The file doesn't say which dimension it is for, so that has to be given.
+/// Each waypoint is one line of colon separated values, for example
+/// `waypoint:Spawn:S:0:64:0:6:false:0:gui.xaero_default:false:0:0:false`
+pub fn parse_xaero(text: &str, dimension: &str) -> Result<Vec<Waypoint>, String> {
+    let mut waypoints = Vec::new();
+    for (line_nr, line) in text.lines().enumerate() {
+        let line = line.trim();
+        // other lines are comments or list the waypoint sets
+        if !line.starts_with("waypoint:") {
+            continue;
+        }
+        let mut waypoint =
+            parse_xaero_line(line).map_err(|e| format!("line {}: {}", line_nr + 1, e))?;
+        waypoint.dimensions = vec![normalize_dimension(dimension)];
+        waypoints.push(waypoint);
+    }
+    Ok(waypoints)
+}
+
+fn parse_xaero_line(line: &str) -> Result<Waypoint, String> {
+    let fields: Vec<&str> = line.split(':').collect();
+    if fields.len() < 10 {
+        return Err(format!("expected at least 10 fields, got {}", fields.len()));
+    }
+    let parse_int = |i: usize, key: &str| {
+        fields[i]
+            .parse::<i32>()
+            .map_err(|e| format!("invalid {} {:?}: {}", key, fields[i], e))
+    };
+    let mut waypoint = Waypoint::new(
+        &unescape_xaero_value(fields[1]),
+        parse_int(3, "x")?,
+        // `~` if the height is unknown
+        parse_int(4, "y").unwrap_or(DEFAULT_Y),
+        parse_int(5, "z")?,
+    );
+    let color_index = parse_int(6, "color")?;
+    waypoint.color = *XAERO_COLORS
+        .get(color_index as usize)
+        .ok_or(format!("invalid color {}", color_index))?;
+    waypoint.enabled = fields[7] != "true";
+    waypoint.death = fields[8] == XAERO_TYPE_DEATH;
+    Ok(waypoint)
+}
+
+/// The contents of a Xaero waypoints file containing these waypoints,
+/// ignoring their dimensions. Colors are rounded to the nearest chat color.
+pub fn write_xaero(waypoints: &[Waypoint]) -> String {
+    let mut text = String::from(
+        "#\n#waypoint:name:initials:x:y:z:color:disabled:type:set:rotate_on_tp:tp_yaw:visibility_type:destination\n#\n",
+    );
+    text += &format!("sets:{}\n#\n", XAERO_DEFAULT_SET);
+    for waypoint in waypoints {
+        text += &format!(
+            "waypoint:{}:{}:{}:{}:{}:{}:{}:{}:{}:false:0:0:false\n",
+            escape_xaero_value(&waypoint.name),
+            escape_xaero_value(&get_initials(&waypoint.name)),
+            waypoint.x,
+            waypoint.y,
+            waypoint.z,
+            get_nearest_xaero_color(waypoint.color),
+            !waypoint.enabled,
+            if waypoint.death {
+                XAERO_TYPE_DEATH
+            } else {
+                "0"
+            },
+            XAERO_DEFAULT_SET,
+        );
+    }
+    text
+}
+
+/// Xaero shows up to two characters in the marker, by default the name's first letter.
+fn get_initials(name: &str) -> String {
+    name.chars()
+        .find(|c| !c.is_whitespace())
+        .map(|c| c.to_uppercase().collect())
+        .unwrap_or_else(|| "X".to_string())
+}
+
+/// Index into `XAERO_COLORS`.
+pub fn get_nearest_xaero_color(color: u32) -> usize {
+    let distance = |other: u32| -> i32 {
+        [0, 8, 16]
+            .iter()
+            .map(|shift| {
+                let d = (color >> shift & 0xff) as i32 - (other >> shift & 0xff) as i32;
+                d * d
+            })
+            .sum()
+    };
+    (0..XAERO_COLORS.len())
+        .min_by_key(|i| distance(XAERO_COLORS[*i]))
+        .expect("non-empty color list")
+}
+
+/// What gets lost when writing this waypoint as a Xaero waypoint.
+pub fn get_xaero_losses(waypoint: &Waypoint) -> Vec<String> {
+    let mut losses = Vec::new();
+    let nearest = XAERO_COLORS[get_nearest_xaero_color(waypoint.color)];
+    if nearest & 0xff_ff_ff != waypoint.color & 0xff_ff_ff {
+        losses.push(format!(
+            "color {} approximated as {}",
+            format_color(waypoint.color | 0xff_00_00_00),
+            format_color(nearest)
+        ));
+    }
+    if !(waypoint.icon.is_empty() || waypoint.death && waypoint.icon == DEATH_ICON) {
+        losses.push(format!("icon {:?}", waypoint.icon));
+    }
+    if !waypoint.world.is_empty() {
+        losses.push(format!("world {:?}", waypoint.world));
+    }
+    losses
+}
+
+/// Xaero replaces the separators in names with `§§`.
+fn unescape_xaero_value(value: &str) -> String {
+    value.replace("§§", ":")
+}
+
+fn escape_xaero_value(value: &str) -> String {
+    value.replace(":", "§§")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_xaero_file() {
+        let text = "#\n\
+            sets:gui.xaero_default\n\
+            #\n\
+            waypoint:Town§§ Hall:T:-120:70:45:12:false:0:gui.xaero_default:false:0:0:false\n\
+            waypoint:Died:D:5:~:6:0:true:1:gui.xaero_default:false:0:0:false\n";
+        let waypoints = parse_xaero(text, NETHER).unwrap();
+        assert_eq!(waypoints.len(), 2);
+        assert_eq!(waypoints[0].name, "Town: Hall");
+        assert_eq!(waypoints[0].color, XAERO_COLORS[12]);
+        assert_eq!(waypoints[1].y, DEFAULT_Y);
+        assert!(!waypoints[1].enabled);
+        assert!(waypoints[1].death);
+        assert!(waypoints[1].is_in_dimension("the_nether"));
+    }
+
+    #[test]
+    fn writes_what_it_reads() {
+        let mut waypoint = Waypoint::new("a:b", -1, 2, 3);
+        waypoint.color = XAERO_COLORS[6];
+        waypoint.enabled = false;
+        let waypoints = vec![waypoint];
+        assert_eq!(
+            parse_xaero(&write_xaero(&waypoints), OVERWORLD).unwrap(),
+            waypoints
+        );
+        assert!(get_xaero_losses(&waypoints[0]).is_empty());
+    }
+
+    #[test]
+    fn maps_dimension_dirs() {
+        for dimension in &[OVERWORLD, NETHER, END, "mymod:moon"] {
+            let dir = get_xaero_dimension_dir(dimension);
+            assert_eq!(get_dimension_of_xaero_dir(&dir).unwrap(), *dimension);
+        }
+        assert_eq!(
+            get_xaero_world_map_dir("minecraft:the_nether"),
+            Some("DIM-1")
+        );
+        assert_eq!(get_xaero_world_map_dir("0"), Some("null"));
+        assert_eq!(get_xaero_world_map_dir("mymod:moon"), None);
+    }
+}
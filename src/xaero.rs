@@ -0,0 +1,687 @@
+extern crate zip;
+
+use crate::get_block_name_from_voxelmap;
+use crate::mc::blocks::get_legacy_block_state;
+use crate::tile::{column_nr_of_pos, Tile, TilePos};
+use crate::{TILE_HEIGHT, TILE_WIDTH};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub const XAERO_REGION_WIDTH: usize = 512;
+/// a region has 8x8 chunks, each of 4x4 tiles, each of 16x16 pixels
+pub const XAERO_REGION_CHUNKS: usize = 8;
+pub const XAERO_CHUNK_TILES: usize = 4;
+pub const XAERO_TILE_PIXELS: usize = 16;
+pub const XAERO_CHUNK_WIDTH: usize = XAERO_CHUNK_TILES * XAERO_TILE_PIXELS;
+
+/// Xaero's legacy block state id for `minecraft:grass`, which it doesn't write out.
+pub const XAERO_GRASS_STATE: u32 = 2;
+
+pub const XAERO_REGION_VERSION: i32 = 1;
+
+/// One block column, see `xaero-format.md`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XaeroPixel {
+    /// legacy block state id: `meta << 12 | id`
+    pub state: u32,
+    /// y of the block
+    pub height: u8,
+    /// block light above the block
+    pub light: u8,
+    pub biome: Option<u8>,
+    /// topmost first
+    pub overlays: Vec<XaeroOverlay>,
+    pub color_type: u8,
+    pub height_shade: u8,
+    pub custom_color: Option<u32>,
+}
+
+/// A see-through block above a pixel, such as water or glass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XaeroOverlay {
+    /// None for water
+    pub state: Option<u32>,
+    /// how many of these blocks are stacked
+    pub opacity: u32,
+    pub light: u8,
+    pub color_type: u8,
+    pub custom_color: Option<u32>,
+}
+
+/// Pixels are indexed `x + z * XAERO_REGION_WIDTH` within the region,
+/// None where Xaero has no data.
+pub struct XaeroRegion {
+    pub pos: Option<TilePos>,
+    pub pixels: Vec<Option<XaeroPixel>>,
+}
+
+impl XaeroRegion {
+    pub fn new(pos: Option<TilePos>) -> Self {
+        Self {
+            pos,
+            pixels: vec![None; XAERO_REGION_WIDTH * XAERO_REGION_WIDTH],
+        }
+    }
+
+    pub fn get_pixel(&self, x: usize, z: usize) -> Option<&XaeroPixel> {
+        self.pixels[x + z * XAERO_REGION_WIDTH].as_ref()
+    }
+}
+
+/// Reads a `<x>_<z>.zip` file containing a `region.xaero`.
+pub fn read_xaero_region(region_path: &PathBuf) -> Result<XaeroRegion, String> {
+    let zip_file = fs::File::open(region_path).map_err(|e| e.to_string())?;
+    let mut zip_archive = zip::ZipArchive::new(zip_file).map_err(|e| e.to_string())?;
+    let mut data = Vec::new();
+    zip_archive
+        .by_name("region.xaero")
+        .map_err(|_e| "No region.xaero in region zip")?
+        .read_to_end(&mut data)
+        .map_err(|e| e.to_string())?;
+    let mut region = parse_xaero_region(&data)?;
+    region.pos = get_xz_from_xaero_path(region_path).ok();
+    Ok(region)
+}
+
+pub fn parse_xaero_region(data: &[u8]) -> Result<XaeroRegion, String> {
+    let mut region = XaeroRegion::new(None);
+    let mut cursor = Cursor::new(data);
+
+    // the prelude is 0xff followed by the version,
+    // some files seem to have a 0x00 before it
+    let prelude_len = match data {
+        [0xff, ..] => 1,
+        [0x00, 0xff, ..] => 2,
+        _ => 0,
+    };
+    if prelude_len > 0 {
+        cursor.set_position(prelude_len);
+        let version = read_int(&mut cursor)?;
+        if version != XAERO_REGION_VERSION {
+            return Err(format!("Unsupported region version {}", version));
+        }
+    }
+
+    while (cursor.position() as usize) < data.len() {
+        let chunk_coords = cursor.read_u8().map_err(|e| e.to_string())? as usize;
+        let (chunk_x, chunk_z) = (chunk_coords >> 4, chunk_coords & 0xf);
+        if chunk_x >= XAERO_REGION_CHUNKS || chunk_z >= XAERO_REGION_CHUNKS {
+            return Err(format!("Invalid chunk coords {:#x}", chunk_coords));
+        }
+        // arrays are indexed [x][z], so z changes fastest
+        for tile_nr in 0..XAERO_CHUNK_TILES * XAERO_CHUNK_TILES {
+            let tile_x =
+                chunk_x * XAERO_CHUNK_WIDTH + tile_nr / XAERO_CHUNK_TILES * XAERO_TILE_PIXELS;
+            let tile_z =
+                chunk_z * XAERO_CHUNK_WIDTH + tile_nr % XAERO_CHUNK_TILES * XAERO_TILE_PIXELS;
+            let start = cursor.position();
+            if read_int(&mut cursor)? == -1 {
+                continue; // absent tile
+            }
+            cursor.set_position(start);
+            for pixel_nr in 0..XAERO_TILE_PIXELS * XAERO_TILE_PIXELS {
+                let x = tile_x + pixel_nr / XAERO_TILE_PIXELS;
+                let z = tile_z + pixel_nr % XAERO_TILE_PIXELS;
+                let pixel =
+                    read_pixel(&mut cursor).map_err(|e| format!("pixel {},{}: {}", x, z, e))?;
+                region.pixels[x + z * XAERO_REGION_WIDTH] = Some(pixel);
+            }
+        }
+    }
+
+    Ok(region)
+}
+
+fn read_int(cursor: &mut Cursor<&[u8]>) -> Result<i32, String> {
+    cursor.read_i32::<BigEndian>().map_err(|e| e.to_string())
+}
+
+fn read_pixel(cursor: &mut Cursor<&[u8]>) -> Result<XaeroPixel, String> {
+    let params = read_int(cursor)? as u32;
+    let not_grass = params & 1 != 0;
+    let has_overlays = params >> 1 & 1 != 0;
+    let color_type = (params >> 2 & 0b11) as u8;
+    let height_in_extra_byte = params >> 6 & 1 != 0;
+    let has_biome = params >> 20 & 1 != 0;
+
+    let state = if not_grass {
+        read_int(cursor)? as u32
+    } else {
+        XAERO_GRASS_STATE
+    };
+    let mut overlays = Vec::new();
+    if has_overlays {
+        let num_overlays = cursor.read_u8().map_err(|e| e.to_string())?;
+        for _ in 0..num_overlays {
+            overlays.push(read_overlay(cursor)?);
+        }
+    }
+    let custom_color = if color_type == 3 {
+        Some(read_int(cursor)? as u32)
+    } else {
+        None
+    };
+    let height = if height_in_extra_byte {
+        cursor.read_u8().map_err(|e| e.to_string())?
+    } else {
+        (params >> 12 & 0xff) as u8
+    };
+    let biome = if has_biome {
+        Some(cursor.read_u8().map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    Ok(XaeroPixel {
+        state,
+        height,
+        light: (params >> 8 & 0xf) as u8,
+        biome,
+        overlays,
+        color_type,
+        height_shade: (params >> 4 & 0b11) as u8,
+        custom_color,
+    })
+}
+
+fn read_overlay(cursor: &mut Cursor<&[u8]>) -> Result<XaeroOverlay, String> {
+    let params = read_int(cursor)? as u32;
+    let not_water = params & 1 != 0;
+    let has_opacity = params >> 3 & 1 != 0;
+    let color_type = (params >> 8 & 0b11) as u8;
+
+    let state = if not_water {
+        Some(read_int(cursor)? as u32)
+    } else {
+        None
+    };
+    let custom_color = if color_type == 2 {
+        Some(read_int(cursor)? as u32)
+    } else {
+        None
+    };
+    let opacity = if has_opacity {
+        read_int(cursor)? as u32
+    } else {
+        1
+    };
+
+    Ok(XaeroOverlay {
+        state,
+        opacity,
+        light: (params >> 4 & 0xf) as u8,
+        color_type,
+        custom_color,
+    })
+}
+
+/// Xaero names region files `<x>_<z>.zip`.
+pub fn get_xz_from_xaero_path(region_path: &Path) -> Result<TilePos, String> {
+    let stem = region_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or("no file name".to_owned())?;
+    let mut it = stem.splitn(2, '_');
+    let mut next_coord = |name: &str| -> Result<i32, String> {
+        it.next()
+            .ok_or(format!("no {} coord in filename", name))?
+            .parse()
+            .map_err(|e: std::num::ParseIntError| e.to_string())
+    };
+    Ok((next_coord("x")?, next_coord("z")?))
+}
+
+/// Name of a legacy block state, as used in VoxelMap's keys.
+pub fn get_xaero_block_name(state: u32) -> &'static str {
+    get_block_name_from_voxelmap((state >> 8) as u8, state as u8)
+}
+
+/// Splits the region into the four VoxelMap tiles it covers, in z-then-x order.
+/// Tiles without any data are left out.
+///
+/// The pixel's block goes into the surface layer, unless it is covered by water,
+/// then it goes into the ocean floor layer and the water into the surface layer.
+/// The topmost other overlay, such as glass, goes into the transparent layer.
+/// Xaero doesn't store the height of overlays, so it is assumed that they are
+/// stacked right on top of the pixel's block.
+/// Sky light isn't stored either, so it is always 15.
+pub fn xaero_region_to_tiles(region: &XaeroRegion) -> Vec<Tile> {
+    let tiles_per_region = XAERO_REGION_WIDTH / TILE_WIDTH;
+    let mut tiles = Vec::new();
+    for tile_dz in 0..tiles_per_region {
+        for tile_dx in 0..tiles_per_region {
+            let mut tile = Tile::new(region.pos.map(|(region_x, region_z)| {
+                (
+                    region_x * tiles_per_region as i32 + tile_dx as i32,
+                    region_z * tiles_per_region as i32 + tile_dz as i32,
+                )
+            }));
+            let mut has_data = false;
+            for z in 0..TILE_HEIGHT {
+                for x in 0..TILE_WIDTH {
+                    let region_x = tile_dx * TILE_WIDTH + x;
+                    let region_z = tile_dz * TILE_HEIGHT + z;
+                    if let Some(pixel) = region.get_pixel(region_x, region_z) {
+                        set_column_from_pixel(&mut tile, column_nr_of_pos(x, z), pixel);
+                        has_data = true;
+                    }
+                }
+            }
+            if has_data {
+                tiles.push(tile);
+            }
+        }
+    }
+    tiles
+}
+
+fn set_column_from_pixel(tile: &mut Tile, column_nr: usize, pixel: &XaeroPixel) {
+    let light = |block_light: u8| block_light & 0xf | 0xf0;
+    // VoxelMap stores the height above the block
+    let ground_height = pixel.height as i32 + 1;
+    let ground_id = tile.get_or_add_key(get_xaero_block_name(pixel.state));
+
+    // from the bottom up, to calculate the overlay heights
+    let mut overlay_height = ground_height;
+    let mut water = None;
+    let mut transparent = None;
+    for overlay in pixel.overlays.iter().rev() {
+        overlay_height += overlay.opacity.min(255) as i32;
+        match overlay.state {
+            None => water = Some((overlay_height, overlay.light)),
+            Some(state) => transparent = Some((overlay_height, state, overlay.light)),
+        }
+    }
+
+    match water {
+        Some((water_height, water_light)) => {
+            let water_id = tile.get_or_add_key("minecraft:water[level=0]");
+            tile.set_height(column_nr, water_height);
+            tile.set_blockstate(column_nr, water_id);
+            tile.set_light(column_nr, light(water_light));
+            tile.set_ocean_floor_height(column_nr, ground_height);
+            tile.set_ocean_floor_blockstate(column_nr, ground_id);
+            tile.set_ocean_floor_light(column_nr, light(pixel.light));
+        }
+        None => {
+            tile.set_height(column_nr, ground_height);
+            tile.set_blockstate(column_nr, ground_id);
+            tile.set_light(column_nr, light(pixel.light));
+        }
+    }
+    if let Some((height, state, overlay_light)) = transparent {
+        let id = tile.get_or_add_key(get_xaero_block_name(state));
+        tile.set_transparent_height(column_nr, height);
+        tile.set_transparent_blockstate(column_nr, id);
+        tile.set_transparent_light(column_nr, light(overlay_light));
+    }
+    tile.set_biome_id(column_nr, pixel.biome.unwrap_or(0) as u16);
+}
+
+/// Writes the region as `<x>_<z>.zip` containing a `region.xaero`.
+pub fn write_xaero_region(region_path: &PathBuf, region: &XaeroRegion) -> Result<(), String> {
+    let zip_file = fs::File::create(region_path).map_err(|e| e.to_string())?;
+    let mut zip_archive = zip::ZipWriter::new(zip_file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip_archive
+        .start_file("region.xaero", options)
+        .map_err(|e| e.to_string())?;
+    zip_archive
+        .write_all(&encode_xaero_region(region))
+        .map_err(|e| e.to_string())?;
+    zip_archive.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// The inverse of `parse_xaero_region`.
+/// Tiles with some pixels missing get air pixels there, as Xaero can't leave them out.
+pub fn encode_xaero_region(region: &XaeroRegion) -> Vec<u8> {
+    let empty_pixel = XaeroPixel {
+        state: 0,
+        height: 0,
+        light: 0,
+        biome: None,
+        overlays: Vec::new(),
+        color_type: 0,
+        height_shade: 0,
+        custom_color: None,
+    };
+    let mut data = vec![0xff];
+    data.write_i32::<BigEndian>(XAERO_REGION_VERSION).unwrap();
+    for chunk_x in 0..XAERO_REGION_CHUNKS {
+        for chunk_z in 0..XAERO_REGION_CHUNKS {
+            let tile_origin = |tile_nr: usize| {
+                (
+                    chunk_x * XAERO_CHUNK_WIDTH + tile_nr / XAERO_CHUNK_TILES * XAERO_TILE_PIXELS,
+                    chunk_z * XAERO_CHUNK_WIDTH + tile_nr % XAERO_CHUNK_TILES * XAERO_TILE_PIXELS,
+                )
+            };
+            let pixel_pos = |(tile_x, tile_z): (usize, usize), pixel_nr: usize| {
+                (
+                    tile_x + pixel_nr / XAERO_TILE_PIXELS,
+                    tile_z + pixel_nr % XAERO_TILE_PIXELS,
+                )
+            };
+            let num_pixels = XAERO_TILE_PIXELS * XAERO_TILE_PIXELS;
+            let is_tile_present = |tile_nr: usize| {
+                (0..num_pixels).any(|pixel_nr| {
+                    let (x, z) = pixel_pos(tile_origin(tile_nr), pixel_nr);
+                    region.get_pixel(x, z).is_some()
+                })
+            };
+            let num_tiles = XAERO_CHUNK_TILES * XAERO_CHUNK_TILES;
+            if !(0..num_tiles).any(is_tile_present) {
+                continue;
+            }
+            data.push((chunk_x << 4 | chunk_z) as u8);
+            for tile_nr in 0..num_tiles {
+                if !is_tile_present(tile_nr) {
+                    data.write_i32::<BigEndian>(-1).unwrap();
+                    continue;
+                }
+                for pixel_nr in 0..num_pixels {
+                    let (x, z) = pixel_pos(tile_origin(tile_nr), pixel_nr);
+                    let pixel = region.get_pixel(x, z).unwrap_or(&empty_pixel);
+                    write_pixel(&mut data, pixel);
+                }
+            }
+        }
+    }
+    data
+}
+
+fn write_pixel(data: &mut Vec<u8>, pixel: &XaeroPixel) {
+    let not_grass = pixel.state != XAERO_GRASS_STATE;
+    let params = not_grass as u32
+        | (!pixel.overlays.is_empty() as u32) << 1
+        | (pixel.color_type as u32 & 0b11) << 2
+        | (pixel.height_shade as u32 & 0b11) << 4
+        | (pixel.light as u32 & 0xf) << 8
+        | (pixel.height as u32) << 12
+        | (pixel.biome.is_some() as u32) << 20;
+    data.write_u32::<BigEndian>(params).unwrap();
+    if not_grass {
+        data.write_u32::<BigEndian>(pixel.state).unwrap();
+    }
+    if !pixel.overlays.is_empty() {
+        data.push(pixel.overlays.len() as u8);
+        for overlay in &pixel.overlays {
+            write_overlay(data, overlay);
+        }
+    }
+    if pixel.color_type == 3 {
+        data.write_u32::<BigEndian>(pixel.custom_color.unwrap_or(0))
+            .unwrap();
+    }
+    if let Some(biome) = pixel.biome {
+        data.push(biome);
+    }
+}
+
+fn write_overlay(data: &mut Vec<u8>, overlay: &XaeroOverlay) {
+    let params = overlay.state.is_some() as u32
+        | ((overlay.opacity > 1) as u32) << 3
+        | (overlay.light as u32 & 0xf) << 4
+        | (overlay.color_type as u32 & 0b11) << 8;
+    data.write_u32::<BigEndian>(params).unwrap();
+    if let Some(state) = overlay.state {
+        data.write_u32::<BigEndian>(state).unwrap();
+    }
+    if overlay.color_type == 2 {
+        data.write_u32::<BigEndian>(overlay.custom_color.unwrap_or(0))
+            .unwrap();
+    }
+    if overlay.opacity > 1 {
+        data.write_u32::<BigEndian>(overlay.opacity).unwrap();
+    }
+}
+
+/// The Xaero region containing a tile.
+pub fn get_xaero_region_pos_of_tile((tile_x, tile_z): TilePos) -> TilePos {
+    let tiles_per_region = (XAERO_REGION_WIDTH / TILE_WIDTH) as i32;
+    (
+        tile_x.div_euclid(tiles_per_region),
+        tile_z.div_euclid(tiles_per_region),
+    )
+}
+
+/// Combines up to four tiles into the region at `region_pos`, the inverse of `xaero_region_to_tiles`.
+/// Tiles outside of the region are ignored.
+/// Water on the surface becomes an overlay on the ocean floor block,
+/// and a transparent block above the surface becomes an overlay on top of that.
+/// Also returns the names of blocks that have no legacy block state, they become air,
+/// and the number of columns whose height was clamped to y=0..=255, such as in 1.18+ worlds.
+pub fn tiles_to_xaero_region(
+    region_pos: TilePos,
+    tiles: &[Box<Tile>],
+) -> (XaeroRegion, BTreeSet<String>, usize) {
+    let mut region = XaeroRegion::new(Some(region_pos));
+    let mut unknown_blocks = BTreeSet::new();
+    let mut clamped_columns = 0;
+    for tile in tiles {
+        let (tile_x, tile_z) = match tile.pos {
+            Some(pos) if get_xaero_region_pos_of_tile(pos) == region_pos => pos,
+            _ => continue,
+        };
+        let tiles_per_region = (XAERO_REGION_WIDTH / TILE_WIDTH) as i32;
+        let offset_x = tile_x.rem_euclid(tiles_per_region) as usize * TILE_WIDTH;
+        let offset_z = tile_z.rem_euclid(tiles_per_region) as usize * TILE_HEIGHT;
+        for z in 0..TILE_HEIGHT {
+            for x in 0..TILE_WIDTH {
+                let column_nr = column_nr_of_pos(x, z);
+                if tile.is_col_empty(column_nr) {
+                    continue;
+                }
+                let pixel = get_pixel_from_column(
+                    tile,
+                    column_nr,
+                    &mut unknown_blocks,
+                    &mut clamped_columns,
+                );
+                region.pixels[offset_x + x + (offset_z + z) * XAERO_REGION_WIDTH] = Some(pixel);
+            }
+        }
+    }
+    (region, unknown_blocks, clamped_columns)
+}
+
+fn get_pixel_from_column(
+    tile: &Tile,
+    column_nr: usize,
+    unknown_blocks: &mut BTreeSet<String>,
+    clamped_columns: &mut usize,
+) -> XaeroPixel {
+    let mut get_state = |block_nr: u16| {
+        let name = tile.get_block_name(block_nr);
+        get_legacy_block_state(name)
+            .map(|state| state as u32)
+            .unwrap_or_else(|| {
+                unknown_blocks.insert(name.to_string());
+                0
+            })
+    };
+    // Xaero stores the block's y in a byte, VoxelMap the height above it
+    let mut xaero_height = |height: i32| {
+        let y = height - 1;
+        if !(0..=255).contains(&y) {
+            *clamped_columns += 1;
+        }
+        y.clamp(0, 255) as u8
+    };
+
+    let surface_name = tile.get_block_name(tile.get_blockstate(column_nr));
+    let is_water = surface_name.starts_with("minecraft:water")
+        || surface_name.starts_with("minecraft:flowing_water");
+    let height = tile.get_height(column_nr);
+
+    let mut overlays = Vec::new();
+    let transparent_height = tile.get_transparent_height(column_nr);
+    let transparent_block = tile.get_transparent_blockstate(column_nr);
+    if transparent_block != 0 && transparent_height > height {
+        overlays.push(XaeroOverlay {
+            state: Some(get_state(transparent_block)),
+            opacity: 1,
+            light: tile.get_transparent_light(column_nr) & 0xf,
+            color_type: 0,
+            custom_color: None,
+        });
+    }
+
+    let (state, ground_height, light) = if is_water {
+        let floor_height = tile.get_ocean_floor_height(column_nr);
+        overlays.push(XaeroOverlay {
+            state: None,
+            opacity: (height - floor_height).max(1) as u32,
+            light: tile.get_light(column_nr) & 0xf,
+            color_type: 0,
+            custom_color: None,
+        });
+        (
+            get_state(tile.get_ocean_floor_blockstate(column_nr)),
+            floor_height,
+            tile.get_ocean_floor_light(column_nr),
+        )
+    } else {
+        (
+            get_state(tile.get_blockstate(column_nr)),
+            height,
+            tile.get_light(column_nr),
+        )
+    };
+
+    XaeroPixel {
+        state,
+        height: xaero_height(ground_height),
+        light: light & 0xf,
+        biome: tile.get_biome(column_nr).and_then(|biome| biome.legacy_id),
+        overlays,
+        color_type: 0,
+        height_shade: 0,
+        custom_color: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_int(data: &mut Vec<u8>, value: i32) {
+        data.extend_from_slice(&value.to_be_bytes());
+    }
+
+    #[test]
+    fn reads_region_into_tiles() {
+        let mut data = vec![0xff];
+        push_int(&mut data, 1);
+        data.push(1 << 4 | 2); // chunk x=1 z=2
+        for tile_nr in 0..16 {
+            if tile_nr != 1 {
+                push_int(&mut data, -1);
+                continue;
+            }
+            for _pixel_nr in 0..256 {
+                // not grass, has overlays, light 5, height 62, has biome
+                push_int(&mut data, 1 | 1 << 1 | 5 << 8 | 62 << 12 | 1 << 20);
+                push_int(&mut data, 12); // sand
+                data.push(1);
+                push_int(&mut data, 1 << 3); // water, 3 deep
+                push_int(&mut data, 3);
+                data.push(24); // deep ocean
+            }
+        }
+
+        let mut region = parse_xaero_region(&data).unwrap();
+        // tile 1 is x=0 z=1 within the chunk
+        assert!(region.get_pixel(64, 64 * 2 + 15).is_none());
+        let pixel = region.get_pixel(64 + 15, 64 * 2 + 16).unwrap();
+        assert_eq!((pixel.state, pixel.height, pixel.light), (12, 62, 5));
+        assert_eq!(pixel.overlays[0].state, None);
+        assert_eq!(pixel.overlays[0].opacity, 3);
+
+        region.pos = Some((-1, 0));
+        let tiles = xaero_region_to_tiles(&region);
+        assert_eq!(tiles.len(), 1);
+        let tile = &tiles[0];
+        assert_eq!(tile.pos, Some((-2, 0)));
+        let column_nr = column_nr_of_pos(64 + 15, 64 * 2 + 16);
+        assert_eq!(tile.get_height(column_nr), 66);
+        assert_eq!(
+            tile.get_block_name(tile.get_blockstate(column_nr)),
+            "minecraft:water[level=0]"
+        );
+        assert_eq!(tile.get_ocean_floor_height(column_nr), 63);
+        assert_eq!(
+            tile.get_block_name(tile.get_ocean_floor_blockstate(column_nr)),
+            "minecraft:sand[variant=sand]"
+        );
+        assert_eq!(tile.get_ocean_floor_light(column_nr), 0xf5);
+        assert_eq!(tile.get_biome_id(column_nr), 24);
+        assert!(tile.is_col_empty(column_nr_of_pos(0, 0)));
+    }
+
+    #[test]
+    fn writes_what_it_reads() {
+        let mut tile = Tile::new(Some((-1, 3)));
+        let glass = tile.get_or_add_key("minecraft:glass");
+        let water = tile.get_or_add_key("minecraft:water[level=0]");
+        let grass = tile.get_or_add_key("minecraft:grass[snowy=false]");
+        let sand = tile.get_or_add_key("minecraft:sand[variant=sand]");
+        let (land, sea) = (column_nr_of_pos(3, 4), column_nr_of_pos(200, 255));
+        tile.set_height(land, 70);
+        tile.set_blockstate(land, grass);
+        tile.set_light(land, 0xf3);
+        tile.set_transparent_height(land, 80);
+        tile.set_transparent_blockstate(land, glass);
+        tile.set_transparent_light(land, 0xf0);
+        tile.set_biome_id(land, 4);
+        tile.set_height(sea, 63);
+        tile.set_blockstate(sea, water);
+        tile.set_light(sea, 0xf0);
+        tile.set_ocean_floor_height(sea, 40);
+        tile.set_ocean_floor_blockstate(sea, sand);
+        tile.set_ocean_floor_light(sea, 0xf0);
+        let deep = column_nr_of_pos(4, 4);
+        tile.set_height(deep, -20);
+        tile.set_blockstate(deep, grass);
+
+        let (region, unknown_blocks, clamped_columns) =
+            tiles_to_xaero_region((-1, 1), &[Box::new(tile)]);
+        assert!(unknown_blocks.is_empty());
+        assert_eq!(clamped_columns, 1);
+        let mut read_region = parse_xaero_region(&encode_xaero_region(&region)).unwrap();
+        // only the two columns' 16x16 xaero tiles are present
+        let num_present = read_region.pixels.iter().filter(|p| p.is_some()).count();
+        assert_eq!(num_present, 2 * 256);
+        read_region.pos = region.pos;
+        let tiles = xaero_region_to_tiles(&read_region);
+        assert_eq!(tiles.len(), 1);
+        let tile = &tiles[0];
+        assert_eq!(tile.pos, Some((-1, 3)));
+        let name = |block_nr| tile.get_block_name(block_nr);
+        assert_eq!(
+            name(tile.get_blockstate(land)),
+            "minecraft:grass[snowy=false]"
+        );
+        assert_eq!(tile.get_height(land), 70);
+        assert_eq!(tile.get_light(land), 0xf3);
+        assert_eq!(
+            name(tile.get_transparent_blockstate(land)),
+            "minecraft:glass"
+        );
+        // xaero doesn't know the height of the glass
+        assert_eq!(tile.get_transparent_height(land), 71);
+        assert_eq!(tile.get_biome_id(land), 4);
+        assert_eq!(name(tile.get_blockstate(sea)), "minecraft:water[level=0]");
+        assert_eq!(tile.get_height(sea), 63);
+        assert_eq!(
+            name(tile.get_ocean_floor_blockstate(sea)),
+            "minecraft:sand[variant=sand]"
+        );
+        assert_eq!(tile.get_ocean_floor_height(sea), 40);
+        assert_eq!(tile.get_height(deep), 1);
+    }
+}